@@ -5,7 +5,7 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
+use syn::{parse_macro_input, Data, DeriveInput, Field, Fields, Type};
 
 /// Derive macro for automatically implementing the `Trackable` trait.
 ///
@@ -35,18 +35,60 @@ use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
 /// - Unit structs
 /// - Enums with data
 /// - Nested types that implement `Trackable`
+///
+/// Individual fields (including fields inside enum variants) can be
+/// customized with a `#[trackable(...)]` attribute:
+/// - `#[trackable(skip)]` excludes the field entirely.
+/// - `#[trackable(rename = "label")]` overrides the label used in the
+///   allocation path.
+/// - `#[trackable(size_with = path::to::fn)]` uses a custom `fn(&T) -> usize`
+///   to estimate the size of a field that doesn't implement `Trackable`.
+///
+/// The container itself (the struct or enum, not a field) can be annotated
+/// with `#[trackable(strict)]` to turn the "unrecognized field type" case
+/// from silent undercounting into a compile error: every field must then be
+/// a primitive, a recognized container (see `is_potentially_heap_allocated`),
+/// or explicitly annotated with `#[trackable(skip)]` or
+/// `#[trackable(size_with = ...)]`.
 #[proc_macro_derive(Trackable)]
 pub fn derive_trackable(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
     let generics = &input.generics;
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let type_params: Vec<syn::Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+
+    let container_config = match parse_container_config(&input.attrs) {
+        Ok(config) => config,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     let expanded = match &input.data {
         Data::Struct(data_struct) => {
-            let heap_ptr_impl = generate_heap_ptr_impl(&data_struct.fields);
-            let size_estimate_impl = generate_size_estimate_impl(&data_struct.fields);
-            let internal_allocations_impl = generate_internal_allocations_impl(&data_struct.fields);
+            if container_config.strict {
+                if let Err(err) = check_strict_fields(&data_struct.fields) {
+                    return err.to_compile_error().into();
+                }
+            }
+            let heap_ptr_impl = match generate_heap_ptr_impl(&data_struct.fields) {
+                Ok(tokens) => tokens,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            let size_estimate_impl = match generate_size_estimate_impl(&data_struct.fields) {
+                Ok(tokens) => tokens,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            let internal_allocations_impl =
+                match generate_internal_allocations_impl(&data_struct.fields) {
+                    Ok(tokens) => tokens,
+                    Err(err) => return err.to_compile_error().into(),
+                };
+            let used_params = match collect_used_type_params(&data_struct.fields, &type_params) {
+                Ok(used) => used,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            let where_clause = augment_where_clause_for_trackable(generics, &used_params);
+            let type_name_impl = generate_type_name_impl(name, &type_params);
 
             quote! {
                 impl #impl_generics memscope_rs::Trackable for #name #ty_generics #where_clause {
@@ -55,7 +97,7 @@ pub fn derive_trackable(input: TokenStream) -> TokenStream {
                     }
 
                     fn get_type_name(&self) -> &'static str {
-                        stringify!(#name)
+                        #type_name_impl
                     }
 
                     fn get_size_estimate(&self) -> usize {
@@ -69,19 +111,42 @@ pub fn derive_trackable(input: TokenStream) -> TokenStream {
             }
         }
         Data::Enum(data_enum) => {
-            let size_estimate_impl = generate_enum_size_estimate_impl(&data_enum.variants);
+            if container_config.strict {
+                for variant in &data_enum.variants {
+                    if let Err(err) = check_strict_fields(&variant.fields) {
+                        return err.to_compile_error().into();
+                    }
+                }
+            }
+            let heap_ptr_impl = match generate_enum_heap_ptr_impl(&data_enum.variants) {
+                Ok(tokens) => tokens,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            let size_estimate_impl = match generate_enum_size_estimate_impl(&data_enum.variants) {
+                Ok(tokens) => tokens,
+                Err(err) => return err.to_compile_error().into(),
+            };
             let internal_allocations_impl =
-                generate_enum_internal_allocations_impl(&data_enum.variants);
+                match generate_enum_internal_allocations_impl(&data_enum.variants) {
+                    Ok(tokens) => tokens,
+                    Err(err) => return err.to_compile_error().into(),
+                };
+            let used_params =
+                match collect_used_type_params_in_variants(&data_enum.variants, &type_params) {
+                    Ok(used) => used,
+                    Err(err) => return err.to_compile_error().into(),
+                };
+            let where_clause = augment_where_clause_for_trackable(generics, &used_params);
+            let type_name_impl = generate_type_name_impl(name, &type_params);
 
             quote! {
                 impl #impl_generics memscope_rs::Trackable for #name #ty_generics #where_clause {
                     fn get_heap_ptr(&self) -> Option<usize> {
-                        // For enums, use the enum instance address
-                        Some(self as *const _ as usize)
+                        #heap_ptr_impl
                     }
 
                     fn get_type_name(&self) -> &'static str {
-                        stringify!(#name)
+                        #type_name_impl
                     }
 
                     fn get_size_estimate(&self) -> usize {
@@ -108,133 +173,390 @@ pub fn derive_trackable(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Generate the `get_type_name` implementation.
+///
+/// Non-generic types resolve to a module-qualified name built entirely at
+/// compile time via `concat!`/`stringify!`/`module_path!`, so `foo::Config`
+/// and `bar::Config` no longer collapse to the same `"Config"`. Generic
+/// types additionally need each type parameter's monomorphized name (e.g.
+/// `Wrapper<String>` vs `Wrapper<Vec<u8>>`), which can only be computed at
+/// runtime via `std::any::type_name`. A `static` inside a generic function is
+/// shared by every monomorphization, so the built strings are cached in one
+/// process-wide map keyed by `std::any::type_name::<Self>()`; each is built
+/// and leaked once per monomorphization so the signature can still return
+/// `&'static str`.
+fn generate_type_name_impl(
+    name: &syn::Ident,
+    type_params: &[syn::Ident],
+) -> proc_macro2::TokenStream {
+    if type_params.is_empty() {
+        return quote! {
+            concat!(module_path!(), "::", stringify!(#name))
+        };
+    }
+
+    quote! {
+        static TYPE_NAMES: std::sync::OnceLock<
+            std::sync::Mutex<std::collections::HashMap<&'static str, &'static str>>,
+        > = std::sync::OnceLock::new();
+        let mut names = TYPE_NAMES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let type_name: &'static str = *names
+            .entry(std::any::type_name::<Self>())
+            .or_insert_with(|| {
+                let params: &[&str] = &[#(std::any::type_name::<#type_params>()),*];
+                let built: &'static str = Box::leak(
+                    format!(
+                        "{}<{}>",
+                        concat!(module_path!(), "::", stringify!(#name)),
+                        params.join(", ")
+                    )
+                    .into_boxed_str(),
+                );
+                built
+            });
+        type_name
+    }
+}
+
 /// Generate the `get_heap_ptr` implementation for structs
-fn generate_heap_ptr_impl(fields: &Fields) -> proc_macro2::TokenStream {
+fn generate_heap_ptr_impl(fields: &Fields) -> syn::Result<proc_macro2::TokenStream> {
     match fields {
         Fields::Named(_) | Fields::Unnamed(_) => {
-            // Check if any field has heap allocations
-            let has_heap_fields = has_potential_heap_allocations(fields);
+            // Check if any non-skipped field has heap allocations
+            let has_heap_fields = has_potential_heap_allocations(fields)?;
 
             if has_heap_fields {
-                quote! {
+                Ok(quote! {
                     // Use the struct's address as the primary identifier
                     Some(self as *const _ as usize)
-                }
+                })
             } else {
-                quote! {
+                Ok(quote! {
                     // No heap allocations detected
                     None
-                }
+                })
             }
         }
-        Fields::Unit => {
-            quote! {
-                // Unit structs have no heap allocations
-                None
+        Fields::Unit => Ok(quote! {
+            // Unit structs have no heap allocations
+            None
+        }),
+    }
+}
+
+/// Container-level configuration parsed from `#[trackable(...)]` on the
+/// struct or enum itself, as opposed to [`FieldConfig`] on its fields.
+#[derive(Default)]
+struct ContainerConfig {
+    /// `#[trackable(strict)]`: reject unrecognized field types at derive
+    /// time instead of silently treating them as zero internal allocation.
+    strict: bool,
+}
+
+/// Parse the struct/enum's own `#[trackable(...)]` attributes, if any.
+fn parse_container_config(attrs: &[syn::Attribute]) -> syn::Result<ContainerConfig> {
+    let mut config = ContainerConfig::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("trackable") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("strict") {
+                config.strict = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown `trackable` container attribute key, expected `strict`"))
             }
+        })?;
+    }
+
+    Ok(config)
+}
+
+/// Per-field configuration parsed from `#[trackable(...)]`.
+///
+/// - `#[trackable(skip)]` excludes the field from both `get_size_estimate`
+///   and `get_internal_allocations`.
+/// - `#[trackable(rename = "label")]` overrides the string pushed into the
+///   allocation tuple in place of the field's own name.
+/// - `#[trackable(size_with = path::to::fn)]` replaces the
+///   `Trackable::get_size_estimate` call with `path::to::fn(&self.field)`,
+///   for opaque types that don't implement `Trackable`.
+#[derive(Default)]
+struct FieldConfig {
+    skip: bool,
+    rename: Option<String>,
+    size_with: Option<syn::Path>,
+}
+
+/// Parse a field's `#[trackable(...)]` attributes, if any, erroring on
+/// unknown keys or conflicting combinations (e.g. `skip` with `rename`).
+fn parse_field_config(field: &Field) -> syn::Result<FieldConfig> {
+    let mut config = FieldConfig::default();
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("trackable") {
+            continue;
         }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                config.skip = true;
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                config.rename = Some(lit.value());
+                Ok(())
+            } else if meta.path.is_ident("size_with") {
+                let path: syn::Path = meta.value()?.parse()?;
+                config.size_with = Some(path);
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unknown `trackable` attribute key, expected `skip`, `rename`, or `size_with`",
+                ))
+            }
+        })?;
     }
+
+    if config.skip && (config.rename.is_some() || config.size_with.is_some()) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "`#[trackable(skip)]` cannot be combined with `rename` or `size_with`",
+        ));
+    }
+
+    Ok(config)
 }
 
-/// Generate the `get_size_estimate` implementation
-fn generate_size_estimate_impl(fields: &Fields) -> proc_macro2::TokenStream {
+/// Generate the `get_size_estimate` implementation. Collection-typed fields
+/// (see [`classify_collection_shape`]) get element-wise accounting via
+/// [`generate_collection_aware_size`] in addition to the field's own
+/// `Trackable` call; everything else keeps a single call.
+fn generate_size_estimate_impl(fields: &Fields) -> syn::Result<proc_macro2::TokenStream> {
     match fields {
         Fields::Named(fields_named) => {
-            let field_sizes = fields_named.named.iter().map(|field| {
-                let field_name = &field.ident;
-                quote! {
-                    total_size += memscope_rs::Trackable::get_size_estimate(&self.#field_name);
+            let mut field_sizes = Vec::new();
+            for field in &fields_named.named {
+                let config = parse_field_config(field)?;
+                if config.skip {
+                    continue;
                 }
-            });
+                let field_name = &field.ident;
+                field_sizes.push(match config.size_with {
+                    Some(size_with) => quote! {
+                        total_size += #size_with(&self.#field_name);
+                    },
+                    None => generate_collection_aware_size(&field.ty, quote! { &self.#field_name }),
+                });
+            }
 
-            quote! {
+            Ok(quote! {
                 let mut total_size = std::mem::size_of::<Self>();
                 #(#field_sizes)*
                 total_size
-            }
+            })
         }
         Fields::Unnamed(fields_unnamed) => {
-            let field_sizes = fields_unnamed.unnamed.iter().enumerate().map(|(i, _)| {
-                let index = syn::Index::from(i);
-                quote! {
-                    total_size += memscope_rs::Trackable::get_size_estimate(&self.#index);
+            let mut field_sizes = Vec::new();
+            for (i, field) in fields_unnamed.unnamed.iter().enumerate() {
+                let config = parse_field_config(field)?;
+                if config.skip {
+                    continue;
                 }
-            });
+                let index = syn::Index::from(i);
+                field_sizes.push(match config.size_with {
+                    Some(size_with) => quote! {
+                        total_size += #size_with(&self.#index);
+                    },
+                    None => generate_collection_aware_size(&field.ty, quote! { &self.#index }),
+                });
+            }
 
-            quote! {
+            Ok(quote! {
                 let mut total_size = std::mem::size_of::<Self>();
                 #(#field_sizes)*
                 total_size
-            }
-        }
-        Fields::Unit => {
-            quote! {
-                std::mem::size_of::<Self>()
-            }
+            })
         }
+        Fields::Unit => Ok(quote! {
+            std::mem::size_of::<Self>()
+        }),
     }
 }
 
-/// Generate the `get_internal_allocations` implementation
-fn generate_internal_allocations_impl(fields: &Fields) -> proc_macro2::TokenStream {
+/// Generate the `get_internal_allocations` implementation. Fields with
+/// `#[trackable(size_with = ...)]` are opaque by definition (that's what the
+/// attribute is for), so they're excluded here rather than requiring
+/// `FieldType: Trackable` for a `get_heap_ptr` call.
+fn generate_internal_allocations_impl(fields: &Fields) -> syn::Result<proc_macro2::TokenStream> {
     match fields {
         Fields::Named(fields_named) => {
-            let field_allocations = fields_named.named.iter().map(|field| {
+            let mut field_allocations = Vec::new();
+            for field in &fields_named.named {
+                let config = parse_field_config(field)?;
+                if config.skip {
+                    continue;
+                }
                 let field_name = &field.ident;
-                let field_name_str = field_name.as_ref().unwrap().to_string();
-                quote! {
-                    if let Some(ptr) = memscope_rs::Trackable::get_heap_ptr(&self.#field_name) {
-                        allocations.push((ptr, format!("{var_name}::{}", #field_name_str)));
-                    }
+                let label = config
+                    .rename
+                    .unwrap_or_else(|| field_name.as_ref().unwrap().to_string());
+                if config.size_with.is_none() {
+                    field_allocations.push(quote! {
+                        if let Some(ptr) = memscope_rs::Trackable::get_heap_ptr(&self.#field_name) {
+                            allocations.push((ptr, format!("{var_name}::{}", #label)));
+                        }
+                    });
                 }
-            });
+            }
 
-            quote! {
+            Ok(quote! {
                 let mut allocations = Vec::new();
                 #(#field_allocations)*
                 allocations
-            }
+            })
         }
         Fields::Unnamed(fields_unnamed) => {
-            let field_allocations = fields_unnamed.unnamed.iter().enumerate().map(|(i, _)| {
+            let mut field_allocations = Vec::new();
+            for (i, field) in fields_unnamed.unnamed.iter().enumerate() {
+                let config = parse_field_config(field)?;
+                if config.skip {
+                    continue;
+                }
                 let index = syn::Index::from(i);
-                let index_str = i.to_string();
-                quote! {
-                    if let Some(ptr) = memscope_rs::Trackable::get_heap_ptr(&self.#index) {
-                        allocations.push((ptr, format!("{var_name}::{}", #index_str)));
-                    }
+                let label = config.rename.unwrap_or_else(|| i.to_string());
+                if config.size_with.is_none() {
+                    field_allocations.push(quote! {
+                        if let Some(ptr) = memscope_rs::Trackable::get_heap_ptr(&self.#index) {
+                            allocations.push((ptr, format!("{var_name}::{}", #label)));
+                        }
+                    });
                 }
-            });
+            }
 
-            quote! {
+            Ok(quote! {
                 let mut allocations = Vec::new();
                 #(#field_allocations)*
                 allocations
-            }
+            })
         }
-        Fields::Unit => {
-            quote! {
-                Vec::new()
+        Fields::Unit => Ok(quote! {
+            Vec::new()
+        }),
+    }
+}
+
+/// Generate the `get_heap_ptr` implementation for enums: returns the heap
+/// pointer of the first non-skipped field in the active variant whose type
+/// passes `is_potentially_heap_allocated`, or `None` for unit variants and
+/// variants made up entirely of inline or `#[trackable(skip)]`'d data,
+/// mirroring struct tracking instead of falling back to the enum's own stack
+/// address.
+fn generate_enum_heap_ptr_impl(
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut variant_arms = Vec::new();
+    for variant in variants {
+        let variant_name = &variant.ident;
+        let arm = match &variant.fields {
+            Fields::Named(fields) => {
+                let mut target = None;
+                for f in &fields.named {
+                    let config = parse_field_config(f)?;
+                    if !config.skip && is_potentially_heap_allocated(&f.ty) {
+                        target = Some(f);
+                        break;
+                    }
+                }
+                match target {
+                    Some(target) => {
+                        let target_name = &target.ident;
+                        quote! {
+                            Self::#variant_name { #target_name, .. } => {
+                                memscope_rs::Trackable::get_heap_ptr(#target_name)
+                            }
+                        }
+                    }
+                    None => quote! {
+                        Self::#variant_name { .. } => None
+                    },
+                }
             }
-        }
+            Fields::Unnamed(fields) => {
+                let mut target_index = None;
+                for (i, f) in fields.unnamed.iter().enumerate() {
+                    let config = parse_field_config(f)?;
+                    if !config.skip && is_potentially_heap_allocated(&f.ty) {
+                        target_index = Some(i);
+                        break;
+                    }
+                }
+                match target_index {
+                    Some(target_index) => {
+                        let target_name = syn::Ident::new("value", proc_macro2::Span::call_site());
+                        let patterns = (0..fields.unnamed.len()).map(|i| {
+                            if i == target_index {
+                                quote! { #target_name }
+                            } else {
+                                quote! { _ }
+                            }
+                        });
+                        quote! {
+                            Self::#variant_name(#(#patterns),*) => {
+                                memscope_rs::Trackable::get_heap_ptr(#target_name)
+                            }
+                        }
+                    }
+                    None => quote! {
+                        Self::#variant_name(..) => None
+                    },
+                }
+            }
+            Fields::Unit => quote! {
+                Self::#variant_name => None
+            },
+        };
+        variant_arms.push(arm);
     }
+
+    Ok(quote! {
+        match self {
+            #(#variant_arms),*
+        }
+    })
 }
 
-/// Generate size estimate for enums
+/// Generate size estimate for enums, with the same collection-aware
+/// per-element accounting as [`generate_size_estimate_impl`].
 fn generate_enum_size_estimate_impl(
     variants: &syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
-) -> proc_macro2::TokenStream {
-    let variant_arms = variants.iter().map(|variant| {
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut variant_arms = Vec::new();
+    for variant in variants {
         let variant_name = &variant.ident;
-        match &variant.fields {
+        let arm = match &variant.fields {
             Fields::Named(fields) => {
                 let field_names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
-                let field_sizes = fields.named.iter().map(|field| {
-                    let field_name = &field.ident;
-                    quote! {
-                        total_size += memscope_rs::Trackable::get_size_estimate(#field_name);
+                let mut field_sizes = Vec::new();
+                for field in &fields.named {
+                    let config = parse_field_config(field)?;
+                    if config.skip {
+                        continue;
                     }
-                });
+                    let field_name = &field.ident;
+                    field_sizes.push(match config.size_with {
+                        Some(size_with) => quote! {
+                            total_size += #size_with(#field_name);
+                        },
+                        None => generate_collection_aware_size(&field.ty, quote! { #field_name }),
+                    });
+                }
 
                 quote! {
                     Self::#variant_name { #(#field_names),* } => {
@@ -246,15 +568,21 @@ fn generate_enum_size_estimate_impl(
             }
             Fields::Unnamed(fields) => {
                 let field_patterns: Vec<_> = (0..fields.unnamed.len())
-                    .map(|i| {
-                        syn::Ident::new(&format!("field_{i}"), proc_macro2::Span::call_site())
-                    })
+                    .map(|i| syn::Ident::new(&format!("field_{i}"), proc_macro2::Span::call_site()))
                     .collect();
-                let field_sizes = field_patterns.iter().map(|field_name| {
-                    quote! {
-                        total_size += memscope_rs::Trackable::get_size_estimate(#field_name);
+                let mut field_sizes = Vec::new();
+                for (field, field_name) in fields.unnamed.iter().zip(&field_patterns) {
+                    let config = parse_field_config(field)?;
+                    if config.skip {
+                        continue;
                     }
-                });
+                    field_sizes.push(match config.size_with {
+                        Some(size_with) => quote! {
+                            total_size += #size_with(#field_name);
+                        },
+                        None => generate_collection_aware_size(&field.ty, quote! { #field_name }),
+                    });
+                }
 
                 quote! {
                     Self::#variant_name(#(#field_patterns),*) => {
@@ -269,35 +597,48 @@ fn generate_enum_size_estimate_impl(
                     Self::#variant_name => std::mem::size_of::<Self>()
                 }
             }
-        }
-    });
+        };
+        variant_arms.push(arm);
+    }
 
-    quote! {
+    Ok(quote! {
         match self {
             #(#variant_arms),*
         }
-    }
+    })
 }
 
-/// Generate internal allocations for enums
+/// Generate internal allocations for enums. As in
+/// [`generate_internal_allocations_impl`], `size_with` fields are excluded
+/// since they're opaque by definition.
 fn generate_enum_internal_allocations_impl(
     variants: &syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
-) -> proc_macro2::TokenStream {
-    let variant_arms = variants.iter().map(|variant| {
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut variant_arms = Vec::new();
+    for variant in variants {
         let variant_name = &variant.ident;
         let variant_name_str = variant_name.to_string();
-        match &variant.fields {
+        let arm = match &variant.fields {
             Fields::Named(fields) => {
                 let field_names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
-                let field_allocations = fields.named.iter().map(|field| {
+                let mut field_allocations = Vec::new();
+                for field in &fields.named {
+                    let config = parse_field_config(field)?;
+                    if config.skip {
+                        continue;
+                    }
                     let field_name = &field.ident;
-                    let field_name_str = field_name.as_ref().unwrap().to_string();
-                    quote! {
-                        if let Some(ptr) = memscope_rs::Trackable::get_heap_ptr(#field_name) {
-                            allocations.push((ptr, format!("{var_name}::{}::{}", #variant_name_str, #field_name_str)));
-                        }
+                    let label = config
+                        .rename
+                        .unwrap_or_else(|| field_name.as_ref().unwrap().to_string());
+                    if config.size_with.is_none() {
+                        field_allocations.push(quote! {
+                            if let Some(ptr) = memscope_rs::Trackable::get_heap_ptr(#field_name) {
+                                allocations.push((ptr, format!("{var_name}::{}::{}", #variant_name_str, #label)));
+                            }
+                        });
                     }
-                });
+                }
                 quote! {
                     Self::#variant_name { #(#field_names),* } => {
                         let mut allocations = Vec::new();
@@ -310,13 +651,23 @@ fn generate_enum_internal_allocations_impl(
                 let field_patterns: Vec<_> = (0..fields.unnamed.len())
                     .map(|i| syn::Ident::new(&format!("field_{i}"), proc_macro2::Span::call_site()))
                     .collect();
-                let field_allocations = field_patterns.iter().enumerate().map(|(i, field_name)| {
-                    quote! {
-                        if let Some(ptr) = memscope_rs::Trackable::get_heap_ptr(#field_name) {
-                            allocations.push((ptr, format!("{var_name}::{}::{}", #variant_name_str, #i)));
-                        }
+                let mut field_allocations = Vec::new();
+                for (i, (field, field_name)) in
+                    fields.unnamed.iter().zip(&field_patterns).enumerate()
+                {
+                    let config = parse_field_config(field)?;
+                    if config.skip {
+                        continue;
                     }
-                });
+                    let label = config.rename.unwrap_or_else(|| i.to_string());
+                    if config.size_with.is_none() {
+                        field_allocations.push(quote! {
+                            if let Some(ptr) = memscope_rs::Trackable::get_heap_ptr(#field_name) {
+                                allocations.push((ptr, format!("{var_name}::{}::{}", #variant_name_str, #label)));
+                            }
+                        });
+                    }
+                }
                 quote! {
                     Self::#variant_name(#(#field_patterns),*) => {
                         let mut allocations = Vec::new();
@@ -330,28 +681,43 @@ fn generate_enum_internal_allocations_impl(
                     Self::#variant_name => Vec::new()
                 }
             }
-        }
-    });
+        };
+        variant_arms.push(arm);
+    }
 
-    quote! {
+    Ok(quote! {
         match self {
             #(#variant_arms),*
         }
-    }
+    })
 }
 
-/// Check if fields potentially contain heap allocations
-fn has_potential_heap_allocations(fields: &Fields) -> bool {
+/// Check if any non-skipped field potentially contains heap allocations.
+/// Fields marked `#[trackable(skip)]` are excluded from both
+/// `get_size_estimate` and `get_internal_allocations`, so `get_heap_ptr` must
+/// ignore them too -- otherwise a struct whose only heap-ish field is skipped
+/// would still report its own address as a heap pointer.
+fn has_potential_heap_allocations(fields: &Fields) -> syn::Result<bool> {
     match fields {
-        Fields::Named(fields_named) => fields_named
-            .named
-            .iter()
-            .any(|field| is_potentially_heap_allocated(&field.ty)),
-        Fields::Unnamed(fields_unnamed) => fields_unnamed
-            .unnamed
-            .iter()
-            .any(|field| is_potentially_heap_allocated(&field.ty)),
-        Fields::Unit => false,
+        Fields::Named(fields_named) => {
+            for field in &fields_named.named {
+                let config = parse_field_config(field)?;
+                if !config.skip && is_potentially_heap_allocated(&field.ty) {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        Fields::Unnamed(fields_unnamed) => {
+            for field in &fields_unnamed.unnamed {
+                let config = parse_field_config(field)?;
+                if !config.skip && is_potentially_heap_allocated(&field.ty) {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        Fields::Unit => Ok(false),
     }
 }
 
@@ -383,3 +749,248 @@ fn is_potentially_heap_allocated(ty: &Type) -> bool {
         _ => false,
     }
 }
+
+/// Check if a type is one of Rust's scalar primitives, for which
+/// `#[trackable(strict)]` never requires an explicit annotation since their
+/// size is always `size_of::<T>()` with no internal allocation.
+fn is_primitive_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().is_some_and(|segment| {
+            matches!(
+                segment.ident.to_string().as_str(),
+                "i8" | "i16"
+                    | "i32"
+                    | "i64"
+                    | "i128"
+                    | "isize"
+                    | "u8"
+                    | "u16"
+                    | "u32"
+                    | "u64"
+                    | "u128"
+                    | "usize"
+                    | "f32"
+                    | "f64"
+                    | "bool"
+                    | "char"
+            )
+        }),
+        _ => false,
+    }
+}
+
+/// For `#[trackable(strict)]`: error on the first field whose type is
+/// neither a primitive nor a recognized container, and that isn't
+/// `#[trackable(skip)]` or `#[trackable(size_with = ...)]`, pointing at the
+/// offending field's own span.
+fn check_strict_fields(fields: &Fields) -> syn::Result<()> {
+    let field_list: Vec<&Field> = match fields {
+        Fields::Named(fields_named) => fields_named.named.iter().collect(),
+        Fields::Unnamed(fields_unnamed) => fields_unnamed.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    for field in field_list {
+        let config = parse_field_config(field)?;
+        if config.skip || config.size_with.is_some() {
+            continue;
+        }
+        if !is_primitive_type(&field.ty) && !is_potentially_heap_allocated(&field.ty) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "field type is not recognized by #[derive(Trackable)]'s heuristics; \
+                 #[trackable(strict)] requires every field to be a primitive, a \
+                 recognized container, or annotated with `#[trackable(skip)]` or \
+                 `#[trackable(size_with = ...)]` -- help: implement `Trackable` \
+                 manually for this type, or add one of those attributes",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Coarse shape of a field's outer type, used to decide how
+/// `generate_collection_aware_size` walks it: a single-element sequence, a
+/// key/value map, or anything else that just gets a plain `Trackable` call.
+enum CollectionShape {
+    Sequence,
+    Map,
+    Other,
+}
+
+/// Classify a field's outer type for size-estimation purposes. Only the
+/// collection kinds that expose element-wise iteration are singled out;
+/// `String`, `Box`, `Rc`, and `Arc` (also recognized by
+/// `is_potentially_heap_allocated`) fall through to `Other` since they don't
+/// have per-element contents to sum.
+fn classify_collection_shape(ty: &Type) -> CollectionShape {
+    match ty {
+        Type::Path(type_path) => match type_path.path.segments.last() {
+            Some(segment) => match segment.ident.to_string().as_str() {
+                "Vec" | "VecDeque" | "LinkedList" | "BinaryHeap" | "HashSet" | "BTreeSet" => {
+                    CollectionShape::Sequence
+                }
+                "HashMap" | "BTreeMap" => CollectionShape::Map,
+                _ => CollectionShape::Other,
+            },
+            None => CollectionShape::Other,
+        },
+        _ => CollectionShape::Other,
+    }
+}
+
+/// Generate the default (non-`size_with`) size-estimate expression for a
+/// field. The blanket `Trackable` impls for collections only account for
+/// their own backing buffer (e.g. `Vec<T>` is `capacity() * size_of::<T>()`),
+/// so a `Vec<String>` field would otherwise ignore every string's own heap
+/// allocation. For known sequence and map types this adds a loop that sums
+/// each element's (or each key's and value's) own `get_size_estimate`, on
+/// top of the existing backing-buffer call. Only the field's outer
+/// collection is walked: each element goes through its own `Trackable`
+/// impl, which for a nested collection counts just that collection's
+/// backing buffer, so `Vec<Vec<u8>>` is counted in full but the strings
+/// inside a `Vec<Vec<String>>` are not. Anything else keeps the plain
+/// single-call estimate used before.
+fn generate_collection_aware_size(
+    ty: &Type,
+    accessor: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match classify_collection_shape(ty) {
+        CollectionShape::Sequence => quote! {
+            total_size += memscope_rs::Trackable::get_size_estimate(#accessor);
+            for element in #accessor.iter() {
+                total_size += memscope_rs::Trackable::get_size_estimate(element);
+            }
+        },
+        CollectionShape::Map => quote! {
+            total_size += memscope_rs::Trackable::get_size_estimate(#accessor);
+            for (key, value) in #accessor.iter() {
+                total_size += memscope_rs::Trackable::get_size_estimate(key);
+                total_size += memscope_rs::Trackable::get_size_estimate(value);
+            }
+        },
+        CollectionShape::Other => quote! {
+            total_size += memscope_rs::Trackable::get_size_estimate(#accessor);
+        },
+    }
+}
+
+/// Collect the type parameters (from `params`) that are referenced by at
+/// least one non-`#[trackable(skip)]` field's type, so a `where` clause can
+/// require `Trackable` only for parameters the generated body actually
+/// calls `Trackable` on.
+fn collect_used_type_params(
+    fields: &Fields,
+    params: &[syn::Ident],
+) -> syn::Result<std::collections::HashSet<syn::Ident>> {
+    let mut used = std::collections::HashSet::new();
+    let field_list: Vec<&Field> = match fields {
+        Fields::Named(fields_named) => fields_named.named.iter().collect(),
+        Fields::Unnamed(fields_unnamed) => fields_unnamed.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    for field in field_list {
+        let config = parse_field_config(field)?;
+        if config.skip || is_phantom_data(&field.ty) {
+            continue;
+        }
+        for param in params {
+            if type_references_param(&field.ty, param) {
+                used.insert(param.clone());
+            }
+        }
+    }
+
+    Ok(used)
+}
+
+/// Same as [`collect_used_type_params`], but over every variant of an enum.
+fn collect_used_type_params_in_variants(
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
+    params: &[syn::Ident],
+) -> syn::Result<std::collections::HashSet<syn::Ident>> {
+    let mut used = std::collections::HashSet::new();
+    for variant in variants {
+        used.extend(collect_used_type_params(&variant.fields, params)?);
+    }
+    Ok(used)
+}
+
+/// `PhantomData<T>` fields don't need `T: Trackable` just to be tracked, so
+/// they're excluded from bound synthesis even when not `#[trackable(skip)]`.
+fn is_phantom_data(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "PhantomData"))
+}
+
+/// Check whether `ty` mentions `param` anywhere within it (through
+/// references, tuples, arrays, generic arguments, etc).
+fn type_references_param(ty: &Type, param: &syn::Ident) -> bool {
+    match ty {
+        Type::Path(type_path) => {
+            if type_path.qself.is_none() && type_path.path.is_ident(param) {
+                return true;
+            }
+            type_path.path.segments.iter().any(|segment| match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| match arg {
+                    syn::GenericArgument::Type(t) => type_references_param(t, param),
+                    _ => false,
+                }),
+                syn::PathArguments::Parenthesized(args) => {
+                    args.inputs.iter().any(|t| type_references_param(t, param))
+                        || matches!(&args.output, syn::ReturnType::Type(_, t) if type_references_param(t, param))
+                }
+                syn::PathArguments::None => false,
+            })
+        }
+        Type::Reference(r) => type_references_param(&r.elem, param),
+        Type::Ptr(p) => type_references_param(&p.elem, param),
+        Type::Paren(p) => type_references_param(&p.elem, param),
+        Type::Group(g) => type_references_param(&g.elem, param),
+        Type::Array(a) => type_references_param(&a.elem, param),
+        Type::Slice(s) => type_references_param(&s.elem, param),
+        Type::Tuple(t) => t
+            .elems
+            .iter()
+            .any(|elem| type_references_param(elem, param)),
+        _ => false,
+    }
+}
+
+/// Clone `generics`'s `where` clause and append a `T: memscope_rs::Trackable`
+/// predicate for each type parameter in `used_params`, so deriving on a
+/// generic container like `struct Wrapper<T> { inner: Box<T> }` produces an
+/// impl that actually requires `T: Trackable` instead of calling
+/// `Trackable::get_size_estimate` on an unconstrained `T`.
+fn augment_where_clause_for_trackable(
+    generics: &syn::Generics,
+    used_params: &std::collections::HashSet<syn::Ident>,
+) -> Option<syn::WhereClause> {
+    if used_params.is_empty() {
+        return generics.where_clause.clone();
+    }
+
+    let mut where_clause = generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| syn::WhereClause {
+            where_token: Default::default(),
+            predicates: syn::punctuated::Punctuated::new(),
+        });
+
+    for param in generics.type_params() {
+        if used_params.contains(&param.ident) {
+            let ident = &param.ident;
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ident: memscope_rs::Trackable));
+        }
+    }
+
+    Some(where_clause)
+}
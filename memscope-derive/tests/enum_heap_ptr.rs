@@ -0,0 +1,63 @@
+//! Integration tests for `generate_enum_heap_ptr_impl`: each variant's
+//! `get_heap_ptr` resolves to the first non-`#[trackable(skip)]` field whose
+//! type passes `is_potentially_heap_allocated`, across named, unnamed, and
+//! unit variants, falling back to `None` when a variant has no such field --
+//! including when its only heap-ish field is explicitly skipped.
+
+use memscope_rs::Trackable;
+
+#[derive(Trackable)]
+enum Payload {
+    Named { label: String, count: u32 },
+    Unnamed(Vec<u8>, u32),
+    Unit,
+}
+
+/// Regression test for the `#[trackable(skip)]` + `get_heap_ptr` interaction:
+/// a variant whose only heap-ish field is skipped must report `None`, not
+/// the skipped field's pointer.
+#[derive(Trackable)]
+enum SkipsOnlyHeapField {
+    Named {
+        #[trackable(skip)]
+        buffer: Vec<u8>,
+        count: u32,
+    },
+    Unnamed(#[trackable(skip)] String, u32),
+}
+
+#[test]
+fn named_variant_resolves_to_heap_field() {
+    let value = Payload::Named {
+        label: "hello".to_string(),
+        count: 1,
+    };
+    assert!(value.get_heap_ptr().is_some());
+}
+
+#[test]
+fn unnamed_variant_resolves_to_heap_field() {
+    let value = Payload::Unnamed(vec![1, 2, 3], 1);
+    assert!(value.get_heap_ptr().is_some());
+}
+
+#[test]
+fn unit_variant_has_no_heap_ptr() {
+    let value = Payload::Unit;
+    assert!(value.get_heap_ptr().is_none());
+}
+
+#[test]
+fn skipped_only_heap_field_in_named_variant_reports_none() {
+    let value = SkipsOnlyHeapField::Named {
+        buffer: vec![1, 2, 3],
+        count: 1,
+    };
+    assert!(value.get_heap_ptr().is_none());
+}
+
+#[test]
+fn skipped_only_heap_field_in_unnamed_variant_reports_none() {
+    let value = SkipsOnlyHeapField::Unnamed("not tracked".to_string(), 1);
+    assert!(value.get_heap_ptr().is_none());
+}
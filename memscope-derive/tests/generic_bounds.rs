@@ -0,0 +1,85 @@
+//! Integration tests for `#[derive(Trackable)]` on generic structs and
+//! enums: the derive must only require `T: Trackable` for type parameters
+//! actually referenced by a non-skipped, non-`PhantomData` field, per
+//! `collect_used_type_params`/`augment_where_clause_for_trackable`. A type
+//! parameter that only appears behind `#[trackable(skip)]` or inside
+//! `PhantomData<T>` should not force callers to implement `Trackable` for it.
+
+use memscope_rs::Trackable;
+use std::marker::PhantomData;
+
+/// `T` is used by `inner`, so the generated impl requires `T: Trackable`.
+#[derive(Trackable)]
+struct Wrapper<T> {
+    inner: Box<T>,
+    count: usize,
+}
+
+/// `Skipped` only appears behind `#[trackable(skip)]`, so no bound is
+/// synthesized for it -- a type with no `Trackable` impl at all must still
+/// be usable here.
+struct NotTrackable;
+
+#[derive(Trackable)]
+struct SkipsUnusedParam<Skipped> {
+    #[trackable(skip)]
+    extra: Skipped,
+    name: String,
+}
+
+/// `Marker` only appears in `PhantomData<Marker>`, so it's excluded from
+/// bound synthesis the same way a skipped field is.
+#[derive(Trackable)]
+struct PhantomMarker<Marker> {
+    value: u64,
+    _marker: PhantomData<Marker>,
+}
+
+/// Bounds are synthesized the same way across every variant of an enum.
+#[derive(Trackable)]
+enum EnumWrapper<T> {
+    Boxed(Box<T>),
+    Empty,
+}
+
+#[test]
+fn generic_struct_tracks_its_boxed_field() {
+    let value = Wrapper {
+        inner: Box::new(42u32),
+        count: 1,
+    };
+    assert!(value.get_heap_ptr().is_some());
+    assert!(value.get_size_estimate() >= std::mem::size_of::<Wrapper<u32>>());
+}
+
+#[test]
+fn unused_type_param_does_not_require_trackable() {
+    let value = SkipsUnusedParam {
+        extra: NotTrackable,
+        name: "example".to_string(),
+    };
+    // The only assertion that matters here is that this compiles at all:
+    // `NotTrackable` has no `Trackable` impl, so a bound wrongly synthesized
+    // for `Skipped` would turn this file into a compile failure.
+    assert!(value.get_size_estimate() >= std::mem::size_of::<SkipsUnusedParam<NotTrackable>>());
+}
+
+#[test]
+fn phantom_data_param_does_not_require_trackable() {
+    let value: PhantomMarker<NotTrackable> = PhantomMarker {
+        value: 7,
+        _marker: PhantomData,
+    };
+    // Compiling at all is the point: `NotTrackable` has no `Trackable` impl,
+    // so a bound wrongly synthesized for `Marker` would break this file.
+    assert!(value.get_size_estimate() >= std::mem::size_of::<PhantomMarker<NotTrackable>>());
+}
+
+#[test]
+fn generic_enum_tracks_the_active_variant() {
+    let boxed = EnumWrapper::Boxed(Box::new("value".to_string()));
+    assert!(boxed.get_heap_ptr().is_some());
+
+    let empty: EnumWrapper<String> = EnumWrapper::Empty;
+    assert!(empty.get_heap_ptr().is_none());
+}
@@ -0,0 +1,54 @@
+//! Integration tests for `generate_type_name_impl`'s generic-aware
+//! `get_type_name`: non-generic types resolve entirely at compile time via
+//! `concat!`/`module_path!`, while generic types additionally splice in each
+//! type parameter's monomorphized name via `std::any::type_name`, so two
+//! distinct monomorphizations of the same generic type report distinct names.
+
+use memscope_rs::Trackable;
+
+#[derive(Trackable)]
+struct Plain {
+    value: u64,
+}
+
+#[derive(Trackable)]
+struct Generic<T> {
+    value: T,
+}
+
+#[test]
+fn non_generic_type_name_is_module_qualified() {
+    let value = Plain { value: 1 };
+    assert_eq!(
+        value.get_type_name(),
+        concat!(module_path!(), "::Plain")
+    );
+}
+
+#[test]
+fn generic_type_name_includes_monomorphized_params() {
+    let strings = Generic { value: "x".to_string() };
+    let numbers = Generic { value: 1u32 };
+
+    let strings_name = strings.get_type_name();
+    let numbers_name = numbers.get_type_name();
+
+    let prefix = concat!(module_path!(), "::Generic");
+    assert!(strings_name.starts_with(prefix));
+    assert!(numbers_name.starts_with(prefix));
+
+    // Distinct monomorphizations must not collapse to the same name.
+    assert_ne!(strings_name, numbers_name);
+    assert!(strings_name.contains("String"));
+    assert!(numbers_name.contains("u32"));
+}
+
+#[test]
+fn generic_type_name_is_cached_per_monomorphization() {
+    let a = Generic { value: "a".to_string() };
+    let b = Generic { value: "b".to_string() };
+
+    // Same monomorphization (`Generic<String>`) shares the same cached
+    // `&'static str`, regardless of the particular instance's contents.
+    assert_eq!(a.get_type_name(), b.get_type_name());
+}
@@ -0,0 +1,67 @@
+//! Integration tests for `generate_collection_aware_size`: sequence and map
+//! fields get element-wise `get_size_estimate` accounting on top of the
+//! backing buffer's own call, so a `Vec<String>` or `HashMap<String, Vec<u8>>`
+//! field reflects its elements' own heap usage instead of only the outer
+//! collection's capacity.
+
+use memscope_rs::Trackable;
+use std::collections::HashMap;
+
+#[derive(Trackable)]
+struct Catalog {
+    names: Vec<String>,
+    tags: HashMap<String, Vec<u8>>,
+}
+
+#[test]
+fn vec_of_strings_accounts_for_each_strings_own_heap_usage() {
+    let empty = Catalog {
+        names: Vec::new(),
+        tags: HashMap::new(),
+    };
+    let populated = Catalog {
+        names: vec!["alpha".to_string(), "beta".to_string(), "gamma".to_string()],
+        tags: HashMap::new(),
+    };
+
+    // Every element's own capacity is summed in addition to the backing
+    // `Vec<String>` buffer, so a populated catalog must estimate larger than
+    // an empty one even though both have the same `size_of::<Catalog>()`.
+    assert!(populated.get_size_estimate() > empty.get_size_estimate());
+}
+
+#[test]
+fn map_accounts_for_both_keys_and_values() {
+    let empty = Catalog {
+        names: Vec::new(),
+        tags: HashMap::new(),
+    };
+    let mut tags = HashMap::new();
+    tags.insert("color".to_string(), vec![1, 2, 3, 4, 5]);
+    tags.insert("shape".to_string(), vec![6, 7, 8]);
+    let populated = Catalog {
+        names: Vec::new(),
+        tags,
+    };
+
+    assert!(populated.get_size_estimate() > empty.get_size_estimate());
+}
+
+#[test]
+fn nested_vec_of_vec_counts_each_inner_vecs_buffer() {
+    #[derive(Trackable)]
+    struct Nested {
+        rows: Vec<Vec<u8>>,
+    }
+
+    let value = Nested {
+        rows: vec![vec![0; 64], vec![0; 64]],
+    };
+
+    // The outer Vec<Vec<u8>>'s own Trackable::get_size_estimate only counts
+    // its own backing buffer (pointer-sized elements); the per-row loop adds
+    // each inner Vec<u8>'s own capacity on top, so the total must exceed
+    // what a plain single-call estimate would report.
+    let outer_only = std::mem::size_of::<Nested>() + value.rows.capacity() * std::mem::size_of::<Vec<u8>>();
+    assert!(value.get_size_estimate() > outer_only);
+}
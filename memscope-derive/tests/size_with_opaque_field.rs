@@ -0,0 +1,64 @@
+//! Regression test for `#[trackable(size_with = ...)]` on a field whose type
+//! does not implement `Trackable`. Before this fix, the derive unconditionally
+//! emitted `memscope_rs::Trackable::get_heap_ptr(&self.field)` for every field
+//! in `get_internal_allocations`, which failed to compile for exactly the
+//! opaque-type use case `size_with` exists to support.
+
+use memscope_rs::Trackable;
+
+/// A type with no `Trackable` impl, standing in for an opaque external type.
+struct OpaqueBlob {
+    bytes: Vec<u8>,
+}
+
+fn opaque_blob_size(blob: &OpaqueBlob) -> usize {
+    blob.bytes.len()
+}
+
+#[derive(Trackable)]
+struct HasOpaqueField {
+    name: String,
+    #[trackable(size_with = opaque_blob_size)]
+    blob: OpaqueBlob,
+}
+
+#[derive(Trackable)]
+enum HasOpaqueVariant {
+    Named {
+        #[trackable(size_with = opaque_blob_size)]
+        blob: OpaqueBlob,
+    },
+    Unnamed(#[trackable(size_with = opaque_blob_size)] OpaqueBlob),
+}
+
+#[test]
+fn size_with_field_is_excluded_from_internal_allocations() {
+    let value = HasOpaqueField {
+        name: "example".to_string(),
+        blob: OpaqueBlob {
+            bytes: vec![0; 16],
+        },
+    };
+
+    // The opaque field contributes to the size estimate via `size_with`...
+    assert!(value.get_size_estimate() >= 16);
+
+    // ...but is not treated as a tracked internal allocation, since its type
+    // is not `Trackable` and has no heap pointer to report.
+    let allocations = value.get_internal_allocations("value");
+    assert!(allocations.iter().all(|(_, label)| !label.ends_with("::blob")));
+}
+
+#[test]
+fn size_with_variant_field_is_excluded_from_internal_allocations() {
+    let named = HasOpaqueVariant::Named {
+        blob: OpaqueBlob { bytes: vec![0; 8] },
+    };
+    assert!(named
+        .get_internal_allocations("named")
+        .iter()
+        .all(|(_, label)| !label.ends_with("::blob")));
+
+    let unnamed = HasOpaqueVariant::Unnamed(OpaqueBlob { bytes: vec![0; 8] });
+    assert!(unnamed.get_internal_allocations("unnamed").is_empty());
+}
@@ -0,0 +1,52 @@
+//! Integration tests for `#[trackable(strict)]`: `check_strict_fields`
+//! accepts a container whose every field is a primitive, a recognized
+//! container, or annotated with `#[trackable(skip)]`/`#[trackable(size_with =
+//! ...)]`, and rejects (via a `syn::Error` turned into `compile_error!`) any
+//! field type it doesn't recognize. The accept case is a normal runtime
+//! test; the reject case can only be observed as a compile failure, so it
+//! uses `trybuild` against a fixture in `tests/strict_attribute_fail/`.
+
+use memscope_rs::Trackable;
+
+#[derive(Trackable)]
+#[trackable(strict)]
+struct StrictRecognizedFields {
+    name: String,
+    tags: Vec<String>,
+    count: u32,
+    flag: bool,
+}
+
+#[derive(Trackable)]
+#[trackable(strict)]
+struct StrictWithEscapeHatches {
+    name: String,
+    #[trackable(skip)]
+    opaque: std::thread::ThreadId,
+}
+
+#[test]
+fn strict_accepts_recognized_field_types() {
+    let value = StrictRecognizedFields {
+        name: "example".to_string(),
+        tags: vec!["a".to_string()],
+        count: 1,
+        flag: true,
+    };
+    assert!(value.get_size_estimate() >= std::mem::size_of::<StrictRecognizedFields>());
+}
+
+#[test]
+fn strict_accepts_skip_as_an_escape_hatch() {
+    let value = StrictWithEscapeHatches {
+        name: "example".to_string(),
+        opaque: std::thread::current().id(),
+    };
+    assert!(value.get_size_estimate() >= std::mem::size_of::<StrictWithEscapeHatches>());
+}
+
+#[test]
+fn strict_rejects_unrecognized_field_type() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/strict_attribute_fail/unrecognized_field.rs");
+}
@@ -0,0 +1,11 @@
+use memscope_rs::Trackable;
+
+struct NotRecognized;
+
+#[derive(Trackable)]
+#[trackable(strict)]
+struct HasUnrecognizedField {
+    value: NotRecognized,
+}
+
+fn main() {}
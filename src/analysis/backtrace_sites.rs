@@ -0,0 +1,170 @@
+//! Resolve captured allocation call stacks into source-level frames and
+//! group allocations by where they were made.
+//!
+//! [`AllocationInfo::stack_trace`](crate::core::types::AllocationInfo::stack_trace)
+//! already holds a cheaply-captured call stack as raw frame strings -- the
+//! capture side of the split std's `Backtrace`/`BacktraceSymbol` use
+//! internally. This module is the resolution side: turning each raw frame
+//! into a [`ResolvedFrame`] with a function name and, when the capture site
+//! encoded one, a source file and line number, then grouping allocations
+//! that share an identical resolved stack into an [`AllocationSite`] so
+//! leaks and hotspots can be attributed to exact call sites rather than just
+//! a type name. Resolution is only ever done when a caller asks for it --
+//! callers that don't care about backtraces never pay for parsing them.
+
+use crate::core::types::AllocationInfo;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// A single resolved call stack frame.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ResolvedFrame {
+    /// Function name, if the raw frame carried one.
+    pub fn_name: Option<String>,
+    /// Source file, if the raw frame encoded a `<fn> at <file>:<line>` suffix.
+    pub filename: Option<String>,
+    /// Line number within `filename`.
+    pub lineno: Option<u32>,
+}
+
+/// Parse one raw captured frame (e.g. `"main"` or `"allocate at src/lib.rs:42"`)
+/// into a [`ResolvedFrame`]. Frames without an `at <file>:<line>` suffix
+/// resolve to a function name only -- that's all a cheap capture site has to
+/// work with.
+fn resolve_frame(raw: &str) -> ResolvedFrame {
+    match raw.split_once(" at ") {
+        Some((fn_name, location)) => match location.rsplit_once(':') {
+            Some((filename, lineno)) => ResolvedFrame {
+                fn_name: Some(fn_name.to_string()),
+                filename: Some(filename.to_string()),
+                lineno: lineno.parse().ok(),
+            },
+            None => ResolvedFrame {
+                fn_name: Some(fn_name.to_string()),
+                filename: Some(location.to_string()),
+                lineno: None,
+            },
+        },
+        None => ResolvedFrame {
+            fn_name: Some(raw.to_string()),
+            filename: None,
+            lineno: None,
+        },
+    }
+}
+
+/// Resolve every raw frame in a captured stack trace, in capture order
+/// (innermost frame first).
+pub fn resolve_stack_trace(raw_frames: &[String]) -> Vec<ResolvedFrame> {
+    raw_frames.iter().map(|raw| resolve_frame(raw)).collect()
+}
+
+/// A unique call site, identified by its resolved frame stack, with the
+/// allocations attributed to it rolled up.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AllocationSite {
+    /// The resolved frames shared by every allocation grouped here.
+    pub frames: Vec<ResolvedFrame>,
+    /// Sum of `size` across every allocation made from this site.
+    pub total_bytes: usize,
+    /// Number of allocations made from this site.
+    pub allocation_count: usize,
+}
+
+fn site_key(frames: &[ResolvedFrame]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    frames.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl Hash for ResolvedFrame {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.fn_name.hash(state);
+        self.filename.hash(state);
+        self.lineno.hash(state);
+    }
+}
+
+/// Group allocations that carry a captured stack trace by their resolved
+/// call site, keyed by a hash of the resolved frame list. Allocations with
+/// no `stack_trace` are excluded -- there's nothing to attribute them to.
+pub fn group_allocation_sites(
+    allocation_history: &[AllocationInfo],
+) -> HashMap<u64, AllocationSite> {
+    let mut sites: HashMap<u64, AllocationSite> = HashMap::new();
+
+    for alloc in allocation_history {
+        let Some(raw_frames) = &alloc.stack_trace else {
+            continue;
+        };
+        let frames = resolve_stack_trace(raw_frames);
+        let key = site_key(&frames);
+        let site = sites.entry(key).or_insert_with(|| AllocationSite {
+            frames,
+            total_bytes: 0,
+            allocation_count: 0,
+        });
+        site.total_bytes += alloc.size;
+        site.allocation_count += 1;
+    }
+
+    sites
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alloc_with_trace(size: usize, trace: &[&str]) -> AllocationInfo {
+        let mut info = AllocationInfo::new(0x1000, size);
+        info.stack_trace = Some(trace.iter().map(|s| s.to_string()).collect());
+        info
+    }
+
+    #[test]
+    fn test_resolve_frame_without_location_is_fn_name_only() {
+        let frame = resolve_frame("main");
+        assert_eq!(frame.fn_name, Some("main".to_string()));
+        assert_eq!(frame.filename, None);
+        assert_eq!(frame.lineno, None);
+    }
+
+    #[test]
+    fn test_resolve_frame_with_location_splits_file_and_line() {
+        let frame = resolve_frame("allocate at src/lib.rs:42");
+        assert_eq!(frame.fn_name, Some("allocate".to_string()));
+        assert_eq!(frame.filename, Some("src/lib.rs".to_string()));
+        assert_eq!(frame.lineno, Some(42));
+    }
+
+    #[test]
+    fn test_allocations_with_no_stack_trace_are_excluded() {
+        let history = vec![AllocationInfo::new(0x1, 16)];
+        let sites = group_allocation_sites(&history);
+        assert!(sites.is_empty());
+    }
+
+    #[test]
+    fn test_identical_stacks_group_into_one_site() {
+        let history = vec![
+            alloc_with_trace(100, &["main", "allocate at src/lib.rs:42"]),
+            alloc_with_trace(200, &["main", "allocate at src/lib.rs:42"]),
+        ];
+        let sites = group_allocation_sites(&history);
+        assert_eq!(sites.len(), 1);
+        let site = sites.values().next().unwrap();
+        assert_eq!(site.total_bytes, 300);
+        assert_eq!(site.allocation_count, 2);
+    }
+
+    #[test]
+    fn test_different_stacks_produce_separate_sites() {
+        let history = vec![
+            alloc_with_trace(100, &["main", "allocate at src/lib.rs:42"]),
+            alloc_with_trace(200, &["main", "other_fn at src/lib.rs:99"]),
+        ];
+        let sites = group_allocation_sites(&history);
+        assert_eq!(sites.len(), 2);
+    }
+}
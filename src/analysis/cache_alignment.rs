@@ -0,0 +1,253 @@
+//! Cache-line alignment analysis for tracked allocations.
+//!
+//! [`fragmentation`](crate::analysis::fragmentation) reports how free space
+//! is laid out; this module instead asks whether *live* allocations sit on
+//! cache-line boundaries. An allocation whose address isn't a multiple of
+//! the cache line size (64 bytes on most x86_64/ARM64 hardware, configurable
+//! here for other targets) either wastes part of a line on padding or, worse,
+//! straddles two lines and forces two cache-line fetches for what should be
+//! one access. [`analyze_cache_alignment`] buckets every allocation's
+//! `ptr % cache_line_size` offset into a histogram and flags hot types (many
+//! allocations, mostly misaligned) as candidates for a cache-aligned
+//! allocator path.
+
+use crate::core::types::AllocationInfo;
+use std::collections::HashMap;
+
+/// Default cache line size, in bytes, used when callers don't know or care
+/// about their target's actual value.
+pub const DEFAULT_CACHE_LINE_SIZE: usize = 64;
+
+/// Count of allocations whose `ptr % cache_line_size` equals `offset`.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AlignmentOffsetBucket {
+    /// Byte offset within a cache line, `0..cache_line_size`.
+    pub offset: usize,
+    /// Number of allocations starting at this offset.
+    pub count: usize,
+}
+
+/// Per-type cache-alignment summary, included only for types with at least
+/// one misaligned allocation.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct TypeCacheAlignment {
+    /// The type this summary is for.
+    pub type_name: String,
+    /// Total allocations of this type considered.
+    pub total_count: usize,
+    /// Allocations whose start address isn't a multiple of the cache line size.
+    pub misaligned_count: usize,
+    /// Allocations that straddle a cache-line boundary (start misaligned and
+    /// large enough that the tail spills into the next line).
+    pub straddling_count: usize,
+}
+
+impl TypeCacheAlignment {
+    /// Fraction of this type's allocations that are misaligned, `0.0..=1.0`.
+    pub fn misaligned_fraction(&self) -> f64 {
+        if self.total_count == 0 {
+            0.0
+        } else {
+            self.misaligned_count as f64 / self.total_count as f64
+        }
+    }
+}
+
+/// Full cache-alignment report produced by [`analyze_cache_alignment`].
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct CacheAlignmentReport {
+    /// Cache line size, in bytes, this report was computed against.
+    pub cache_line_size: usize,
+    /// Histogram of start-address offsets within a cache line, across all
+    /// allocations considered.
+    pub offset_histogram: Vec<AlignmentOffsetBucket>,
+    /// Types that are both frequently allocated and frequently misaligned,
+    /// sorted by `total_count` descending -- candidates for a cache-aligned
+    /// allocation path.
+    pub hot_misaligned_types: Vec<TypeCacheAlignment>,
+}
+
+/// A type is reported as "hot and misaligned" once it has at least this many
+/// allocations and at least this fraction of them are misaligned.
+const HOT_TYPE_MIN_COUNT: usize = 5;
+const HOT_TYPE_MIN_MISALIGNED_FRACTION: f64 = 0.5;
+
+/// Analyze `allocations` for cache-line alignment against `cache_line_size`
+/// (see [`DEFAULT_CACHE_LINE_SIZE`] for a sensible default).
+pub fn analyze_cache_alignment(
+    allocations: &[AllocationInfo],
+    cache_line_size: usize,
+) -> CacheAlignmentReport {
+    if cache_line_size == 0 {
+        return CacheAlignmentReport::default();
+    }
+
+    let mut offset_counts: HashMap<usize, usize> = HashMap::new();
+    let mut per_type: HashMap<String, TypeCacheAlignment> = HashMap::new();
+
+    for allocation in allocations {
+        let offset = allocation.ptr % cache_line_size;
+        *offset_counts.entry(offset).or_insert(0) += 1;
+
+        let type_name = allocation
+            .type_name
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+        let entry = per_type
+            .entry(type_name.clone())
+            .or_insert_with(|| TypeCacheAlignment {
+                type_name,
+                ..Default::default()
+            });
+        entry.total_count += 1;
+        if offset != 0 {
+            entry.misaligned_count += 1;
+            if offset + allocation.size > cache_line_size {
+                entry.straddling_count += 1;
+            }
+        }
+    }
+
+    let mut offset_histogram: Vec<AlignmentOffsetBucket> = offset_counts
+        .into_iter()
+        .map(|(offset, count)| AlignmentOffsetBucket { offset, count })
+        .collect();
+    offset_histogram.sort_by_key(|bucket| bucket.offset);
+
+    let mut hot_misaligned_types: Vec<TypeCacheAlignment> = per_type
+        .into_values()
+        .filter(|summary| {
+            summary.total_count >= HOT_TYPE_MIN_COUNT
+                && summary.misaligned_fraction() >= HOT_TYPE_MIN_MISALIGNED_FRACTION
+        })
+        .collect();
+    hot_misaligned_types.sort_by_key(|summary| std::cmp::Reverse(summary.total_count));
+
+    CacheAlignmentReport {
+        cache_line_size,
+        offset_histogram,
+        hot_misaligned_types,
+    }
+}
+
+/// Suggest cache-aligned allocation for hot, frequently-misaligned types,
+/// and call out when the histogram shows at least half of all allocations
+/// at a nonzero offset (i.e. a custom aligned allocator would actually
+/// help, as opposed to a handful of incidentally-misaligned allocations).
+pub fn generate_cache_alignment_recommendations(report: &CacheAlignmentReport) -> Vec<String> {
+    let mut recommendations = Vec::new();
+
+    for summary in &report.hot_misaligned_types {
+        recommendations.push(format!(
+            "Type '{}' has {}/{} allocations ({:.0}%) misaligned to the {}-byte cache line ({} straddling a line boundary) -- consider a cache-aligned allocation path for this type",
+            summary.type_name,
+            summary.misaligned_count,
+            summary.total_count,
+            summary.misaligned_fraction() * 100.0,
+            report.cache_line_size,
+            summary.straddling_count,
+        ));
+    }
+
+    let total: usize = report.offset_histogram.iter().map(|b| b.count).sum();
+    let aligned: usize = report
+        .offset_histogram
+        .iter()
+        .find(|b| b.offset == 0)
+        .map(|b| b.count)
+        .unwrap_or(0);
+    if total > 0 && (total - aligned) as f64 / total as f64 >= HOT_TYPE_MIN_MISALIGNED_FRACTION {
+        recommendations.push(format!(
+            "{}/{} tracked allocations are not cache-line aligned -- a custom aligned allocator would likely reduce cache-line splits",
+            total - aligned,
+            total
+        ));
+    }
+
+    recommendations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocation(ptr: usize, size: usize, type_name: &str) -> AllocationInfo {
+        let mut info = AllocationInfo::new(ptr, size);
+        info.type_name = Some(type_name.to_string());
+        info
+    }
+
+    #[test]
+    fn test_aligned_allocation_has_zero_offset() {
+        let allocations = vec![allocation(64 * 4, 32, "String")];
+        let report = analyze_cache_alignment(&allocations, 64);
+        assert_eq!(report.offset_histogram.len(), 1);
+        assert_eq!(report.offset_histogram[0].offset, 0);
+    }
+
+    #[test]
+    fn test_misaligned_allocation_reports_nonzero_offset() {
+        let allocations = vec![allocation(64 * 4 + 16, 32, "String")];
+        let report = analyze_cache_alignment(&allocations, 64);
+        assert_eq!(report.offset_histogram.len(), 1);
+        assert_eq!(report.offset_histogram[0].offset, 16);
+    }
+
+    #[test]
+    fn test_single_straddling_allocation_is_not_reported_as_hot() {
+        // offset 48 + size 32 = 80 > 64, so this spills into the next line
+        let allocations = vec![allocation(64 * 4 + 48, 32, "Buffer")];
+        let report = analyze_cache_alignment(&allocations, 64);
+        let buffer = report
+            .hot_misaligned_types
+            .iter()
+            .find(|t| t.type_name == "Buffer");
+        // Not "hot" (only 1 allocation, below HOT_TYPE_MIN_COUNT), so the
+        // type is left out of the report even though it straddles a line
+        assert!(buffer.is_none());
+    }
+
+    #[test]
+    fn test_hot_misaligned_type_is_reported_and_sorted_first() {
+        let mut allocations = Vec::new();
+        for i in 0..10 {
+            allocations.push(allocation(64 * i + 8, 16, "HotType"));
+        }
+        for i in 0..3 {
+            allocations.push(allocation(64 * i, 16, "ColdType"));
+        }
+        let report = analyze_cache_alignment(&allocations, 64);
+        assert_eq!(report.hot_misaligned_types.len(), 1);
+        assert_eq!(report.hot_misaligned_types[0].type_name, "HotType");
+        assert_eq!(report.hot_misaligned_types[0].misaligned_count, 10);
+    }
+
+    #[test]
+    fn test_generate_cache_alignment_recommendations_flags_hot_type() {
+        let mut allocations = Vec::new();
+        for i in 0..10 {
+            allocations.push(allocation(64 * i + 8, 16, "HotType"));
+        }
+        let report = analyze_cache_alignment(&allocations, 64);
+        let recommendations = generate_cache_alignment_recommendations(&report);
+        assert!(recommendations
+            .iter()
+            .any(|r| r.contains("HotType") && r.contains("cache-aligned")));
+    }
+
+    #[test]
+    fn test_generate_cache_alignment_recommendations_empty_for_all_aligned() {
+        let allocations = vec![allocation(64 * 2, 16, "String")];
+        let report = analyze_cache_alignment(&allocations, 64);
+        let recommendations = generate_cache_alignment_recommendations(&report);
+        assert!(recommendations.is_empty());
+    }
+
+    #[test]
+    fn test_zero_cache_line_size_returns_empty_report() {
+        let allocations = vec![allocation(64, 16, "String")];
+        let report = analyze_cache_alignment(&allocations, 0);
+        assert!(report.offset_histogram.is_empty());
+        assert!(report.hot_misaligned_types.is_empty());
+    }
+}
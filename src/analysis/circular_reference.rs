@@ -346,6 +346,162 @@ fn suggest_weak_positions(nodes: &[CircularReferenceNode]) -> Vec<usize> {
     }
 }
 
+/// A strongly-connected component of strong `Rc`/`Arc` references: every
+/// allocation in it is memory that can never be freed, since each node in
+/// the component keeps at least one other node in it alive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeakRisk {
+    /// Pointer addresses of the smart pointers participating in the leak.
+    pub addresses: Vec<usize>,
+
+    /// Number of smart pointers in the strongly-connected component.
+    pub cycle_size: usize,
+
+    /// Total size of the allocations participating in the leak.
+    pub estimated_leaked_memory: usize,
+
+    /// Suggested remediation, e.g. "break the cycle with Weak".
+    pub suggested_fix: String,
+}
+
+/// Find every strongly-connected component of size > 1 in the strong
+/// `Rc`/`Arc` reference graph using Tarjan's algorithm, and report each as a
+/// [`LeakRisk`]. Unlike [`detect_circular_references`], which reports one
+/// path per DFS back edge, this finds every set of allocations that keep
+/// each other alive even when the set forms a larger strongly-connected
+/// component than a single simple cycle (e.g. two cycles sharing a node).
+pub fn detect_leak_risks(allocations: &[AllocationInfo]) -> Vec<LeakRisk> {
+    let graph = ReferenceGraph::new(allocations);
+    let components = tarjan_scc(&graph);
+
+    components
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|component| {
+            let estimated_leaked_memory = component
+                .iter()
+                .filter_map(|ptr| graph.allocations.get(ptr))
+                .map(|allocation| allocation.size)
+                .sum();
+
+            LeakRisk {
+                cycle_size: component.len(),
+                addresses: component,
+                estimated_leaked_memory,
+                suggested_fix: "break the cycle with Weak".to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Tarjan's strongly-connected-components algorithm over the strong
+/// reference graph's adjacency list.
+///
+/// Implemented with an explicit work stack rather than recursion: a long
+/// `Rc`/`Arc` chain (e.g. a linked list with thousands of nodes, a shape
+/// several of this crate's own examples use) would otherwise drive recursion
+/// depth proportional to chain length and risk a stack overflow.
+fn tarjan_scc(graph: &ReferenceGraph) -> Vec<Vec<usize>> {
+    struct TarjanState {
+        index_counter: usize,
+        indices: HashMap<usize, usize>,
+        lowlinks: HashMap<usize, usize>,
+        on_stack: HashSet<usize>,
+        stack: Vec<usize>,
+        components: Vec<Vec<usize>>,
+    }
+
+    /// One simulated `strongconnect` call frame: the node being visited and
+    /// how many of its neighbors have been processed so far.
+    struct Frame {
+        node: usize,
+        neighbor_idx: usize,
+    }
+
+    fn strongconnect(start: usize, graph: &ReferenceGraph, state: &mut TarjanState) {
+        let mut call_stack = vec![Frame {
+            node: start,
+            neighbor_idx: 0,
+        }];
+
+        while let Some(frame) = call_stack.last_mut() {
+            let node = frame.node;
+
+            // First time visiting `node` in this simulated call: index it,
+            // exactly like the top of a recursive `strongconnect(node)`.
+            if frame.neighbor_idx == 0 {
+                state.indices.insert(node, state.index_counter);
+                state.lowlinks.insert(node, state.index_counter);
+                state.index_counter += 1;
+                state.stack.push(node);
+                state.on_stack.insert(node);
+            }
+
+            let neighbors = graph.adjacency.get(&node).map(Vec::as_slice).unwrap_or(&[]);
+            if let Some(&neighbor) = neighbors.get(frame.neighbor_idx) {
+                frame.neighbor_idx += 1;
+                if !state.indices.contains_key(&neighbor) {
+                    // Simulate `strongconnect(neighbor)`: push its frame and
+                    // revisit `node` once it unwinds.
+                    call_stack.push(Frame {
+                        node: neighbor,
+                        neighbor_idx: 0,
+                    });
+                } else if state.on_stack.contains(&neighbor) {
+                    let neighbor_index = state.indices[&neighbor];
+                    let node_lowlink = state.lowlinks[&node];
+                    state
+                        .lowlinks
+                        .insert(node, node_lowlink.min(neighbor_index));
+                }
+                continue;
+            }
+
+            // All of `node`'s neighbors are processed: this is where a
+            // recursive call would return.
+            call_stack.pop();
+            if state.lowlinks[&node] == state.indices[&node] {
+                let mut component = Vec::new();
+                loop {
+                    let member = state.stack.pop().expect("node pushed before strongconnect");
+                    state.on_stack.remove(&member);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                state.components.push(component);
+            }
+
+            if let Some(parent) = call_stack.last() {
+                let parent_node = parent.node;
+                let node_lowlink = state.lowlinks[&node];
+                let parent_lowlink = state.lowlinks[&parent_node];
+                state
+                    .lowlinks
+                    .insert(parent_node, parent_lowlink.min(node_lowlink));
+            }
+        }
+    }
+
+    let mut state = TarjanState {
+        index_counter: 0,
+        indices: HashMap::new(),
+        lowlinks: HashMap::new(),
+        on_stack: HashSet::new(),
+        stack: Vec::new(),
+        components: Vec::new(),
+    };
+
+    for &ptr in graph.smart_pointers.keys() {
+        if !state.indices.contains_key(&ptr) {
+            strongconnect(ptr, graph, &mut state);
+        }
+    }
+
+    state.components
+}
+
 /// Generate statistics for the analysis
 fn generate_statistics(circular_references: &[CircularReference]) -> CircularReferenceStatistics {
     let mut by_severity = HashMap::new();
@@ -1022,4 +1178,132 @@ mod tests {
         };
         assert_eq!(complex_type, CircularReferenceType::Complex);
     }
+
+    fn rc_allocation(
+        ptr: usize,
+        size: usize,
+        data_ptr: usize,
+        clones: Vec<usize>,
+    ) -> AllocationInfo {
+        let smart_info = SmartPointerInfo {
+            data_ptr,
+            pointer_type: SmartPointerType::Rc,
+            is_weak_reference: false,
+            clones,
+            cloned_from: None,
+            ref_count_history: vec![RefCountSnapshot {
+                strong_count: 1,
+                weak_count: 0,
+                timestamp: 0,
+            }],
+            weak_count: None,
+            is_data_owner: true,
+            is_implicitly_deallocated: false,
+        };
+
+        AllocationInfo {
+            ptr,
+            size,
+            var_name: None,
+            type_name: Some("Rc<RefCell<Node>>".to_string()),
+            smart_pointer_info: Some(smart_info),
+            scope_name: None,
+            timestamp_alloc: 0,
+            timestamp_dealloc: None,
+            thread_id: "main".to_string(),
+            borrow_count: 0,
+            stack_trace: None,
+            is_leaked: false,
+            lifetime_ms: None,
+            borrow_info: None,
+            clone_info: None,
+            ownership_history_available: false,
+            memory_layout: None,
+            generic_info: None,
+            dynamic_type_info: None,
+            runtime_state: None,
+            stack_allocation: None,
+            temporary_object: None,
+            fragmentation_analysis: None,
+            generic_instantiation: None,
+            type_relationships: None,
+            type_usage: None,
+            function_call_tracking: None,
+            lifecycle_tracking: None,
+            access_tracking: None,
+            drop_chain_analysis: None,
+        }
+    }
+
+    #[test]
+    fn test_detect_leak_risks_empty() {
+        let empty_allocations = vec![];
+        assert!(detect_leak_risks(&empty_allocations).is_empty());
+    }
+
+    #[test]
+    fn test_detect_leak_risks_no_cycle() {
+        // A -> B, no clones back to A: acyclic, no leak risk.
+        let allocations = vec![
+            rc_allocation(0x1000, 1024, 0x2000, vec![]),
+            rc_allocation(0x2000, 512, 0x3000, vec![]),
+        ];
+        assert!(detect_leak_risks(&allocations).is_empty());
+    }
+
+    #[test]
+    fn test_detect_leak_risks_two_node_cycle() {
+        // A and B clone each other: a strongly-connected component of size 2.
+        let allocations = vec![
+            rc_allocation(0x1000, 1024, 0x2000, vec![0x2000]),
+            rc_allocation(0x2000, 2048, 0x1000, vec![0x1000]),
+        ];
+        let risks = detect_leak_risks(&allocations);
+
+        assert_eq!(risks.len(), 1);
+        assert_eq!(risks[0].cycle_size, 2);
+        assert_eq!(risks[0].estimated_leaked_memory, 3072);
+        assert_eq!(risks[0].suggested_fix, "break the cycle with Weak");
+        let mut addresses = risks[0].addresses.clone();
+        addresses.sort_unstable();
+        assert_eq!(addresses, vec![0x1000, 0x2000]);
+    }
+
+    #[test]
+    fn test_detect_leak_risks_long_chain_does_not_overflow_stack() {
+        // A long Rc<RefCell<Node>> chain closed into one big cycle: with
+        // recursive strongconnect, this drove recursion depth proportional
+        // to chain length and could blow the stack.
+        const CHAIN_LEN: usize = 50_000;
+        let allocations: Vec<AllocationInfo> = (0..CHAIN_LEN)
+            .map(|i| {
+                let ptr = 0x1000 + i;
+                let next_ptr = 0x1000 + (i + 1) % CHAIN_LEN;
+                rc_allocation(ptr, 16, next_ptr, vec![next_ptr])
+            })
+            .collect();
+
+        let risks = detect_leak_risks(&allocations);
+
+        assert_eq!(risks.len(), 1);
+        assert_eq!(risks[0].cycle_size, CHAIN_LEN);
+        assert_eq!(risks[0].estimated_leaked_memory, CHAIN_LEN * 16);
+    }
+
+    #[test]
+    fn test_detect_leak_risks_skips_weak_references() {
+        let mut allocations = vec![
+            rc_allocation(0x1000, 1024, 0x2000, vec![0x2000]),
+            rc_allocation(0x2000, 2048, 0x1000, vec![0x1000]),
+        ];
+        // Breaking the cycle with a weak reference means the graph no
+        // longer contains that node, so no SCC of size > 1 remains.
+        allocations[1]
+            .smart_pointer_info
+            .as_mut()
+            .unwrap()
+            .is_weak_reference = true;
+
+        assert!(detect_leak_risks(&allocations).is_empty());
+    }
 }
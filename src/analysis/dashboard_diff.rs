@@ -0,0 +1,555 @@
+//! Snapshot diff between two previously exported dashboards.
+//!
+//! [`build_unified_dashboard_structure`](crate::core::tracker::export_json::build_unified_dashboard_structure)
+//! and the `type_analysis.json` generator each produce a single point-in-time
+//! snapshot. Comparing two runs (two commits, two workloads, before/after a
+//! fix) today means diffing the raw JSON by hand. [`diff_dashboards`] and
+//! [`diff_type_analysis`] do that comparison for you: they key on `type_name`,
+//! report which types appeared or vanished, compute net growth for the types
+//! present in both, flag types that crossed a severity boundary in
+//! `identify_memory_hotspots`, and surface the change in overall memory
+//! efficiency and fragmentation.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Per-type change between two snapshots.
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeUsageDelta {
+    /// The type this delta is for.
+    pub type_name: String,
+    /// `total_size` before.
+    pub total_size_before: i64,
+    /// `total_size` after.
+    pub total_size_after: i64,
+    /// `total_size_after - total_size_before`.
+    pub total_size_delta: i64,
+    /// Allocation count before.
+    pub allocation_count_before: i64,
+    /// Allocation count after.
+    pub allocation_count_after: i64,
+    /// `allocation_count_after - allocation_count_before`.
+    pub allocation_count_delta: i64,
+    /// Hotspot severity (`"low"`/`"medium"`/`"high"`) before, if this type
+    /// was a hotspot in the `before` snapshot.
+    pub severity_before: Option<String>,
+    /// Hotspot severity after, if this type is a hotspot in the `after`
+    /// snapshot.
+    pub severity_after: Option<String>,
+}
+
+/// The full comparison between two snapshots.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DashboardDiff {
+    /// Types present in `after` but not `before`.
+    pub added_types: Vec<String>,
+    /// Types present in `before` but not `after`.
+    pub removed_types: Vec<String>,
+    /// Types present in both snapshots, sorted by `|total_size_delta|` descending.
+    pub changed_types: Vec<TypeUsageDelta>,
+    /// Types that are a memory hotspot in `after` but were not one in `before`.
+    pub newly_appeared_hotspots: Vec<String>,
+    /// Types whose hotspot severity in `after` differs from `before` (including
+    /// a type becoming, or ceasing to be, a hotspot at all).
+    pub severity_crossed_types: Vec<String>,
+    /// `after.memory_efficiency - before.memory_efficiency`, if both snapshots
+    /// carried the field.
+    pub memory_efficiency_delta: Option<f64>,
+    /// `after.fragmentation_ratio - before.fragmentation_ratio`, if both
+    /// snapshots carried the field.
+    pub fragmentation_ratio_delta: Option<f64>,
+    /// `after.leaked_memory - before.leaked_memory`, in bytes. Only set by
+    /// [`diff_memory_stats`], which has typed `MemoryStats` to read it from.
+    pub leaked_memory_delta: Option<i64>,
+    /// `after.leaked_allocations - before.leaked_allocations`. Only set by
+    /// [`diff_memory_stats`].
+    pub leaked_allocations_delta: Option<i64>,
+}
+
+struct TypeRow {
+    total_size: i64,
+    count: i64,
+}
+
+fn extract_type_rows(
+    array: &[Value],
+    name_key: &str,
+    size_key: &str,
+    count_key: &str,
+) -> HashMap<String, TypeRow> {
+    array
+        .iter()
+        .filter_map(|entry| {
+            let name = entry.get(name_key)?.as_str()?.to_string();
+            let total_size = entry.get(size_key).and_then(Value::as_i64).unwrap_or(0);
+            let count = entry.get(count_key).and_then(Value::as_i64).unwrap_or(0);
+            Some((name, TypeRow { total_size, count }))
+        })
+        .collect()
+}
+
+fn extract_hotspot_severity(array: &[Value]) -> HashMap<String, String> {
+    array
+        .iter()
+        .filter_map(|entry| {
+            let name = entry.get("type")?.as_str()?.to_string();
+            let severity = entry.get("severity")?.as_str()?.to_string();
+            Some((name, severity))
+        })
+        .collect()
+}
+
+/// Build a [`DashboardDiff`] from the extracted type tables and hotspot
+/// severity maps of two snapshots, plus their top-level performance deltas.
+fn diff(
+    before_types: &HashMap<String, TypeRow>,
+    after_types: &HashMap<String, TypeRow>,
+    before_hotspots: &HashMap<String, String>,
+    after_hotspots: &HashMap<String, String>,
+    memory_efficiency_delta: Option<f64>,
+    fragmentation_ratio_delta: Option<f64>,
+) -> DashboardDiff {
+    let mut added_types: Vec<String> = after_types
+        .keys()
+        .filter(|name| !before_types.contains_key(*name))
+        .cloned()
+        .collect();
+    added_types.sort();
+
+    let mut removed_types: Vec<String> = before_types
+        .keys()
+        .filter(|name| !after_types.contains_key(*name))
+        .cloned()
+        .collect();
+    removed_types.sort();
+
+    let mut changed_types: Vec<TypeUsageDelta> = before_types
+        .iter()
+        .filter_map(|(name, before_row)| {
+            let after_row = after_types.get(name)?;
+            Some(TypeUsageDelta {
+                type_name: name.clone(),
+                total_size_before: before_row.total_size,
+                total_size_after: after_row.total_size,
+                total_size_delta: after_row.total_size - before_row.total_size,
+                allocation_count_before: before_row.count,
+                allocation_count_after: after_row.count,
+                allocation_count_delta: after_row.count - before_row.count,
+                severity_before: before_hotspots.get(name).cloned(),
+                severity_after: after_hotspots.get(name).cloned(),
+            })
+        })
+        .collect();
+    changed_types.sort_by_key(|delta| std::cmp::Reverse(delta.total_size_delta.abs()));
+
+    let mut newly_appeared_hotspots: Vec<String> = after_hotspots
+        .keys()
+        .filter(|name| !before_hotspots.contains_key(*name))
+        .cloned()
+        .collect();
+    newly_appeared_hotspots.sort();
+
+    let mut severity_crossed_types: Vec<String> = changed_types
+        .iter()
+        .filter(|delta| delta.severity_before != delta.severity_after)
+        .map(|delta| delta.type_name.clone())
+        .collect();
+    severity_crossed_types.sort();
+
+    DashboardDiff {
+        added_types,
+        removed_types,
+        changed_types,
+        newly_appeared_hotspots,
+        severity_crossed_types,
+        memory_efficiency_delta,
+        fragmentation_ratio_delta,
+        leaked_memory_delta: None,
+        leaked_allocations_delta: None,
+    }
+}
+
+fn delta_of(before: &Value, after: &Value, pointer: &str) -> Option<f64> {
+    let before_value = before.pointer(pointer)?.as_f64()?;
+    let after_value = after.pointer(pointer)?.as_f64()?;
+    Some(after_value - before_value)
+}
+
+/// Compare two `build_unified_dashboard_structure` outputs, keyed by
+/// `type_usage[].type`.
+pub fn diff_dashboards(before: &Value, after: &Value) -> Value {
+    let before_types = extract_type_rows(
+        before
+            .pointer("/type_usage")
+            .and_then(Value::as_array)
+            .unwrap_or(&[]),
+        "type",
+        "total_size",
+        "count",
+    );
+    let after_types = extract_type_rows(
+        after
+            .pointer("/type_usage")
+            .and_then(Value::as_array)
+            .unwrap_or(&[]),
+        "type",
+        "total_size",
+        "count",
+    );
+    let before_hotspots = extract_hotspot_severity(
+        before
+            .pointer("/analysis_summary/memory_hotspots")
+            .and_then(Value::as_array)
+            .unwrap_or(&[]),
+    );
+    let after_hotspots = extract_hotspot_severity(
+        after
+            .pointer("/analysis_summary/memory_hotspots")
+            .and_then(Value::as_array)
+            .unwrap_or(&[]),
+    );
+
+    let result = diff(
+        &before_types,
+        &after_types,
+        &before_hotspots,
+        &after_hotspots,
+        delta_of(before, after, "/performance_metrics/memory_efficiency"),
+        delta_of(before, after, "/performance_metrics/fragmentation_ratio"),
+    );
+
+    serde_json::to_value(result).unwrap_or(Value::Null)
+}
+
+/// Compare two `type_analysis.json` outputs, keyed by `type_analysis[].type_name`.
+pub fn diff_type_analysis(before: &Value, after: &Value) -> Value {
+    let before_types = extract_type_rows(
+        before
+            .pointer("/type_analysis")
+            .and_then(Value::as_array)
+            .unwrap_or(&[]),
+        "type_name",
+        "total_size",
+        "allocation_count",
+    );
+    let after_types = extract_type_rows(
+        after
+            .pointer("/type_analysis")
+            .and_then(Value::as_array)
+            .unwrap_or(&[]),
+        "type_name",
+        "total_size",
+        "allocation_count",
+    );
+    let before_hotspots = extract_hotspot_severity(
+        before
+            .pointer("/memory_hotspots")
+            .and_then(Value::as_array)
+            .unwrap_or(&[]),
+    );
+    let after_hotspots = extract_hotspot_severity(
+        after
+            .pointer("/memory_hotspots")
+            .and_then(Value::as_array)
+            .unwrap_or(&[]),
+    );
+
+    let result = diff(
+        &before_types,
+        &after_types,
+        &before_hotspots,
+        &after_hotspots,
+        None,
+        None,
+    );
+
+    serde_json::to_value(result).unwrap_or(Value::Null)
+}
+
+/// Compare two `MemoryStats` snapshots and their `TypeMemoryUsage` tables
+/// directly, without going through an exported JSON dashboard first. Keys
+/// per-type rows on `type_name` and uses each type's `current_size` (the
+/// deep/retained size, see [`crate::analysis::malloc_size_of`]) rather than
+/// `total_size`, since comparing retained memory across runs is the point.
+/// The result is the same [`DashboardDiff`] shape `diff_dashboards` produces,
+/// so it serializes through the same JSON export path and two recorded runs
+/// can be compared fully offline.
+pub fn diff_memory_stats(
+    before_stats: &crate::core::types::MemoryStats,
+    before_types: &[crate::core::types::TypeMemoryUsage],
+    after_stats: &crate::core::types::MemoryStats,
+    after_types: &[crate::core::types::TypeMemoryUsage],
+) -> DashboardDiff {
+    let to_rows = |types: &[crate::core::types::TypeMemoryUsage]| -> HashMap<String, TypeRow> {
+        types
+            .iter()
+            .map(|usage| {
+                (
+                    usage.type_name.clone(),
+                    TypeRow {
+                        total_size: usage.current_size as i64,
+                        count: usage.allocation_count as i64,
+                    },
+                )
+            })
+            .collect()
+    };
+
+    let fragmentation_ratio_delta = after_stats.fragmentation_analysis.fragmentation_ratio
+        - before_stats.fragmentation_analysis.fragmentation_ratio;
+
+    let mut result = diff(
+        &to_rows(before_types),
+        &to_rows(after_types),
+        &HashMap::new(),
+        &HashMap::new(),
+        None,
+        Some(fragmentation_ratio_delta),
+    );
+    result.leaked_memory_delta =
+        Some(after_stats.leaked_memory as i64 - before_stats.leaked_memory as i64);
+    result.leaked_allocations_delta =
+        Some(after_stats.leaked_allocations as i64 - before_stats.leaked_allocations as i64);
+    result
+}
+
+/// Flag regressions from a [`DashboardDiff`]: types whose retained memory
+/// grew by more than `growth_threshold_bytes` between runs, any increase in
+/// leaked memory, and newly appeared types that are already memory hotspots
+/// with no baseline to compare against. Returns recommendation strings in
+/// the same style as
+/// [`generate_optimization_recommendations`](crate::core::tracker::export_json::generate_optimization_recommendations),
+/// so CI can gate on either a single snapshot or a before/after comparison.
+pub fn generate_regression_recommendations(
+    diff: &DashboardDiff,
+    growth_threshold_bytes: i64,
+) -> Vec<String> {
+    let mut recommendations = Vec::new();
+
+    for delta in &diff.changed_types {
+        if delta.total_size_delta > growth_threshold_bytes {
+            recommendations.push(format!(
+                "Regression: '{}' retained memory grew by {} bytes (from {} to {}), exceeding the {}-byte growth threshold",
+                delta.type_name,
+                delta.total_size_delta,
+                delta.total_size_before,
+                delta.total_size_after,
+                growth_threshold_bytes
+            ));
+        }
+    }
+
+    if let Some(leaked_delta) = diff.leaked_memory_delta {
+        if leaked_delta > 0 {
+            recommendations.push(format!(
+                "Regression: leaked memory increased by {leaked_delta} bytes between runs"
+            ));
+        }
+    }
+
+    let new_hotspots: Vec<&str> = diff
+        .added_types
+        .iter()
+        .filter(|name| diff.newly_appeared_hotspots.contains(name))
+        .map(String::as_str)
+        .collect();
+    if !new_hotspots.is_empty() {
+        recommendations.push(format!(
+            "Regression: newly appeared memory hotspots with no baseline: {}",
+            new_hotspots.join(", ")
+        ));
+    }
+
+    if recommendations.is_empty() {
+        recommendations.push("No memory regressions detected between snapshots".to_string());
+    }
+
+    recommendations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{MemoryStats, TypeMemoryUsage};
+    use serde_json::json;
+
+    #[test]
+    fn test_added_and_removed_types() {
+        let before = json!({
+            "type_usage": [{"type": "String", "total_size": 100, "count": 1}],
+            "performance_metrics": {},
+            "analysis_summary": {"memory_hotspots": []},
+        });
+        let after = json!({
+            "type_usage": [{"type": "Vec<u8>", "total_size": 200, "count": 2}],
+            "performance_metrics": {},
+            "analysis_summary": {"memory_hotspots": []},
+        });
+
+        let diff = diff_dashboards(&before, &after);
+        assert_eq!(diff["added_types"], json!(["Vec<u8>"]));
+        assert_eq!(diff["removed_types"], json!(["String"]));
+        assert!(diff["changed_types"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_changed_type_reports_deltas_sorted_by_absolute_size() {
+        let before = json!({
+            "type_usage": [
+                {"type": "String", "total_size": 100, "count": 1},
+                {"type": "Vec<u8>", "total_size": 1000, "count": 1},
+            ],
+            "performance_metrics": {},
+            "analysis_summary": {"memory_hotspots": []},
+        });
+        let after = json!({
+            "type_usage": [
+                {"type": "String", "total_size": 150, "count": 2},
+                {"type": "Vec<u8>", "total_size": 500, "count": 1},
+            ],
+            "performance_metrics": {},
+            "analysis_summary": {"memory_hotspots": []},
+        });
+
+        let diff = diff_dashboards(&before, &after);
+        let changed = diff["changed_types"].as_array().unwrap();
+        assert_eq!(changed.len(), 2);
+        // Vec<u8> moved by 500 bytes, String by 50 -- Vec<u8> sorts first.
+        assert_eq!(changed[0]["type_name"], "Vec<u8>");
+        assert_eq!(changed[0]["total_size_delta"], -500);
+        assert_eq!(changed[1]["type_name"], "String");
+        assert_eq!(changed[1]["total_size_delta"], 50);
+        assert_eq!(changed[1]["allocation_count_delta"], 1);
+    }
+
+    #[test]
+    fn test_newly_appeared_hotspot_and_severity_crossing() {
+        let before = json!({
+            "type_usage": [{"type": "Buffer", "total_size": 1000, "count": 1}],
+            "performance_metrics": {},
+            "analysis_summary": {"memory_hotspots": [{"type": "Buffer", "total_size": 1000, "allocation_count": 1, "severity": "medium"}]},
+        });
+        let after = json!({
+            "type_usage": [{"type": "Buffer", "total_size": 2 * 1024 * 1024, "count": 1}],
+            "performance_metrics": {},
+            "analysis_summary": {"memory_hotspots": [{"type": "Buffer", "total_size": 2 * 1024 * 1024, "allocation_count": 1, "severity": "high"}]},
+        });
+
+        let diff = diff_dashboards(&before, &after);
+        assert!(diff["newly_appeared_hotspots"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+        assert_eq!(diff["severity_crossed_types"], json!(["Buffer"]));
+    }
+
+    #[test]
+    fn test_performance_metric_deltas() {
+        let before = json!({
+            "type_usage": [],
+            "performance_metrics": {"memory_efficiency": 80.0, "fragmentation_ratio": 0.1},
+            "analysis_summary": {"memory_hotspots": []},
+        });
+        let after = json!({
+            "type_usage": [],
+            "performance_metrics": {"memory_efficiency": 60.0, "fragmentation_ratio": 0.4},
+            "analysis_summary": {"memory_hotspots": []},
+        });
+
+        let diff = diff_dashboards(&before, &after);
+        assert_eq!(diff["memory_efficiency_delta"], json!(-20.0));
+        assert!((diff["fragmentation_ratio_delta"].as_f64().unwrap() - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_diff_type_analysis_uses_type_analysis_keying() {
+        let before = json!({
+            "type_analysis": [{"type_name": "String", "total_size": 100, "allocation_count": 1}],
+            "memory_hotspots": [],
+        });
+        let after = json!({
+            "type_analysis": [{"type_name": "String", "total_size": 300, "allocation_count": 3}],
+            "memory_hotspots": [],
+        });
+
+        let diff = diff_type_analysis(&before, &after);
+        let changed = diff["changed_types"].as_array().unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0]["total_size_delta"], 200);
+        assert_eq!(changed[0]["allocation_count_delta"], 2);
+    }
+
+    fn type_usage(name: &str, current_size: usize, allocation_count: usize) -> TypeMemoryUsage {
+        TypeMemoryUsage {
+            type_name: name.to_string(),
+            total_size: current_size,
+            allocation_count,
+            average_size: 0,
+            current_size,
+            efficiency_score: 0.0,
+            peak_size: current_size,
+        }
+    }
+
+    #[test]
+    fn test_diff_memory_stats_reports_leaked_and_fragmentation_deltas() {
+        let mut before_stats = MemoryStats::new();
+        before_stats.leaked_memory = 100;
+        before_stats.leaked_allocations = 1;
+        before_stats.fragmentation_analysis.fragmentation_ratio = 0.1;
+
+        let mut after_stats = MemoryStats::new();
+        after_stats.leaked_memory = 500;
+        after_stats.leaked_allocations = 3;
+        after_stats.fragmentation_analysis.fragmentation_ratio = 0.4;
+
+        let before_types = vec![type_usage("String", 100, 1)];
+        let after_types = vec![type_usage("String", 300, 2), type_usage("Vec<u8>", 1000, 1)];
+
+        let diff = diff_memory_stats(&before_stats, &before_types, &after_stats, &after_types);
+        assert_eq!(diff.leaked_memory_delta, Some(400));
+        assert_eq!(diff.leaked_allocations_delta, Some(2));
+        assert!((diff.fragmentation_ratio_delta.unwrap() - 0.3).abs() < 1e-9);
+        assert_eq!(diff.added_types, vec!["Vec<u8>".to_string()]);
+        assert_eq!(diff.changed_types.len(), 1);
+        assert_eq!(diff.changed_types[0].total_size_delta, 200);
+    }
+
+    #[test]
+    fn test_generate_regression_recommendations_flags_growth_beyond_threshold() {
+        let before_stats = MemoryStats::new();
+        let after_stats = MemoryStats::new();
+        let before_types = vec![type_usage("Buffer", 1000, 1)];
+        let after_types = vec![type_usage("Buffer", 1_000_000, 1)];
+
+        let diff = diff_memory_stats(&before_stats, &before_types, &after_stats, &after_types);
+        let recommendations = generate_regression_recommendations(&diff, 10_000);
+        assert!(recommendations
+            .iter()
+            .any(|r| r.contains("Buffer") && r.contains("growth threshold")));
+    }
+
+    #[test]
+    fn test_generate_regression_recommendations_flags_leak_increase() {
+        let mut before_stats = MemoryStats::new();
+        before_stats.leaked_memory = 0;
+        let mut after_stats = MemoryStats::new();
+        after_stats.leaked_memory = 5000;
+
+        let diff = diff_memory_stats(&before_stats, &[], &after_stats, &[]);
+        let recommendations = generate_regression_recommendations(&diff, i64::MAX);
+        assert!(recommendations
+            .iter()
+            .any(|r| r.contains("leaked memory increased")));
+    }
+
+    #[test]
+    fn test_generate_regression_recommendations_reports_healthy_when_no_regressions() {
+        let stats = MemoryStats::new();
+        let diff = diff_memory_stats(&stats, &[], &stats, &[]);
+        let recommendations = generate_regression_recommendations(&diff, i64::MAX);
+        assert_eq!(recommendations.len(), 1);
+        assert!(recommendations[0].contains("No memory regressions"));
+    }
+}
@@ -0,0 +1,207 @@
+//! Content-fingerprint deduplication analysis using BLAKE3.
+//!
+//! Computes a BLAKE3 fingerprint over each allocation's observable content --
+//! size and type name, which is all this tracker captures without copying
+//! live memory -- and groups allocations that share an identical fingerprint
+//! into a [`DuplicateCluster`]. Clusters surface repeated immutable buffers
+//! (strings, config blobs) that are natural candidates for interning or
+//! `Arc`-sharing instead of being copied. BLAKE3 is used for its speed and
+//! built-in parallelism, so hashing thousands of allocations stays cheap;
+//! this pass is meant to run alongside [`analyze_external_fragmentation`](crate::analysis::analyze_external_fragmentation)
+//! over the same allocation slice.
+
+use crate::analysis::backtrace_sites::resolve_stack_trace;
+use crate::core::types::AllocationInfo;
+use std::collections::HashMap;
+
+/// A group of allocations that share the same size and type name. This is a
+/// heuristic, not proof of byte-identical content: the tracker never copies
+/// live memory, so two allocations of the same size and type are reported as
+/// duplicates even if their actual bytes differ (e.g. same-sized `Vec<u8>`
+/// read buffers with different contents). Treat `wasted_bytes` as an upper
+/// bound worth investigating, not a precise measurement.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DuplicateCluster {
+    /// Hex-encoded BLAKE3 fingerprint shared by every allocation in the cluster.
+    pub fingerprint: String,
+    /// Addresses of the duplicate allocations.
+    pub addresses: Vec<usize>,
+    /// Size of each allocation in the cluster (identical by construction).
+    pub allocation_size: usize,
+    /// Bytes wasted by the duplicates: `allocation_size * (count - 1)`.
+    pub wasted_bytes: usize,
+}
+
+/// A call site ranked by how many wasted bytes it's responsible for.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OffendingCallSite {
+    /// Human-readable call site, built from the resolved stack trace.
+    pub site: String,
+    /// Wasted bytes attributed to this call site.
+    pub wasted_bytes: usize,
+}
+
+/// Report produced by [`analyze_deduplication`].
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct DeduplicationAnalysis {
+    /// Clusters of two or more allocations sharing the same size and type
+    /// (not verified byte-identical -- see [`DuplicateCluster`]), largest
+    /// waste first.
+    pub clusters: Vec<DuplicateCluster>,
+    /// Total bytes wasted across all clusters.
+    pub total_wasted_bytes: usize,
+    /// Call sites responsible for the most wasted bytes, highest first.
+    pub top_offending_call_sites: Vec<OffendingCallSite>,
+}
+
+/// Fingerprint an allocation's observable content. The tracker never copies
+/// live memory, so the fingerprint covers everything it does capture -- size
+/// and type name -- rather than raw bytes.
+fn fingerprint_allocation(allocation: &AllocationInfo) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&allocation.size.to_le_bytes());
+    hasher.update(allocation.type_name.as_deref().unwrap_or("").as_bytes());
+    hasher.finalize().to_hex().to_string()
+}
+
+/// Render an allocation's captured stack trace into a single human-readable
+/// call-site string, e.g. `"allocate at src/lib.rs:42"`. Allocations with no
+/// captured stack trace fall back to `"<unknown>"`.
+fn call_site_label(allocation: &AllocationInfo) -> String {
+    let Some(raw_frames) = &allocation.stack_trace else {
+        return "<unknown>".to_string();
+    };
+
+    match resolve_stack_trace(raw_frames).first() {
+        Some(frame) => match (&frame.fn_name, &frame.filename, frame.lineno) {
+            (Some(name), Some(file), Some(line)) => format!("{name} at {file}:{line}"),
+            (Some(name), _, _) => name.clone(),
+            _ => "<unknown>".to_string(),
+        },
+        None => "<unknown>".to_string(),
+    }
+}
+
+/// Find groups of allocations with an identical content fingerprint, report
+/// the bytes wasted by keeping duplicate copies around instead of interning
+/// or `Arc`-sharing them, and rank the call sites responsible for the most
+/// waste.
+pub fn analyze_deduplication(allocations: &[AllocationInfo]) -> DeduplicationAnalysis {
+    let mut groups: HashMap<String, Vec<&AllocationInfo>> = HashMap::new();
+    for allocation in allocations {
+        groups
+            .entry(fingerprint_allocation(allocation))
+            .or_default()
+            .push(allocation);
+    }
+
+    let mut clusters = Vec::new();
+    let mut call_site_waste: HashMap<String, usize> = HashMap::new();
+    let mut total_wasted_bytes = 0;
+
+    for (fingerprint, members) in groups {
+        if members.len() < 2 {
+            continue;
+        }
+
+        let allocation_size = members[0].size;
+        let wasted_bytes = allocation_size * (members.len() - 1);
+        total_wasted_bytes += wasted_bytes;
+
+        // The first member is the "original"; every subsequent member is a
+        // duplicate copy whose bytes are wasted, attributed to its call site.
+        for member in members.iter().skip(1) {
+            *call_site_waste.entry(call_site_label(member)).or_insert(0) += member.size;
+        }
+
+        clusters.push(DuplicateCluster {
+            fingerprint,
+            addresses: members.iter().map(|a| a.ptr).collect(),
+            allocation_size,
+            wasted_bytes,
+        });
+    }
+
+    clusters.sort_by(|a, b| b.wasted_bytes.cmp(&a.wasted_bytes));
+
+    let mut top_offending_call_sites: Vec<OffendingCallSite> = call_site_waste
+        .into_iter()
+        .map(|(site, wasted_bytes)| OffendingCallSite { site, wasted_bytes })
+        .collect();
+    top_offending_call_sites.sort_by(|a, b| b.wasted_bytes.cmp(&a.wasted_bytes));
+    top_offending_call_sites.truncate(10);
+
+    DeduplicationAnalysis {
+        clusters,
+        total_wasted_bytes,
+        top_offending_call_sites,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alloc(ptr: usize, size: usize, type_name: &str) -> AllocationInfo {
+        let mut info = AllocationInfo::new(ptr, size);
+        info.type_name = Some(type_name.to_string());
+        info
+    }
+
+    #[test]
+    fn test_no_duplicates_produces_empty_report() {
+        let allocations = vec![alloc(0x1000, 16, "String"), alloc(0x2000, 32, "Vec<u8>")];
+        let report = analyze_deduplication(&allocations);
+        assert!(report.clusters.is_empty());
+        assert_eq!(report.total_wasted_bytes, 0);
+    }
+
+    #[test]
+    fn test_identical_allocations_form_a_cluster() {
+        let allocations = vec![
+            alloc(0x1000, 64, "String"),
+            alloc(0x2000, 64, "String"),
+            alloc(0x3000, 64, "String"),
+        ];
+        let report = analyze_deduplication(&allocations);
+        assert_eq!(report.clusters.len(), 1);
+        assert_eq!(report.clusters[0].addresses.len(), 3);
+        assert_eq!(report.clusters[0].wasted_bytes, 128); // 64 * (3 - 1)
+        assert_eq!(report.total_wasted_bytes, 128);
+    }
+
+    #[test]
+    fn test_different_types_or_sizes_do_not_collide() {
+        let allocations = vec![
+            alloc(0x1000, 64, "String"),
+            alloc(0x2000, 64, "Vec<u8>"),
+            alloc(0x3000, 32, "String"),
+        ];
+        let report = analyze_deduplication(&allocations);
+        assert!(report.clusters.is_empty());
+    }
+
+    #[test]
+    fn test_call_sites_ranked_by_wasted_bytes() {
+        let mut original = alloc(0x1000, 100, "String");
+        original.stack_trace = Some(vec!["build_config at src/config.rs:10".to_string()]);
+        let mut duplicate = alloc(0x2000, 100, "String");
+        duplicate.stack_trace = Some(vec!["build_config at src/config.rs:10".to_string()]);
+
+        let report = analyze_deduplication(&[original, duplicate]);
+        assert_eq!(report.top_offending_call_sites.len(), 1);
+        assert_eq!(
+            report.top_offending_call_sites[0].site,
+            "build_config at src/config.rs:10"
+        );
+        assert_eq!(report.top_offending_call_sites[0].wasted_bytes, 100);
+    }
+
+    #[test]
+    fn test_allocations_without_stack_trace_use_unknown_call_site() {
+        let allocations = vec![alloc(0x1000, 64, "String"), alloc(0x2000, 64, "String")];
+        let report = analyze_deduplication(&allocations);
+        assert_eq!(report.top_offending_call_sites.len(), 1);
+        assert_eq!(report.top_offending_call_sites[0].site, "<unknown>");
+    }
+}
@@ -0,0 +1,628 @@
+//! External memory fragmentation via coalesced free-address-range analysis.
+//!
+//! `build_unified_dashboard_structure` and `generate_optimization_recommendations`
+//! used to approximate fragmentation as `1.0 - active_memory / total_allocated`,
+//! which conflates "memory that was freed" with "memory that is fragmented" and
+//! says nothing about how the free space is actually laid out. This module
+//! instead walks the allocation history, builds the set of freed address
+//! ranges, coalesces adjacent/overlapping ones into maximal free runs, and
+//! derives external fragmentation from the shape of those runs: a single
+//! large free run is not fragmented even if most memory is free, while many
+//! small disjoint runs are fragmented even if little memory is free overall.
+//!
+//! Pointer reuse means the same address range can appear as freed more than
+//! once (allocate, free, reallocate at the same address, free again).
+//! Overlapping freed ranges are merged by union during the sweep, so such a
+//! span is counted once, and a span a still-live allocation has since reused
+//! is not counted as free at all.
+//!
+//! With the optional `jemalloc-stats` feature enabled and jemalloc as the
+//! global allocator, [`analyze_external_fragmentation`] prefers real
+//! allocator-reported fragmentation over this heuristic; see
+//! [`FragmentationSource`].
+
+use crate::core::types::{AllocationInfo, FragmentationAnalysis};
+
+/// Where a [`FragmentationAnalysis`] in an [`ExternalFragmentationReport`]
+/// came from: measured directly from the allocator, or estimated from the
+/// coalesced free-run heuristic below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FragmentationSource {
+    /// Read from jemalloc's own `stats.allocated`/`stats.active`/
+    /// `stats.resident` counters (requires the `jemalloc-stats` feature and
+    /// jemalloc as the global allocator).
+    Measured,
+    /// Derived from the coalesced free-address-range sweep below.
+    Estimated,
+}
+
+impl Default for FragmentationSource {
+    fn default() -> Self {
+        FragmentationSource::Estimated
+    }
+}
+
+/// Reads jemalloc's own epoch counters for true internal/external
+/// fragmentation, bypassing the free-run heuristic entirely. Only compiled
+/// in with the `jemalloc-stats` feature, and only useful when the process's
+/// global allocator actually is jemalloc.
+#[cfg(feature = "jemalloc-stats")]
+mod jemalloc_stats {
+    use super::FragmentationAnalysis;
+
+    /// `internal = (active - allocated) / active`: the gap between bytes the
+    /// application asked for and the bytes jemalloc's active runs actually
+    /// hold, i.e. padding/alignment/size-class overhead.
+    /// `external = (resident - active) / resident`: pages jemalloc still
+    /// holds resident but isn't actively using, i.e. retained/decommittable
+    /// memory.
+    pub fn measure() -> Option<FragmentationAnalysis> {
+        use jemalloc_ctl::{epoch, stats};
+
+        // Advance the stats epoch so the reads below reflect current usage
+        // rather than a stale cached snapshot.
+        epoch::advance().ok()?;
+
+        let allocated = stats::allocated::read().ok()? as f64;
+        let active = stats::active::read().ok()? as f64;
+        let resident = stats::resident::read().ok()? as f64;
+
+        let internal_fragmentation = if active > 0.0 {
+            ((active - allocated) / active).max(0.0)
+        } else {
+            0.0
+        };
+        let external_fragmentation = if resident > 0.0 {
+            ((resident - active) / resident).max(0.0)
+        } else {
+            0.0
+        };
+
+        Some(FragmentationAnalysis {
+            fragmentation_ratio: internal_fragmentation + external_fragmentation,
+            largest_free_block: 0,
+            smallest_free_block: 0,
+            free_block_count: 0,
+            total_free_memory: (resident - allocated).max(0.0) as usize,
+            external_fragmentation,
+            internal_fragmentation,
+        })
+    }
+}
+
+/// One coalesced run of contiguous free address space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FreeRun {
+    start: usize,
+    end: usize,
+}
+
+impl FreeRun {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// A bucketed count of free runs by size, for histogram-style reporting.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct FreeBlockHistogramBucket {
+    /// Human-readable bucket label, e.g. "64KB-1MB".
+    pub label: String,
+    /// Number of free runs whose size falls in this bucket.
+    pub count: usize,
+    /// Total free bytes held by runs in this bucket.
+    pub total_bytes: usize,
+}
+
+/// Full report produced by [`analyze_external_fragmentation`]: the canonical
+/// [`FragmentationAnalysis`] summary plus a size-bucketed breakdown of the
+/// free runs it was computed from.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ExternalFragmentationReport {
+    /// Summary statistics in the shape the rest of the codebase expects.
+    pub analysis: FragmentationAnalysis,
+    /// Free runs bucketed by size, smallest size range first; ranges with no
+    /// runs are omitted. Built from the free-run sweep even when `analysis`
+    /// came from measured jemalloc stats.
+    pub histogram: Vec<FreeBlockHistogramBucket>,
+    /// Whether `analysis` was measured from the allocator or estimated.
+    pub source: FragmentationSource,
+}
+
+/// A vacant address range observed between a dealloc that opened it and the
+/// alloc that later reused (closed) it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MemoryHole {
+    /// Address where the vacant range began.
+    pub start_address: usize,
+    /// Size of the vacant range in bytes.
+    pub size: usize,
+    /// How long the range sat vacant before being reused, in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Per-hole lifetimes plus aggregate churn, from [`analyze_hole_lifetimes`].
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct HoleLifetimeReport {
+    /// Every hole that was opened and later closed during the trace.
+    pub holes: Vec<MemoryHole>,
+    /// Average of `holes[].duration_ms`; `0.0` if no hole ever closed.
+    pub mean_hole_lifetime_ms: f64,
+    /// Holes opened per second over the trace's observed time span, a rough
+    /// measure of how much the address space is churning regardless of how
+    /// long any individual hole lasts.
+    pub churn_rate_per_sec: f64,
+}
+
+/// Sweep-line over `(ptr, size, timestamp_alloc, timestamp_dealloc)` events,
+/// tracking how long freed address ranges sit vacant before being reused.
+///
+/// This distinguishes transient fragmentation (a range freed and immediately
+/// reallocated) from long-lived freed space (a range that stays vacant for a
+/// long time), which a static end-of-trace snapshot like
+/// [`analyze_external_fragmentation`] cannot see.
+///
+/// Events are processed in timestamp order (deallocs before allocs at equal
+/// timestamps, so an address reused at the instant it's freed correctly
+/// closes the hole it opened). Adjacent vacant ranges are merged into a
+/// single hole the same way [`coalesce_free_runs`] merges free runs, so a
+/// hole's reported duration reflects how long its full (possibly merged)
+/// span was vacant. An allocation that only partially fills an open hole
+/// closes that hole's lifetime entirely rather than splitting off the
+/// unfilled remainder as a new hole; the aggregate metrics below don't need
+/// that finer distinction.
+pub fn analyze_hole_lifetimes(allocation_history: &[AllocationInfo]) -> HoleLifetimeReport {
+    #[derive(Clone, Copy)]
+    enum EventKind {
+        Dealloc,
+        Alloc,
+    }
+
+    struct Event {
+        time_ns: u64,
+        kind: EventKind,
+        ptr: usize,
+        size: usize,
+    }
+
+    let mut events = Vec::new();
+    for alloc in allocation_history {
+        events.push(Event {
+            time_ns: alloc.timestamp_alloc,
+            kind: EventKind::Alloc,
+            ptr: alloc.ptr,
+            size: alloc.size,
+        });
+        if let Some(timestamp_dealloc) = alloc.timestamp_dealloc {
+            events.push(Event {
+                time_ns: timestamp_dealloc,
+                kind: EventKind::Dealloc,
+                ptr: alloc.ptr,
+                size: alloc.size,
+            });
+        }
+    }
+    events.sort_by(|a, b| {
+        a.time_ns
+            .cmp(&b.time_ns)
+            .then_with(|| match (a.kind, b.kind) {
+                (EventKind::Dealloc, EventKind::Alloc) => std::cmp::Ordering::Less,
+                (EventKind::Alloc, EventKind::Dealloc) => std::cmp::Ordering::Greater,
+                _ => std::cmp::Ordering::Equal,
+            })
+    });
+
+    // Open holes keyed by start address, each mapping to (end address, time
+    // the (possibly merged) span became fully vacant).
+    let mut open_holes: std::collections::BTreeMap<usize, (usize, u64)> =
+        std::collections::BTreeMap::new();
+    let mut closed_holes = Vec::new();
+    let mut holes_opened: u64 = 0;
+    let mut first_time = None;
+    let mut last_time = None;
+
+    for event in &events {
+        first_time.get_or_insert(event.time_ns);
+        last_time = Some(event.time_ns);
+
+        match event.kind {
+            EventKind::Dealloc => {
+                let mut start = event.ptr;
+                let mut end = event.ptr + event.size;
+                let mut opened_at = event.time_ns;
+
+                if let Some((&prev_start, &(prev_end, prev_opened))) =
+                    open_holes.range(..start).next_back()
+                {
+                    if prev_end == start {
+                        open_holes.remove(&prev_start);
+                        start = prev_start;
+                        opened_at = opened_at.max(prev_opened);
+                    }
+                }
+                if let Some((&next_start, &(next_end, next_opened))) =
+                    open_holes.range(end..).next()
+                {
+                    if next_start == end {
+                        open_holes.remove(&next_start);
+                        end = next_end;
+                        opened_at = opened_at.max(next_opened);
+                    }
+                }
+
+                open_holes.insert(start, (end, opened_at));
+                holes_opened += 1;
+            }
+            EventKind::Alloc => {
+                if let Some((&hole_start, &(hole_end, opened_at))) =
+                    open_holes.range(..=event.ptr).next_back()
+                {
+                    if hole_start <= event.ptr && event.ptr < hole_end {
+                        open_holes.remove(&hole_start);
+                        let duration_ms = event.time_ns.saturating_sub(opened_at) / 1_000_000;
+                        closed_holes.push(MemoryHole {
+                            start_address: hole_start,
+                            size: hole_end - hole_start,
+                            duration_ms,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let mean_hole_lifetime_ms = if closed_holes.is_empty() {
+        0.0
+    } else {
+        closed_holes
+            .iter()
+            .map(|hole| hole.duration_ms as f64)
+            .sum::<f64>()
+            / closed_holes.len() as f64
+    };
+
+    let churn_rate_per_sec = match (first_time, last_time) {
+        (Some(first), Some(last)) if last > first => {
+            holes_opened as f64 / ((last - first) as f64 / 1_000_000_000.0)
+        }
+        _ => 0.0,
+    };
+
+    HoleLifetimeReport {
+        holes: closed_holes,
+        mean_hole_lifetime_ms,
+        churn_rate_per_sec,
+    }
+}
+
+/// Coalesce the freed ranges in `allocation_history` into maximal free runs
+/// and derive external fragmentation from their distribution.
+///
+/// Runs in O(n log n): a single sort of the freed intervals by start address
+/// followed by a linear sweep.
+pub fn analyze_external_fragmentation(
+    allocation_history: &[AllocationInfo],
+) -> ExternalFragmentationReport {
+    #[cfg(feature = "jemalloc-stats")]
+    if let Some(analysis) = jemalloc_stats::measure() {
+        return ExternalFragmentationReport {
+            analysis,
+            histogram: free_block_histogram(&coalesce_free_runs(allocation_history)),
+            source: FragmentationSource::Measured,
+        };
+    }
+
+    let free_runs = coalesce_free_runs(allocation_history);
+
+    let total_free_memory: usize = free_runs.iter().map(FreeRun::len).sum();
+    let largest_free_block = free_runs.iter().map(FreeRun::len).max().unwrap_or(0);
+    let smallest_free_block = free_runs.iter().map(FreeRun::len).min().unwrap_or(0);
+
+    let external_fragmentation = if total_free_memory > 0 {
+        1.0 - (largest_free_block as f64 / total_free_memory as f64)
+    } else {
+        0.0
+    };
+
+    let analysis = FragmentationAnalysis {
+        fragmentation_ratio: external_fragmentation,
+        largest_free_block,
+        smallest_free_block,
+        free_block_count: free_runs.len(),
+        total_free_memory,
+        external_fragmentation,
+        internal_fragmentation: 0.0,
+    };
+
+    ExternalFragmentationReport {
+        analysis,
+        histogram: free_block_histogram(&free_runs),
+        source: FragmentationSource::Estimated,
+    }
+}
+
+/// Build the coalesced free-run list for `allocation_history`.
+///
+/// A freed interval only counts as free where no later, still-live
+/// allocation (`timestamp_dealloc.is_none()`) has since reused that address
+/// range — `AllocationHistoryManager` mutates `timestamp_dealloc` in place on
+/// the same history entry rather than removing it, so a freed entry and a
+/// live entry can legitimately share overlapping addresses when a pointer is
+/// reused. Live ranges are subtracted out before coalescing.
+fn coalesce_free_runs(allocation_history: &[AllocationInfo]) -> Vec<FreeRun> {
+    // Sort by start address (most recently freed first at equal starts).
+    // Intervals that overlap due to pointer reuse are merged by union in
+    // `push_free_run`, so their relative order doesn't change the result.
+    let mut intervals: Vec<(usize, usize, u64)> = allocation_history
+        .iter()
+        .filter_map(|alloc| {
+            let timestamp_dealloc = alloc.timestamp_dealloc?;
+            Some((alloc.ptr, alloc.ptr + alloc.size, timestamp_dealloc))
+        })
+        .collect();
+    intervals.sort_by(|a, b| a.0.cmp(&b.0).then(b.2.cmp(&a.2)));
+
+    let mut live_ranges: Vec<(usize, usize)> = allocation_history
+        .iter()
+        .filter(|alloc| alloc.timestamp_dealloc.is_none())
+        .map(|alloc| (alloc.ptr, alloc.ptr + alloc.size))
+        .collect();
+    live_ranges.sort_by_key(|&(start, _)| start);
+    let mut merged_live: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in live_ranges {
+        match merged_live.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged_live.push((start, end)),
+        }
+    }
+
+    let mut runs: Vec<FreeRun> = Vec::new();
+    let mut live_cursor = 0usize;
+    for (start, end, _timestamp_dealloc) in intervals {
+        // Live ranges are scanned left to right once overall: freed interval
+        // starts are non-decreasing, so a live range fully behind the
+        // current interval stays behind for every later one too.
+        while live_cursor < merged_live.len() && merged_live[live_cursor].1 <= start {
+            live_cursor += 1;
+        }
+
+        let mut piece_start = start;
+        let mut idx = live_cursor;
+        while piece_start < end {
+            match merged_live.get(idx) {
+                Some(&(live_start, live_end)) if live_start < end => {
+                    if live_start > piece_start {
+                        push_free_run(&mut runs, piece_start, live_start);
+                    }
+                    piece_start = piece_start.max(live_end);
+                    idx += 1;
+                }
+                _ => {
+                    push_free_run(&mut runs, piece_start, end);
+                    break;
+                }
+            }
+        }
+    }
+    runs
+}
+
+/// Append `[start, end)` to `runs`, coalescing it into the last run if it
+/// touches or overlaps it.
+fn push_free_run(runs: &mut Vec<FreeRun>, start: usize, end: usize) {
+    match runs.last_mut() {
+        Some(last) if start <= last.end => {
+            last.end = last.end.max(end);
+        }
+        _ => runs.push(FreeRun { start, end }),
+    }
+}
+
+/// Bucket free runs by size into fixed, human-readable ranges.
+fn free_block_histogram(free_runs: &[FreeRun]) -> Vec<FreeBlockHistogramBucket> {
+    const BUCKETS: &[(&str, usize, usize)] = &[
+        ("<1KB", 0, 1024),
+        ("1KB-64KB", 1024, 64 * 1024),
+        ("64KB-1MB", 64 * 1024, 1024 * 1024),
+        (">1MB", 1024 * 1024, usize::MAX),
+    ];
+
+    BUCKETS
+        .iter()
+        .filter_map(|&(label, lower, upper)| {
+            let matching: Vec<usize> = free_runs
+                .iter()
+                .map(FreeRun::len)
+                .filter(|&len| len >= lower && len < upper)
+                .collect();
+            if matching.is_empty() {
+                return None;
+            }
+            Some(FreeBlockHistogramBucket {
+                label: label.to_string(),
+                count: matching.len(),
+                total_bytes: matching.iter().sum(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn freed_alloc(ptr: usize, size: usize, timestamp_dealloc: u64) -> AllocationInfo {
+        let mut alloc = AllocationInfo::new(ptr, size);
+        alloc.timestamp_dealloc = Some(timestamp_dealloc);
+        alloc
+    }
+
+    fn timed_alloc(
+        ptr: usize,
+        size: usize,
+        timestamp_alloc: u64,
+        timestamp_dealloc: Option<u64>,
+    ) -> AllocationInfo {
+        let mut alloc = AllocationInfo::new(ptr, size);
+        alloc.timestamp_alloc = timestamp_alloc;
+        alloc.timestamp_dealloc = timestamp_dealloc;
+        alloc
+    }
+
+    #[test]
+    fn test_no_freed_allocations_reports_zero_fragmentation() {
+        let history = vec![AllocationInfo::new(0x1000, 64)];
+        let report = analyze_external_fragmentation(&history);
+        assert_eq!(report.analysis.free_block_count, 0);
+        assert_eq!(report.analysis.total_free_memory, 0);
+        assert_eq!(report.analysis.external_fragmentation, 0.0);
+        assert!(report.histogram.is_empty());
+    }
+
+    #[test]
+    fn test_single_free_run_has_zero_external_fragmentation() {
+        let history = vec![freed_alloc(0x1000, 256, 1)];
+        let report = analyze_external_fragmentation(&history);
+        assert_eq!(report.analysis.free_block_count, 1);
+        assert_eq!(report.analysis.total_free_memory, 256);
+        assert_eq!(report.analysis.largest_free_block, 256);
+        assert_eq!(report.analysis.external_fragmentation, 0.0);
+    }
+
+    #[test]
+    fn test_adjacent_ranges_coalesce_into_one_run() {
+        // [0x1000, 0x1100) and [0x1100, 0x1200) touch exactly at 0x1100.
+        let history = vec![freed_alloc(0x1000, 0x100, 1), freed_alloc(0x1100, 0x100, 2)];
+        let report = analyze_external_fragmentation(&history);
+        assert_eq!(report.analysis.free_block_count, 1);
+        assert_eq!(report.analysis.largest_free_block, 0x200);
+    }
+
+    #[test]
+    fn test_disjoint_ranges_produce_fragmented_report() {
+        let history = vec![
+            freed_alloc(0x1000, 0x100, 1),
+            freed_alloc(0x2000, 0x100, 2),
+            freed_alloc(0x3000, 0x100, 3),
+        ];
+        let report = analyze_external_fragmentation(&history);
+        assert_eq!(report.analysis.free_block_count, 3);
+        assert_eq!(report.analysis.total_free_memory, 0x300);
+        assert_eq!(report.analysis.largest_free_block, 0x100);
+        // Three equal-sized disjoint runs: largest / total = 1/3.
+        assert!((report.analysis.external_fragmentation - (1.0 - 1.0 / 3.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_overlapping_ranges_from_pointer_reuse_coalesce_by_union() {
+        // A reused pointer freed twice: the later, shorter-lived allocation
+        // overlaps the first. Both spans are free now, so they coalesce.
+        let history = vec![freed_alloc(0x1000, 0x200, 1), freed_alloc(0x1100, 0x200, 5)];
+        let report = analyze_external_fragmentation(&history);
+        assert_eq!(report.analysis.free_block_count, 1);
+        assert_eq!(report.analysis.largest_free_block, 0x300);
+    }
+
+    #[test]
+    fn test_live_reallocation_is_not_counted_as_free() {
+        // Freed at 0x1000/0x100, then reallocated at the same address and
+        // never freed again. The freed entry must not make this range count
+        // as free: the address is currently occupied.
+        let history = vec![
+            freed_alloc(0x1000, 0x100, 1),
+            timed_alloc(0x1000, 0x100, 2, None),
+        ];
+        let report = analyze_external_fragmentation(&history);
+        assert_eq!(report.analysis.free_block_count, 0);
+        assert_eq!(report.analysis.total_free_memory, 0);
+    }
+
+    #[test]
+    fn test_live_reallocation_splits_a_larger_freed_run() {
+        // A freed 0x1000..0x1300 run with a still-live 0x1100..0x1200 hole
+        // reused inside it should report two smaller free runs, not one.
+        let history = vec![
+            freed_alloc(0x1000, 0x300, 1),
+            timed_alloc(0x1100, 0x100, 2, None),
+        ];
+        let report = analyze_external_fragmentation(&history);
+        assert_eq!(report.analysis.free_block_count, 2);
+        assert_eq!(report.analysis.total_free_memory, 0x200);
+        assert_eq!(report.analysis.largest_free_block, 0x100);
+    }
+
+    #[test]
+    fn test_histogram_buckets_by_size() {
+        let history = vec![
+            freed_alloc(0x1000, 512, 1),
+            freed_alloc(0x3000, 2048, 2),
+            freed_alloc(0x6000, 2 * 1024 * 1024, 3),
+        ];
+        let report = analyze_external_fragmentation(&history);
+        assert_eq!(report.histogram.len(), 3);
+        assert!(report
+            .histogram
+            .iter()
+            .any(|b| b.label == "<1KB" && b.count == 1));
+        assert!(report
+            .histogram
+            .iter()
+            .any(|b| b.label == "1KB-64KB" && b.count == 1));
+        assert!(report
+            .histogram
+            .iter()
+            .any(|b| b.label == ">1MB" && b.count == 1));
+    }
+
+    #[test]
+    fn test_hole_closes_when_address_is_reused() {
+        let history = vec![
+            timed_alloc(0x1000, 0x100, 0, Some(10_000_000)),
+            timed_alloc(0x1000, 0x50, 60_000_000, None),
+        ];
+        let report = analyze_hole_lifetimes(&history);
+        assert_eq!(report.holes.len(), 1);
+        assert_eq!(report.holes[0].start_address, 0x1000);
+        assert_eq!(report.holes[0].size, 0x100);
+        assert_eq!(report.holes[0].duration_ms, 50);
+        assert_eq!(report.mean_hole_lifetime_ms, 50.0);
+    }
+
+    #[test]
+    fn test_hole_with_no_reuse_stays_open_and_unreported() {
+        let history = vec![timed_alloc(0x2000, 0x100, 0, Some(5_000_000))];
+        let report = analyze_hole_lifetimes(&history);
+        assert!(report.holes.is_empty());
+        assert_eq!(report.mean_hole_lifetime_ms, 0.0);
+    }
+
+    #[test]
+    fn test_adjacent_holes_merge_before_closing() {
+        // [0x1000,0x1100) frees at t=1s, [0x1100,0x1200) frees at t=1.02s;
+        // they merge into one contiguous hole, vacant as a whole only since
+        // the later of the two opening times (1.02s). An allocation landing
+        // inside the merged span at t=1.05s closes it as a single hole.
+        let history = vec![
+            timed_alloc(0x1000, 0x100, 100, Some(1_000_000_000)),
+            timed_alloc(0x1100, 0x100, 200, Some(1_020_000_000)),
+            timed_alloc(0x1050, 0x10, 1_050_000_000, None),
+        ];
+        let report = analyze_hole_lifetimes(&history);
+        assert_eq!(report.holes.len(), 1);
+        assert_eq!(report.holes[0].start_address, 0x1000);
+        assert_eq!(report.holes[0].size, 0x200);
+        assert_eq!(report.holes[0].duration_ms, 30);
+    }
+
+    #[test]
+    fn test_churn_rate_counts_holes_opened_per_second() {
+        let history = vec![
+            timed_alloc(0x1000, 0x10, 0, Some(500_000_000)),
+            timed_alloc(0x2000, 0x10, 100_000_000, Some(1_000_000_000)),
+            timed_alloc(0x3000, 0x10, 200_000_000, Some(2_000_000_000)),
+        ];
+        let report = analyze_hole_lifetimes(&history);
+        assert!(report.holes.is_empty());
+        assert!((report.churn_rate_per_sec - 1.5).abs() < 1e-9);
+    }
+}
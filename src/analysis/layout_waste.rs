@@ -0,0 +1,220 @@
+//! Per-type memory-layout and padding-waste analysis.
+//!
+//! [`crate::core::types::AllocationInfo::memory_layout`] already records a
+//! field-level breakdown (offset, size, alignment) for allocations whose
+//! type layout was captured, but nothing aggregates it across a run. This
+//! module groups live allocations by `type_name`, accumulates total live
+//! bytes/instance count/average size, and derives the padding bytes wasted
+//! per instance directly from the field layout: the sum of gaps between
+//! consecutive fields' end offsets and the next field's start, plus tail
+//! padding up to the type's total size. The result is a ranked report (most
+//! wasted bytes first) that points straight at struct-reordering
+//! opportunities, the same way compiler code-stats tools surface
+//! variant/field sizes.
+
+use crate::core::types::{AllocationInfo, FieldLayoutInfo, MemoryLayoutInfo};
+use std::collections::HashMap;
+
+/// Padding waste and size summary for one distinct `type_name`.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct TypeLayoutWaste {
+    /// The type this summary is for.
+    pub type_name: String,
+    /// Number of live instances of this type considered.
+    pub instance_count: usize,
+    /// Sum of `size` across all live instances of this type.
+    pub total_live_bytes: usize,
+    /// `total_live_bytes / instance_count`.
+    pub average_size: f64,
+    /// Padding bytes wasted per instance, derived from one representative
+    /// instance's field layout (`0` when no instance carried layout info).
+    pub padding_bytes_per_instance: usize,
+    /// `padding_bytes_per_instance * instance_count`.
+    pub total_wasted_bytes: usize,
+    /// Field-level breakdown from the representative instance used to
+    /// derive `padding_bytes_per_instance`, if any instance carried one.
+    pub representative_field_layout: Vec<FieldLayoutInfo>,
+}
+
+/// Ranked padding-waste report produced by [`analyze_padding_waste`].
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct LayoutWasteReport {
+    /// Per-type summaries, sorted by `total_wasted_bytes` descending.
+    pub types: Vec<TypeLayoutWaste>,
+}
+
+/// Sum of gaps between consecutive fields (sorted by offset) plus tail
+/// padding up to `layout.total_size`. Returns `0` when `layout` has no field
+/// layout captured.
+pub fn compute_padding_waste(layout: &MemoryLayoutInfo) -> usize {
+    if layout.field_layout.is_empty() {
+        return 0;
+    }
+
+    let mut fields = layout.field_layout.clone();
+    fields.sort_by_key(|field| field.offset);
+
+    let mut wasted = 0usize;
+    let mut last_end = 0usize;
+    for field in &fields {
+        if field.offset > last_end {
+            wasted += field.offset - last_end;
+        }
+        last_end = field.offset + field.size;
+    }
+    wasted += layout.total_size.saturating_sub(last_end);
+    wasted
+}
+
+/// Aggregate `allocations` into a ranked per-type padding-waste report.
+pub fn analyze_padding_waste(allocations: &[AllocationInfo]) -> LayoutWasteReport {
+    let mut by_type: HashMap<String, TypeLayoutWaste> = HashMap::new();
+
+    for allocation in allocations {
+        let type_name = allocation
+            .type_name
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+        let entry = by_type
+            .entry(type_name.clone())
+            .or_insert_with(|| TypeLayoutWaste {
+                type_name,
+                ..Default::default()
+            });
+        entry.instance_count += 1;
+        entry.total_live_bytes += allocation.size;
+
+        if entry.representative_field_layout.is_empty() {
+            if let Some(layout) = &allocation.memory_layout {
+                if !layout.field_layout.is_empty() {
+                    entry.padding_bytes_per_instance = compute_padding_waste(layout);
+                    entry.representative_field_layout = layout.field_layout.clone();
+                }
+            }
+        }
+    }
+
+    let mut types: Vec<TypeLayoutWaste> = by_type
+        .into_values()
+        .map(|mut summary| {
+            summary.average_size = if summary.instance_count > 0 {
+                summary.total_live_bytes as f64 / summary.instance_count as f64
+            } else {
+                0.0
+            };
+            summary.total_wasted_bytes = summary
+                .padding_bytes_per_instance
+                .saturating_mul(summary.instance_count);
+            summary
+        })
+        .collect();
+    types.sort_by_key(|summary| std::cmp::Reverse(summary.total_wasted_bytes));
+
+    LayoutWasteReport { types }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{LayoutEfficiency, OptimizationPotential, PaddingAnalysis};
+
+    fn field(name: &str, offset: usize, size: usize, alignment: usize) -> FieldLayoutInfo {
+        FieldLayoutInfo {
+            field_name: name.to_string(),
+            field_type: "T".to_string(),
+            offset,
+            size,
+            alignment,
+            is_padding: false,
+        }
+    }
+
+    fn layout(
+        total_size: usize,
+        alignment: usize,
+        fields: Vec<FieldLayoutInfo>,
+    ) -> MemoryLayoutInfo {
+        MemoryLayoutInfo {
+            total_size,
+            alignment,
+            field_layout: fields,
+            padding_info: PaddingAnalysis {
+                total_padding_bytes: 0,
+                padding_locations: Vec::new(),
+                padding_ratio: 0.0,
+                optimization_suggestions: Vec::new(),
+            },
+            layout_efficiency: LayoutEfficiency {
+                memory_utilization: 1.0,
+                cache_friendliness: 100.0,
+                alignment_waste: 0,
+                optimization_potential: OptimizationPotential::None,
+            },
+        }
+    }
+
+    fn allocation(
+        ptr: usize,
+        size: usize,
+        type_name: &str,
+        layout: Option<MemoryLayoutInfo>,
+    ) -> AllocationInfo {
+        let mut info = AllocationInfo::new(ptr, size);
+        info.type_name = Some(type_name.to_string());
+        info.memory_layout = layout;
+        info
+    }
+
+    #[test]
+    fn test_compute_padding_waste_sums_internal_gaps_and_tail() {
+        // bool (1 byte) at offset 0, then u32 (4 bytes, align 4) at offset 4:
+        // 3 bytes internal gap; total_size 8 means 0 tail padding here.
+        let layout = layout(8, 4, vec![field("flag", 0, 1, 1), field("count", 4, 4, 4)]);
+        assert_eq!(compute_padding_waste(&layout), 3);
+    }
+
+    #[test]
+    fn test_compute_padding_waste_includes_tail_padding() {
+        let layout = layout(16, 8, vec![field("flag", 0, 1, 1)]);
+        assert_eq!(compute_padding_waste(&layout), 15);
+    }
+
+    #[test]
+    fn test_compute_padding_waste_is_zero_without_field_layout() {
+        let layout = layout(8, 4, vec![]);
+        assert_eq!(compute_padding_waste(&layout), 0);
+    }
+
+    #[test]
+    fn test_analyze_padding_waste_groups_by_type_and_computes_average_size() {
+        let allocations = vec![
+            allocation(0x1000, 16, "Widget", None),
+            allocation(0x2000, 24, "Widget", None),
+        ];
+        let report = analyze_padding_waste(&allocations);
+        let widget = report
+            .types
+            .iter()
+            .find(|t| t.type_name == "Widget")
+            .unwrap();
+        assert_eq!(widget.instance_count, 2);
+        assert_eq!(widget.total_live_bytes, 40);
+        assert_eq!(widget.average_size, 20.0);
+    }
+
+    #[test]
+    fn test_analyze_padding_waste_ranks_types_by_total_wasted_bytes_descending() {
+        let small_waste_layout = layout(8, 4, vec![field("a", 0, 4, 4), field("b", 4, 4, 4)]);
+        let big_waste_layout = layout(16, 8, vec![field("a", 0, 1, 1)]);
+
+        let allocations = vec![
+            allocation(0x1000, 8, "Tight", Some(small_waste_layout)),
+            allocation(0x2000, 16, "Wasteful", Some(big_waste_layout)),
+        ];
+        let report = analyze_padding_waste(&allocations);
+        assert_eq!(report.types[0].type_name, "Wasteful");
+        assert_eq!(report.types[0].total_wasted_bytes, 15);
+        assert_eq!(report.types[1].type_name, "Tight");
+        assert_eq!(report.types[1].total_wasted_bytes, 0);
+    }
+}
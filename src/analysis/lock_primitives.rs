@@ -0,0 +1,272 @@
+//! Synchronization-primitive classification for concurrency analysis.
+//!
+//! The concurrency heuristics historically matched only the substrings
+//! `Mutex`, `Arc`, `Rc`, and `atomic_`, so `parking_lot::Mutex`,
+//! `parking_lot::RwLock`, `parking_lot::FairMutex`, and std `RwLock` guards
+//! were invisible to the breakdown. This module classifies an allocation's
+//! type name into a [`LockKind`], tracks read-lock vs write-lock guard
+//! allocations separately, and factors lock fairness into the deadlock risk
+//! score: fair mutexes reduce starvation risk while unfair ones raise
+//! contention risk under many threads.
+
+use crate::core::types::AllocationInfo;
+
+/// Which synchronization primitive produced a guard/lock allocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum LockKind {
+    StdMutex,
+    StdRwLock,
+    ParkingLotMutex,
+    ParkingLotFairMutex,
+    ParkingLotRwLock,
+}
+
+impl LockKind {
+    /// Whether this primitive is fair (FIFO-ordered acquisition), which
+    /// reduces starvation risk at the cost of some throughput under
+    /// uncontended access.
+    pub fn is_fair(self) -> bool {
+        matches!(self, LockKind::ParkingLotFairMutex)
+    }
+
+    /// Whether this primitive distinguishes shared (read) and exclusive
+    /// (write) guards.
+    pub fn is_rw_lock(self) -> bool {
+        matches!(self, LockKind::StdRwLock | LockKind::ParkingLotRwLock)
+    }
+}
+
+/// Classify a type name (as recorded on `AllocationInfo::type_name`) into
+/// the synchronization primitive it belongs to, if recognized. Checked most
+/// specific to least specific, since `parking_lot::RwLock` and
+/// `parking_lot::FairMutex` guard type names also contain `Mutex`/`RwLock`.
+pub fn classify_lock_kind(type_name: &str) -> Option<LockKind> {
+    if type_name.contains("FairMutex") {
+        Some(LockKind::ParkingLotFairMutex)
+    } else if type_name.contains("parking_lot") && type_name.contains("RwLock") {
+        Some(LockKind::ParkingLotRwLock)
+    } else if type_name.contains("parking_lot") && type_name.contains("Mutex") {
+        Some(LockKind::ParkingLotMutex)
+    } else if type_name.contains("RwLock") {
+        Some(LockKind::StdRwLock)
+    } else if type_name.contains("Mutex") {
+        Some(LockKind::StdMutex)
+    } else {
+        None
+    }
+}
+
+/// Whether a type name is an `RwLock` read-guard, as opposed to a write
+/// guard or a plain `Mutex` guard.
+fn is_read_guard(type_name: &str) -> bool {
+    type_name.contains("ReadGuard")
+}
+
+/// Whether a type name is an `RwLock` write-guard or a `Mutex` guard.
+fn is_write_guard(type_name: &str) -> bool {
+    type_name.contains("WriteGuard") || type_name.contains("MutexGuard")
+}
+
+/// Breakdown of synchronization-primitive allocations by [`LockKind`], with
+/// read/write guard splits and a fairness-aware deadlock risk score.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct LockPrimitiveReport {
+    /// Allocations classified as `std::sync::Mutex`.
+    pub std_mutex_count: usize,
+    /// Allocations classified as `std::sync::RwLock`.
+    pub std_rwlock_count: usize,
+    /// Allocations classified as `parking_lot::Mutex`.
+    pub parking_lot_mutex_count: usize,
+    /// Allocations classified as `parking_lot::FairMutex`.
+    pub parking_lot_fair_mutex_count: usize,
+    /// Allocations classified as `parking_lot::RwLock`.
+    pub parking_lot_rwlock_count: usize,
+    /// `RwLock` read-guard allocations, across both std and parking_lot.
+    pub read_guard_count: usize,
+    /// `RwLock` write-guard and `Mutex` guard allocations.
+    pub write_guard_count: usize,
+    /// Fairness- and contention-adjusted deadlock risk in `[0.0, 1.0]`.
+    pub deadlock_risk_score: f64,
+    /// Human-readable bucketing of `deadlock_risk_score` ("low"/"medium"/"high").
+    pub lock_contention_risk: String,
+}
+
+impl LockPrimitiveReport {
+    /// Total allocations classified into any [`LockKind`].
+    pub fn total_lock_allocations(&self) -> usize {
+        self.std_mutex_count
+            + self.std_rwlock_count
+            + self.parking_lot_mutex_count
+            + self.parking_lot_fair_mutex_count
+            + self.parking_lot_rwlock_count
+    }
+}
+
+/// Classify every allocation's recorded type name into a [`LockKind`], tally
+/// read/write guards, and derive a fairness-aware deadlock risk score.
+/// Guard allocations are tallied only as read/write guards, not under their
+/// [`LockKind`].
+pub fn analyze_lock_primitives(allocations: &[AllocationInfo]) -> LockPrimitiveReport {
+    let mut report = LockPrimitiveReport::default();
+
+    for alloc in allocations {
+        let Some(type_name) = alloc.type_name.as_deref() else {
+            continue;
+        };
+
+        if is_read_guard(type_name) {
+            report.read_guard_count += 1;
+        } else if is_write_guard(type_name) {
+            report.write_guard_count += 1;
+        } else if let Some(kind) = classify_lock_kind(type_name) {
+            match kind {
+                LockKind::StdMutex => report.std_mutex_count += 1,
+                LockKind::StdRwLock => report.std_rwlock_count += 1,
+                LockKind::ParkingLotMutex => report.parking_lot_mutex_count += 1,
+                LockKind::ParkingLotFairMutex => report.parking_lot_fair_mutex_count += 1,
+                LockKind::ParkingLotRwLock => report.parking_lot_rwlock_count += 1,
+            }
+        }
+    }
+
+    report.deadlock_risk_score = calculate_deadlock_risk(&report);
+    report.lock_contention_risk = assess_lock_contention_risk(report.deadlock_risk_score);
+    report
+}
+
+/// Fairness-aware deadlock/starvation risk in `[0.0, 1.0]`.
+///
+/// Using more distinct lock primitives at once raises the risk of nested
+/// acquisition ordering mistakes. Beyond that, unfair primitives (std
+/// `Mutex`/`RwLock`, `parking_lot::Mutex`/`RwLock`) raise contention risk
+/// under many concurrent lockers since an unlucky thread can be repeatedly
+/// skipped by the OS scheduler's wakeup order; `parking_lot::FairMutex`
+/// grants the lock in FIFO order instead, which lowers starvation risk at
+/// the cost of some uncontended throughput.
+fn calculate_deadlock_risk(report: &LockPrimitiveReport) -> f64 {
+    let total = report.total_lock_allocations();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let distinct_kinds = [
+        report.std_mutex_count,
+        report.std_rwlock_count,
+        report.parking_lot_mutex_count,
+        report.parking_lot_fair_mutex_count,
+        report.parking_lot_rwlock_count,
+    ]
+    .iter()
+    .filter(|&&count| count > 0)
+    .count();
+    let mut risk = (distinct_kinds as f64 - 1.0).max(0.0) * 0.15;
+
+    let unfair = report.std_mutex_count
+        + report.std_rwlock_count
+        + report.parking_lot_mutex_count
+        + report.parking_lot_rwlock_count;
+    let fair = report.parking_lot_fair_mutex_count;
+
+    risk += (unfair as f64 / total as f64) * 0.5;
+    risk -= (fair as f64 / total as f64) * 0.2;
+
+    risk.clamp(0.0, 1.0)
+}
+
+/// Bucket a deadlock risk score into a human-readable risk level.
+fn assess_lock_contention_risk(deadlock_risk_score: f64) -> String {
+    if deadlock_risk_score >= 0.7 {
+        "high".to_string()
+    } else if deadlock_risk_score >= 0.3 {
+        "medium".to_string()
+    } else {
+        "low".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alloc_with_type(type_name: &str) -> AllocationInfo {
+        let mut alloc = AllocationInfo::new(0x1000, 8);
+        alloc.type_name = Some(type_name.to_string());
+        alloc
+    }
+
+    #[test]
+    fn test_classifies_std_and_parking_lot_primitives() {
+        assert_eq!(
+            classify_lock_kind("std::sync::Mutex<i32>"),
+            Some(LockKind::StdMutex)
+        );
+        assert_eq!(
+            classify_lock_kind("std::sync::RwLock<Vec<u8>>"),
+            Some(LockKind::StdRwLock)
+        );
+        assert_eq!(
+            classify_lock_kind("parking_lot::Mutex<i32>"),
+            Some(LockKind::ParkingLotMutex)
+        );
+        assert_eq!(
+            classify_lock_kind("parking_lot::RwLock<i32>"),
+            Some(LockKind::ParkingLotRwLock)
+        );
+        assert_eq!(
+            classify_lock_kind("parking_lot::FairMutex<i32>"),
+            Some(LockKind::ParkingLotFairMutex)
+        );
+        assert_eq!(classify_lock_kind("String"), None);
+    }
+
+    #[test]
+    fn test_analyze_lock_primitives_counts_each_kind() {
+        let allocations = vec![
+            alloc_with_type("std::sync::Mutex<i32>"),
+            alloc_with_type("parking_lot::RwLock<i32>"),
+            alloc_with_type("parking_lot::RwLockReadGuard<i32>"),
+            alloc_with_type("parking_lot::RwLockWriteGuard<i32>"),
+            alloc_with_type("std::sync::MutexGuard<i32>"),
+        ];
+        let report = analyze_lock_primitives(&allocations);
+        assert_eq!(report.std_mutex_count, 1);
+        assert_eq!(report.parking_lot_rwlock_count, 1);
+        assert_eq!(report.read_guard_count, 1);
+        assert_eq!(report.write_guard_count, 2);
+    }
+
+    #[test]
+    fn test_fair_mutex_lowers_risk_versus_unfair_equivalent() {
+        let unfair = vec![
+            alloc_with_type("parking_lot::Mutex<i32>"),
+            alloc_with_type("parking_lot::Mutex<i32>"),
+        ];
+        let fair = vec![
+            alloc_with_type("parking_lot::FairMutex<i32>"),
+            alloc_with_type("parking_lot::FairMutex<i32>"),
+        ];
+        let unfair_report = analyze_lock_primitives(&unfair);
+        let fair_report = analyze_lock_primitives(&fair);
+        assert!(fair_report.deadlock_risk_score < unfair_report.deadlock_risk_score);
+    }
+
+    #[test]
+    fn test_no_lock_allocations_reports_zero_risk() {
+        let allocations = vec![alloc_with_type("String")];
+        let report = analyze_lock_primitives(&allocations);
+        assert_eq!(report.total_lock_allocations(), 0);
+        assert_eq!(report.deadlock_risk_score, 0.0);
+        assert_eq!(report.lock_contention_risk, "low");
+    }
+
+    #[test]
+    fn test_many_distinct_kinds_raises_risk() {
+        let allocations = vec![
+            alloc_with_type("std::sync::Mutex<i32>"),
+            alloc_with_type("std::sync::RwLock<i32>"),
+            alloc_with_type("parking_lot::Mutex<i32>"),
+        ];
+        let report = analyze_lock_primitives(&allocations);
+        assert!(report.deadlock_risk_score > 0.0);
+    }
+}
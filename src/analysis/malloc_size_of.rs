@@ -0,0 +1,285 @@
+//! `MallocSizeOf`-style deep heap-size measurement.
+//!
+//! `TypeMemoryUsage::current_size` is bookkeeping of *requested* allocation
+//! sizes, so a `Vec<Vec<u8>>` with a single shallow entry per outer `Vec` is
+//! indistinguishable from one whose entries each hold megabytes. This module
+//! adds a recursive "how much heap does this value actually keep alive"
+//! measurement, following Gecko/Servo's `MallocSizeOf` trait: each type sums
+//! its own heap-allocated block(s) plus the deep size of anything it owns,
+//! via a shared [`MallocSizeOfOps`] that tracks visited pointers so a shared
+//! `Rc`/`Arc` -- or a cycle -- is only charged once.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Per-measurement state shared across a `size_of` call tree: which heap
+/// blocks have already been counted, and how the allocator's real usable
+/// size of a pointer is obtained.
+pub struct MallocSizeOfOps {
+    seen_pointers: std::collections::HashSet<usize>,
+    enclosing_size_hook: Option<Box<dyn Fn(usize, usize) -> usize>>,
+}
+
+impl Default for MallocSizeOfOps {
+    fn default() -> Self {
+        Self {
+            seen_pointers: std::collections::HashSet::new(),
+            enclosing_size_hook: None,
+        }
+    }
+}
+
+impl MallocSizeOfOps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install a platform hook for the allocator's real usable size of a
+    /// pointer, given the pointer and the size originally requested -- e.g.
+    /// a hook backed by `malloc_usable_size` on platforms that expose it.
+    /// Without a hook installed, the requested size is used as-is.
+    pub fn with_enclosing_size_hook(
+        mut self,
+        hook: impl Fn(usize, usize) -> usize + 'static,
+    ) -> Self {
+        self.enclosing_size_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// The allocator's real usable size for a block at `ptr` requested at
+    /// `requested_size` bytes.
+    fn enclosing_size(&self, ptr: usize, requested_size: usize) -> usize {
+        match &self.enclosing_size_hook {
+            Some(hook) => hook(ptr, requested_size),
+            None => requested_size,
+        }
+    }
+
+    /// Mark `ptr` as measured, returning `true` the first time it's seen in
+    /// this measurement (the caller should count it) and `false` if it's
+    /// already been charged to another owner -- the mechanism that keeps a
+    /// shared `Rc`/`Arc`, or a cycle, from being double-counted.
+    pub fn mark_seen(&mut self, ptr: usize) -> bool {
+        // A null/dangling pointer (e.g. an empty Vec's data pointer) owns no
+        // heap block to dedupe against.
+        ptr == 0 || self.seen_pointers.insert(ptr)
+    }
+}
+
+/// A type whose deep, recursive heap footprint can be measured.
+///
+/// `size_of` returns this value's own heap-allocated block(s) plus the deep
+/// size of anything it owns -- not `std::mem::size_of::<Self>()`, the stack
+/// footprint, which the caller already accounts for via the containing
+/// allocation.
+pub trait MallocSizeOf {
+    /// Recursively measure this value's owned heap footprint, in bytes.
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize;
+}
+
+macro_rules! impl_malloc_size_of_stack_only {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl MallocSizeOf for $ty {
+                fn size_of(&self, _ops: &mut MallocSizeOfOps) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_malloc_size_of_stack_only!(
+    (),
+    bool,
+    char,
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize
+);
+
+impl MallocSizeOf for String {
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        if self.capacity() == 0 || !ops.mark_seen(self.as_ptr() as usize) {
+            return 0;
+        }
+        ops.enclosing_size(self.as_ptr() as usize, self.capacity())
+    }
+}
+
+impl<T: MallocSizeOf> MallocSizeOf for Vec<T> {
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        let backing = if self.capacity() == 0 || !ops.mark_seen(self.as_ptr() as usize) {
+            0
+        } else {
+            ops.enclosing_size(
+                self.as_ptr() as usize,
+                self.capacity() * std::mem::size_of::<T>(),
+            )
+        };
+        backing + self.iter().map(|item| item.size_of(ops)).sum::<usize>()
+    }
+}
+
+impl<T: MallocSizeOf> MallocSizeOf for Option<T> {
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        match self {
+            Some(value) => value.size_of(ops),
+            None => 0,
+        }
+    }
+}
+
+impl<T: MallocSizeOf> MallocSizeOf for Box<T> {
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        let ptr = self.as_ref() as *const T as usize;
+        let own_block = if ops.mark_seen(ptr) {
+            ops.enclosing_size(ptr, std::mem::size_of::<T>())
+        } else {
+            0
+        };
+        own_block + self.as_ref().size_of(ops)
+    }
+}
+
+impl<K: MallocSizeOf, V: MallocSizeOf, S> MallocSizeOf for HashMap<K, V, S> {
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        let table_ptr = self as *const Self as usize;
+        let table = if self.capacity() == 0 || !ops.mark_seen(table_ptr) {
+            0
+        } else {
+            ops.enclosing_size(
+                table_ptr,
+                self.capacity() * (std::mem::size_of::<K>() + std::mem::size_of::<V>()),
+            )
+        };
+        table
+            + self
+                .iter()
+                .map(|(k, v)| k.size_of(ops) + v.size_of(ops))
+                .sum::<usize>()
+    }
+}
+
+impl<T: MallocSizeOf> MallocSizeOf for Rc<T> {
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        let ptr = Rc::as_ptr(self) as usize;
+        if !ops.mark_seen(ptr) {
+            return 0;
+        }
+        ops.enclosing_size(ptr, std::mem::size_of::<T>()) + self.as_ref().size_of(ops)
+    }
+}
+
+impl<T: MallocSizeOf> MallocSizeOf for Arc<T> {
+    fn size_of(&self, ops: &mut MallocSizeOfOps) -> usize {
+        let ptr = Arc::as_ptr(self) as usize;
+        if !ops.mark_seen(ptr) {
+            return 0;
+        }
+        ops.enclosing_size(ptr, std::mem::size_of::<T>()) + self.as_ref().size_of(ops)
+    }
+}
+
+/// Recompute `usage.current_size` from a deep [`MallocSizeOf`] measurement
+/// of a representative `sample`, scaled by the type's recorded allocation
+/// count, and refresh `usage.efficiency_score` against `usage.peak_size`.
+///
+/// This is an opt-in refinement for callers that hold a live sample value:
+/// `TypeMemoryUsage` is normally computed from aggregate `AllocationInfo`
+/// bookkeeping (request sizes only), so there's nowhere in that pipeline to
+/// plug in a real per-value measurement. A caller that tracked a live `T`
+/// can call this afterwards to replace the shallow estimate with the deep
+/// one, letting recommendations distinguish many small allocations from a
+/// few holding large owned graphs.
+pub fn apply_deep_size_sample<T: MallocSizeOf>(
+    usage: &mut crate::core::types::TypeMemoryUsage,
+    sample: &T,
+) {
+    let mut ops = MallocSizeOfOps::new();
+    let deep_size_per_instance = std::mem::size_of::<T>() + sample.size_of(&mut ops);
+    usage.current_size = deep_size_per_instance.saturating_mul(usage.allocation_count.max(1));
+    usage.efficiency_score = if usage.peak_size > 0 {
+        (usage.current_size as f64 / usage.peak_size as f64).min(1.0)
+    } else {
+        usage.efficiency_score
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::TypeMemoryUsage;
+
+    #[test]
+    fn test_primitive_has_no_heap_footprint() {
+        let mut ops = MallocSizeOfOps::new();
+        assert_eq!(42u64.size_of(&mut ops), 0);
+    }
+
+    #[test]
+    fn test_string_counts_its_heap_buffer() {
+        let s = String::from("hello world");
+        let mut ops = MallocSizeOfOps::new();
+        assert_eq!(s.size_of(&mut ops), s.capacity());
+    }
+
+    #[test]
+    fn test_vec_counts_backing_buffer_and_elements() {
+        let v: Vec<String> = vec![String::from("a"), String::from("bb")];
+        let mut ops = MallocSizeOfOps::new();
+        let expected =
+            v.capacity() * std::mem::size_of::<String>() + v[0].capacity() + v[1].capacity();
+        assert_eq!(v.size_of(&mut ops), expected);
+    }
+
+    #[test]
+    fn test_shared_rc_is_only_counted_once() {
+        let shared = Rc::new(String::from("shared payload"));
+        let a = Rc::clone(&shared);
+        let b = Rc::clone(&shared);
+        let mut ops = MallocSizeOfOps::new();
+        let first = a.size_of(&mut ops);
+        let second = b.size_of(&mut ops);
+        assert!(first > 0);
+        assert_eq!(second, 0);
+    }
+
+    #[test]
+    fn test_enclosing_size_hook_overrides_requested_size() {
+        let s = String::from("hi");
+        let mut ops = MallocSizeOfOps::new().with_enclosing_size_hook(|_ptr, _requested| 999);
+        assert_eq!(s.size_of(&mut ops), 999);
+    }
+
+    #[test]
+    fn test_apply_deep_size_sample_updates_current_size_and_efficiency() {
+        let mut usage = TypeMemoryUsage {
+            type_name: "Vec<String>".to_string(),
+            total_size: 100,
+            allocation_count: 2,
+            average_size: 50,
+            current_size: 100,
+            efficiency_score: 0.0,
+            peak_size: 200,
+        };
+        let sample: Vec<String> = vec![String::from("abcdefgh")];
+        apply_deep_size_sample(&mut usage, &sample);
+        let mut ops = MallocSizeOfOps::new();
+        let per_instance = std::mem::size_of::<Vec<String>>() + sample.size_of(&mut ops);
+        assert_eq!(usage.current_size, per_instance * 2);
+        assert!(usage.efficiency_score > 0.0);
+    }
+}
@@ -0,0 +1,196 @@
+//! Per-owner memory budgets with reservation tracking.
+//!
+//! `generate_optimization_recommendations` only ever emits a handful of fixed
+//! global heuristics (fragmentation ratio, efficiency, large-allocation
+//! count). This module lets callers assert a byte ceiling per "owner" --
+//! either a scope name (`"parser"`) or a type name (`"Vec<u8>"`) -- and
+//! reports which owners blew their budget.
+//!
+//! Rather than only looking at the final snapshot, [`check_memory_budgets`]
+//! replays `allocation_history` as a timeline of `+size`/`-size` events per
+//! owner and tracks both the steady-state total (what's left at the end) and
+//! the peak reservation seen at any point, since a transient spike can blow a
+//! budget even if the process looks fine by the time export runs.
+
+use crate::core::types::AllocationInfo;
+use std::collections::HashMap;
+
+/// Byte limit per owner key (scope name or type name).
+pub type MemoryBudgets = HashMap<String, usize>;
+
+/// An owner whose peak reservation exceeded its configured budget.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BudgetFinding {
+    /// The scope or type name the budget was assigned to.
+    pub owner: String,
+    /// The configured byte limit.
+    pub limit: usize,
+    /// Memory still attributed to this owner at the end of `allocation_history`.
+    pub steady_state: usize,
+    /// The highest memory attributed to this owner at any point in time.
+    pub peak_observed: usize,
+    /// `peak_observed - limit`.
+    pub overshoot: usize,
+}
+
+/// Check each owner in `budgets` against its peak reservation across
+/// `allocation_history`, returning one [`BudgetFinding`] per owner whose peak
+/// exceeded its limit, worst overshoot first.
+///
+/// An allocation contributes to both its scope-name owner and its type-name
+/// owner, since a budget may be keyed by either.
+pub fn check_memory_budgets(
+    allocation_history: &[AllocationInfo],
+    budgets: &MemoryBudgets,
+) -> Vec<BudgetFinding> {
+    if budgets.is_empty() {
+        return Vec::new();
+    }
+
+    // (timestamp, signed size delta) events per owner, built only for owners
+    // that actually have a budget configured.
+    let mut events: HashMap<&str, Vec<(u64, i64)>> = HashMap::new();
+    for alloc in allocation_history {
+        for owner in [alloc.scope_name.as_deref(), alloc.type_name.as_deref()]
+            .into_iter()
+            .flatten()
+        {
+            if !budgets.contains_key(owner) {
+                continue;
+            }
+            let owner_events = events.entry(owner).or_default();
+            owner_events.push((alloc.timestamp_alloc, alloc.size as i64));
+            if let Some(timestamp_dealloc) = alloc.timestamp_dealloc {
+                owner_events.push((timestamp_dealloc, -(alloc.size as i64)));
+            }
+        }
+    }
+
+    let mut findings: Vec<BudgetFinding> = budgets
+        .iter()
+        .filter_map(|(owner, &limit)| {
+            let owner_events = events.get(owner.as_str())?;
+            let mut sorted = owner_events.clone();
+            sorted.sort_by_key(|(timestamp, _)| *timestamp);
+
+            let mut cumulative: i64 = 0;
+            let mut peak: i64 = 0;
+            for (_, delta) in &sorted {
+                cumulative += delta;
+                peak = peak.max(cumulative);
+            }
+            let peak_observed = peak.max(0) as usize;
+            let steady_state = cumulative.max(0) as usize;
+
+            if peak_observed > limit {
+                Some(BudgetFinding {
+                    owner: owner.clone(),
+                    limit,
+                    steady_state,
+                    peak_observed,
+                    overshoot: peak_observed - limit,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    findings.sort_by(|a, b| b.overshoot.cmp(&a.overshoot));
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alloc(
+        scope_name: Option<&str>,
+        type_name: Option<&str>,
+        size: usize,
+        timestamp_alloc: u64,
+        timestamp_dealloc: Option<u64>,
+    ) -> AllocationInfo {
+        let mut a = AllocationInfo::new(0x1000, size);
+        a.scope_name = scope_name.map(str::to_string);
+        a.type_name = type_name.map(str::to_string);
+        a.timestamp_alloc = timestamp_alloc;
+        a.timestamp_dealloc = timestamp_dealloc;
+        a
+    }
+
+    #[test]
+    fn test_no_budgets_means_no_findings() {
+        let history = vec![alloc(Some("parser"), None, 10_000_000, 0, None)];
+        assert!(check_memory_budgets(&history, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_owner_within_budget_has_no_finding() {
+        let history = vec![alloc(Some("parser"), None, 1024, 0, None)];
+        let budgets = HashMap::from([("parser".to_string(), 4 * 1024 * 1024)]);
+        assert!(check_memory_budgets(&history, &budgets).is_empty());
+    }
+
+    #[test]
+    fn test_steady_state_over_budget_is_reported() {
+        let history = vec![alloc(Some("parser"), None, 5 * 1024 * 1024, 0, None)];
+        let budgets = HashMap::from([("parser".to_string(), 4 * 1024 * 1024)]);
+        let findings = check_memory_budgets(&history, &budgets);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].owner, "parser");
+        assert_eq!(findings[0].steady_state, 5 * 1024 * 1024);
+        assert_eq!(findings[0].peak_observed, 5 * 1024 * 1024);
+        assert_eq!(findings[0].overshoot, 1024 * 1024);
+    }
+
+    #[test]
+    fn test_transient_peak_over_budget_is_caught_even_after_freeing() {
+        // Two 3MB allocations overlap briefly, pushing the peak to 6MB, then
+        // one is freed leaving a steady-state of 3MB -- comfortably under a
+        // 4MB budget. The peak should still be flagged.
+        let history = vec![
+            alloc(Some("parser"), None, 3 * 1024 * 1024, 0, Some(10)),
+            alloc(Some("parser"), None, 3 * 1024 * 1024, 5, None),
+        ];
+        let budgets = HashMap::from([("parser".to_string(), 4 * 1024 * 1024)]);
+        let findings = check_memory_budgets(&history, &budgets);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].peak_observed, 6 * 1024 * 1024);
+        assert_eq!(findings[0].steady_state, 3 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_allocation_contributes_to_both_scope_and_type_owner() {
+        let history = vec![alloc(
+            Some("parser"),
+            Some("Vec<u8>"),
+            20 * 1024 * 1024,
+            0,
+            None,
+        )];
+        let budgets = HashMap::from([
+            ("parser".to_string(), 4 * 1024 * 1024),
+            ("Vec<u8>".to_string(), 16 * 1024 * 1024),
+        ]);
+        let findings = check_memory_budgets(&history, &budgets);
+        let owners: Vec<&str> = findings.iter().map(|f| f.owner.as_str()).collect();
+        assert!(owners.contains(&"parser"));
+        assert!(owners.contains(&"Vec<u8>"));
+    }
+
+    #[test]
+    fn test_findings_sorted_by_overshoot_descending() {
+        let history = vec![
+            alloc(Some("parser"), None, 5 * 1024 * 1024, 0, None),
+            alloc(Some("renderer"), None, 20 * 1024 * 1024, 0, None),
+        ];
+        let budgets = HashMap::from([
+            ("parser".to_string(), 4 * 1024 * 1024),
+            ("renderer".to_string(), 4 * 1024 * 1024),
+        ]);
+        let findings = check_memory_budgets(&history, &budgets);
+        assert_eq!(findings[0].owner, "renderer");
+        assert_eq!(findings[1].owner, "parser");
+    }
+}
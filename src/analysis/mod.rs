@@ -14,18 +14,35 @@ pub mod variable_relationships;
 
 // New analysis modules for ComplexTypeForRust.md features
 pub mod async_analysis;
+pub mod backtrace_sites;
 pub mod borrow_analysis;
+pub mod cache_alignment;
 pub mod closure_analysis;
+pub mod dashboard_diff;
+pub mod deduplication;
 pub mod enhanced_ffi_function_resolver;
 pub mod ffi_function_resolver;
+pub mod fragmentation;
 pub mod generic_analysis;
+pub mod layout_waste;
 pub mod lifecycle_analysis;
+pub mod lock_primitives;
+pub mod malloc_size_of;
+pub mod memory_budget;
 pub mod memory_passport_tracker;
+pub mod ownership_flow;
+pub mod parallel_scan;
 pub mod safety_analyzer;
+pub mod search_index;
 pub mod security_violation_analyzer;
+pub mod system_memory;
+pub mod type_interner;
 
 // Re-export key analysis functions
-pub use circular_reference::{CircularReference, CircularReferenceAnalysis, CircularReferenceNode};
+pub use circular_reference::{
+    detect_leak_risks, CircularReference, CircularReferenceAnalysis, CircularReferenceNode,
+    LeakRisk,
+};
 pub use enhanced_memory_analysis::{analyze_memory_with_enhanced_features, EnhancedMemoryAnalyzer};
 pub use unsafe_ffi_tracker::UnsafeFFITracker;
 pub use variable_relationships::{
@@ -38,25 +55,59 @@ pub use variable_relationships::{
 pub use async_analysis::{
     get_global_async_analyzer, AsyncAnalyzer, AsyncPatternAnalysis, AsyncStatistics,
 };
+pub use backtrace_sites::{
+    group_allocation_sites, resolve_stack_trace, AllocationSite, ResolvedFrame,
+};
 pub use borrow_analysis::{get_global_borrow_analyzer, BorrowAnalyzer, BorrowPatternAnalysis};
+pub use cache_alignment::{
+    analyze_cache_alignment, generate_cache_alignment_recommendations, AlignmentOffsetBucket,
+    CacheAlignmentReport, TypeCacheAlignment, DEFAULT_CACHE_LINE_SIZE,
+};
 pub use closure_analysis::{get_global_closure_analyzer, ClosureAnalysisReport, ClosureAnalyzer};
+pub use dashboard_diff::{
+    diff_dashboards, diff_memory_stats, diff_type_analysis, generate_regression_recommendations,
+    DashboardDiff, TypeUsageDelta,
+};
+pub use deduplication::{
+    analyze_deduplication, DeduplicationAnalysis, DuplicateCluster, OffendingCallSite,
+};
 pub use ffi_function_resolver::{
     get_global_ffi_resolver, initialize_global_ffi_resolver, FfiFunctionCategory,
     FfiFunctionResolver, FfiRiskLevel, ResolutionStats, ResolvedFfiFunction, ResolverConfig,
 };
+pub use fragmentation::{
+    analyze_external_fragmentation, analyze_hole_lifetimes, ExternalFragmentationReport,
+    FragmentationSource, FreeBlockHistogramBucket, HoleLifetimeReport, MemoryHole,
+};
 pub use generic_analysis::{get_global_generic_analyzer, GenericAnalyzer, GenericStatistics};
+pub use layout_waste::{analyze_padding_waste, compute_padding_waste, LayoutWasteReport, TypeLayoutWaste};
 pub use lifecycle_analysis::{
     get_global_lifecycle_analyzer, LifecycleAnalysisReport, LifecycleAnalyzer,
 };
+pub use lock_primitives::{analyze_lock_primitives, LockKind, LockPrimitiveReport};
+pub use memory_budget::{check_memory_budgets, BudgetFinding, MemoryBudgets};
 pub use memory_passport_tracker::{
     get_global_passport_tracker, initialize_global_passport_tracker, LeakDetail,
     LeakDetectionResult, MemoryPassport, MemoryPassportTracker, PassportEvent, PassportEventType,
     PassportStatus, PassportTrackerConfig, PassportTrackerStats,
 };
+pub use ownership_flow::{
+    analyze_ownership_flow, OwnershipState, OwnershipViolation, ViolationKind,
+};
+pub use parallel_scan::{
+    analyze_concurrency_safety_parallel, analyze_system_libraries_parallel,
+    calculate_size_distribution_parallel, ParallelConfig,
+};
 pub use safety_analyzer::{
     DynamicViolation, RiskAssessment, RiskFactor, RiskFactorType, SafetyAnalysisConfig,
     SafetyAnalysisStats, SafetyAnalyzer, UnsafeReport, UnsafeSource,
 };
+pub use malloc_size_of::{apply_deep_size_sample, MallocSizeOf, MallocSizeOfOps};
+pub use search_index::{build_search_index, query_dashboard, MatchedAllocation, SearchIndex};
+pub use system_memory::{check_system_memory, total_system_memory, MaxMemory, SystemMemoryCheck};
+pub use type_interner::{
+    count_allocations_by_type, intern_allocation_type_names, TypeNameHandle, TypeNameInterner,
+};
 pub use unsafe_ffi_tracker::ComprehensiveSafetyReport;
 
 use crate::core::types::*;
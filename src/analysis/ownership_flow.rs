@@ -0,0 +1,387 @@
+//! Forward dataflow analysis over recorded ownership events.
+//!
+//! `generate_lifetime_json` and `build_unified_dashboard_structure` used to
+//! *synthesize* ownership events from heuristics (clone detection via
+//! `var_name.contains("clone")`, fake source pointers via `ptr.wrapping_sub`).
+//! This module instead computes ownership state from the real
+//! [`OwnershipEvent`](crate::core::ownership_history::OwnershipEvent) stream
+//! recorded by `OwnershipHistoryRecorder`, and reports genuine lifetime
+//! violations instead of fictional lifetimes.
+//!
+//! For each allocation pointer, events are sorted by timestamp and folded
+//! through an abstract state machine (`Owned | Shared(n) | MutBorrowed | Moved
+//! | Dropped`). `Cloned { source_ptr }` events additionally join the clone
+//! target's state with the source pointer's state at that timestamp, so a
+//! clone taken while the source was mutably borrowed is still caught even
+//! though it lives in a different pointer's event stream.
+
+use crate::core::ownership_history::{OwnershipEvent, OwnershipEventType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Abstract ownership state tracked per allocation pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnershipState {
+    /// Sole owner, no outstanding borrows.
+    Owned,
+    /// `n` outstanding immutable borrows (or clones sharing the allocation).
+    Shared(u32),
+    /// A single outstanding mutable borrow.
+    MutBorrowed,
+    /// Ownership was moved out; the pointer should no longer be accessed.
+    Moved,
+    /// The allocation was dropped.
+    Dropped,
+}
+
+/// Kind of ownership violation detected by the dataflow pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationKind {
+    /// An event touched the allocation after it had already been dropped.
+    UseAfterDrop,
+    /// A mutable borrow overlapped another borrow or clone: either a mutable
+    /// borrow was taken while one was live, or a borrow or clone was taken
+    /// while a mutable borrow was live.
+    AliasingMutBorrow,
+    /// An event touched the allocation after ownership had been moved away.
+    UseAfterMove,
+}
+
+impl ViolationKind {
+    /// Stable string form, used as the JSON tag in exported reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ViolationKind::UseAfterDrop => "use-after-drop",
+            ViolationKind::AliasingMutBorrow => "aliasing-mut-borrow",
+            ViolationKind::UseAfterMove => "use-after-move",
+        }
+    }
+}
+
+/// A single detected violation for one allocation pointer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnershipViolation {
+    /// Pointer of the allocation the violation was observed on.
+    pub allocation_ptr: usize,
+    /// What kind of violation this is.
+    pub kind: ViolationKind,
+    /// Timestamp of the offending event.
+    pub timestamp: u64,
+    /// Human-readable explanation, suitable for a report line.
+    pub description: String,
+}
+
+/// Apply the transfer function for `event`, returning the resulting state and
+/// a violation if the event conflicts with `state`.
+fn transfer(
+    state: OwnershipState,
+    event: &OwnershipEvent,
+) -> (OwnershipState, Option<ViolationKind>) {
+    use OwnershipEventType::*;
+
+    if state == OwnershipState::Dropped {
+        return (state, Some(ViolationKind::UseAfterDrop));
+    }
+    if state == OwnershipState::Moved {
+        return (state, Some(ViolationKind::UseAfterMove));
+    }
+
+    match &event.event_type {
+        Allocated => (OwnershipState::Owned, None),
+        Cloned { .. } => match state {
+            OwnershipState::MutBorrowed => (
+                OwnershipState::MutBorrowed,
+                Some(ViolationKind::AliasingMutBorrow),
+            ),
+            OwnershipState::Shared(n) => (OwnershipState::Shared(n + 1), None),
+            _ => (OwnershipState::Shared(1), None),
+        },
+        Borrowed { .. } => match state {
+            OwnershipState::MutBorrowed => (
+                OwnershipState::MutBorrowed,
+                Some(ViolationKind::AliasingMutBorrow),
+            ),
+            OwnershipState::Shared(n) => (OwnershipState::Shared(n + 1), None),
+            OwnershipState::Owned => (OwnershipState::Shared(1), None),
+            other => (other, None),
+        },
+        MutablyBorrowed { .. } => match state {
+            OwnershipState::Owned => (OwnershipState::MutBorrowed, None),
+            _ => (
+                OwnershipState::MutBorrowed,
+                Some(ViolationKind::AliasingMutBorrow),
+            ),
+        },
+        BorrowReleased { .. } => match state {
+            OwnershipState::Shared(n) if n > 1 => (OwnershipState::Shared(n - 1), None),
+            OwnershipState::Shared(_) => (OwnershipState::Owned, None),
+            OwnershipState::MutBorrowed => (OwnershipState::Owned, None),
+            other => (other, None),
+        },
+        Dropped => (OwnershipState::Dropped, None),
+        OwnershipTransferred { .. } => (OwnershipState::Moved, None),
+        RefCountChanged { .. } => (state, None),
+    }
+}
+
+/// Join two states observed for the same allocation at the same logical
+/// point (e.g. a clone's initial state joined with its source's state at the
+/// moment of the clone). Any combination that mixes exclusive access
+/// (`MutBorrowed`) with shared access, or touches a `Moved`/`Dropped` value,
+/// collapses to that access's violation kind.
+fn join(a: OwnershipState, b: OwnershipState) -> (OwnershipState, Option<ViolationKind>) {
+    use OwnershipState::*;
+    match (a, b) {
+        (Dropped, _) | (_, Dropped) => (Dropped, Some(ViolationKind::UseAfterDrop)),
+        (Moved, _) | (_, Moved) => (Moved, Some(ViolationKind::UseAfterMove)),
+        (MutBorrowed, Shared(_)) | (Shared(_), MutBorrowed) | (MutBorrowed, MutBorrowed) => {
+            (MutBorrowed, Some(ViolationKind::AliasingMutBorrow))
+        }
+        (Shared(x), Shared(y)) => (Shared(x.max(y)), None),
+        (Shared(n), Owned) | (Owned, Shared(n)) => (Shared(n), None),
+        (Owned, Owned) => (Owned, None),
+        (MutBorrowed, Owned) | (Owned, MutBorrowed) => (MutBorrowed, None),
+    }
+}
+
+/// Run the per-pointer event history up to (and including) `timestamp` and
+/// return the state reached, used to resolve a clone's source state at the
+/// moment of the clone.
+fn state_at(events: &[OwnershipEvent], timestamp: u64) -> OwnershipState {
+    let mut state = OwnershipState::Owned;
+    for event in events {
+        if event.timestamp > timestamp {
+            break;
+        }
+        let (next, _) = transfer(state, event);
+        state = next;
+    }
+    state
+}
+
+/// Compute ownership violations across every recorded allocation pointer.
+///
+/// `events_by_ptr` is the real event history recorded by
+/// `OwnershipHistoryRecorder`, keyed by allocation pointer (not the
+/// heuristic-derived `ownership_events` previously fabricated per export).
+pub fn analyze_ownership_flow(
+    events_by_ptr: &HashMap<usize, Vec<OwnershipEvent>>,
+) -> Vec<OwnershipViolation> {
+    let mut sorted_by_ptr: HashMap<usize, Vec<OwnershipEvent>> =
+        HashMap::with_capacity(events_by_ptr.len());
+    for (ptr, events) in events_by_ptr {
+        let mut events = events.clone();
+        events.sort_by_key(|e| e.timestamp);
+        sorted_by_ptr.insert(*ptr, events);
+    }
+
+    let mut violations = Vec::new();
+
+    for (ptr, events) in &sorted_by_ptr {
+        let mut state = OwnershipState::Owned;
+
+        for event in events {
+            let (mut next_state, mut violation) = transfer(state, event);
+
+            // A clone converges two event streams: join the clone target's
+            // state with the source pointer's state at the clone's timestamp.
+            if let OwnershipEventType::Cloned { source_ptr } = &event.event_type {
+                if let Some(source_events) = sorted_by_ptr.get(source_ptr) {
+                    let source_state = state_at(source_events, event.timestamp);
+                    let (joined, join_violation) = join(next_state, source_state);
+                    next_state = joined;
+                    violation = violation.or(join_violation);
+                }
+            }
+
+            if let Some(kind) = violation {
+                violations.push(OwnershipViolation {
+                    allocation_ptr: *ptr,
+                    kind,
+                    timestamp: event.timestamp,
+                    description: describe_violation(kind, *ptr, event),
+                });
+            }
+
+            state = next_state;
+        }
+    }
+
+    violations.sort_by_key(|v| (v.allocation_ptr, v.timestamp));
+    violations
+}
+
+fn describe_violation(kind: ViolationKind, ptr: usize, event: &OwnershipEvent) -> String {
+    match kind {
+        ViolationKind::UseAfterDrop => format!(
+            "allocation 0x{ptr:x} accessed via {:?} after it was dropped",
+            event.event_type
+        ),
+        ViolationKind::AliasingMutBorrow => format!(
+            "allocation 0x{ptr:x} accessed via {:?} while a mutable borrow overlapped another borrow or clone",
+            event.event_type
+        ),
+        ViolationKind::UseAfterMove => format!(
+            "allocation 0x{ptr:x} accessed via {:?} after ownership was moved",
+            event.event_type
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ownership_history::{
+        OwnershipEvent, OwnershipEventDetails, OwnershipEventType,
+    };
+
+    fn event(timestamp: u64, event_type: OwnershipEventType) -> OwnershipEvent {
+        OwnershipEvent {
+            event_id: timestamp,
+            timestamp,
+            event_type,
+            source_stack_id: 0,
+            details: OwnershipEventDetails {
+                clone_source_ptr: None,
+                transfer_target_var: None,
+                borrower_scope: None,
+                ref_count_info: None,
+                context: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_clean_lifecycle_has_no_violations() {
+        let mut events = HashMap::new();
+        events.insert(
+            0x1000,
+            vec![
+                event(1, OwnershipEventType::Allocated),
+                event(
+                    2,
+                    OwnershipEventType::Borrowed {
+                        borrower_scope: "fn_a".to_string(),
+                    },
+                ),
+                event(
+                    3,
+                    OwnershipEventType::BorrowReleased {
+                        borrower_scope: "fn_a".to_string(),
+                    },
+                ),
+                event(4, OwnershipEventType::Dropped),
+            ],
+        );
+
+        let violations = analyze_ownership_flow(&events);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn test_use_after_drop_detected() {
+        let mut events = HashMap::new();
+        events.insert(
+            0x2000,
+            vec![
+                event(1, OwnershipEventType::Allocated),
+                event(2, OwnershipEventType::Dropped),
+                event(
+                    3,
+                    OwnershipEventType::Borrowed {
+                        borrower_scope: "fn_b".to_string(),
+                    },
+                ),
+            ],
+        );
+
+        let violations = analyze_ownership_flow(&events);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, ViolationKind::UseAfterDrop);
+        assert_eq!(violations[0].timestamp, 3);
+    }
+
+    #[test]
+    fn test_use_after_move_detected() {
+        let mut events = HashMap::new();
+        events.insert(
+            0x3000,
+            vec![
+                event(1, OwnershipEventType::Allocated),
+                event(
+                    2,
+                    OwnershipEventType::OwnershipTransferred {
+                        target_var: "y".to_string(),
+                    },
+                ),
+                event(
+                    3,
+                    OwnershipEventType::MutablyBorrowed {
+                        borrower_scope: "fn_c".to_string(),
+                    },
+                ),
+            ],
+        );
+
+        let violations = analyze_ownership_flow(&events);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, ViolationKind::UseAfterMove);
+    }
+
+    #[test]
+    fn test_aliasing_mut_borrow_detected() {
+        let mut events = HashMap::new();
+        events.insert(
+            0x4000,
+            vec![
+                event(1, OwnershipEventType::Allocated),
+                event(
+                    2,
+                    OwnershipEventType::Borrowed {
+                        borrower_scope: "fn_d".to_string(),
+                    },
+                ),
+                event(
+                    3,
+                    OwnershipEventType::MutablyBorrowed {
+                        borrower_scope: "fn_e".to_string(),
+                    },
+                ),
+            ],
+        );
+
+        let violations = analyze_ownership_flow(&events);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, ViolationKind::AliasingMutBorrow);
+    }
+
+    #[test]
+    fn test_clone_while_source_mutably_borrowed_joins_to_violation() {
+        let mut events = HashMap::new();
+        events.insert(
+            0x5000,
+            vec![
+                event(1, OwnershipEventType::Allocated),
+                event(
+                    2,
+                    OwnershipEventType::MutablyBorrowed {
+                        borrower_scope: "fn_f".to_string(),
+                    },
+                ),
+            ],
+        );
+        events.insert(
+            0x5008,
+            vec![
+                event(2, OwnershipEventType::Allocated),
+                event(3, OwnershipEventType::Cloned { source_ptr: 0x5000 }),
+            ],
+        );
+
+        let violations = analyze_ownership_flow(&events);
+        assert!(violations
+            .iter()
+            .any(|v| v.allocation_ptr == 0x5008 && v.kind == ViolationKind::AliasingMutBorrow));
+    }
+}
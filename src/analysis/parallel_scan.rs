@@ -0,0 +1,409 @@
+//! Rayon-parallelized analysis passes for large allocation captures.
+//!
+//! Classifying a single allocation into a size bucket, a [`SystemLibraryStats`]
+//! category, or a [`ConcurrencyAnalysis`] bucket and folding that contribution
+//! into an accumulator doesn't depend on any other allocation -- these are
+//! associative fold-into-a-map operations. That means the whole pass can be
+//! split across threads with `par_iter().fold(...).reduce(...)`, merging the
+//! per-thread partial accumulators back together with plain addition.
+//! [`ParallelConfig`] lets small snapshots skip rayon's setup overhead by
+//! falling back to the sequential path below `parallel_threshold`. Because
+//! the merge step is just commutative, associative addition, the result is
+//! identical regardless of how many threads did the folding.
+
+use crate::core::types::{AllocationInfo, ConcurrencyAnalysis, SystemLibraryStats};
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Tuning knobs for the parallel analysis passes.
+#[derive(Debug, Clone)]
+pub struct ParallelConfig {
+    /// Parallel processing threshold (only run the parallel path if the
+    /// number of allocations exceeds this value).
+    pub parallel_threshold: usize,
+    /// Maximum number of threads (`None` means use rayon's global default).
+    pub max_threads: Option<usize>,
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        Self {
+            parallel_threshold: 10_000,
+            max_threads: None,
+        }
+    }
+}
+
+impl ParallelConfig {
+    fn use_parallel(&self, allocation_count: usize) -> bool {
+        allocation_count > self.parallel_threshold
+    }
+
+    /// Apply `max_threads` to rayon's global thread pool, if configured.
+    /// A no-op when `max_threads` is `None` or the pool was already built.
+    fn apply_thread_count(&self) {
+        if let Some(max_threads) = self.max_threads {
+            let _ = rayon::ThreadPoolBuilder::new()
+                .num_threads(max_threads)
+                .build_global();
+        }
+    }
+}
+
+/// Classify one allocation's size into a human-readable bucket, matching
+/// [`calculate_size_distribution_parallel`]'s categories.
+fn size_bucket(size: usize) -> &'static str {
+    match size {
+        0..=64 => "tiny (0-64B)",
+        65..=256 => "small (65-256B)",
+        257..=1024 => "medium (257B-1KB)",
+        1025..=4096 => "large (1-4KB)",
+        4097..=16384 => "very_large (4-16KB)",
+        16385..=65536 => "huge (16-64KB)",
+        _ => "massive (>64KB)",
+    }
+}
+
+fn merge_counts(
+    mut a: HashMap<String, usize>,
+    b: HashMap<String, usize>,
+) -> HashMap<String, usize> {
+    for (key, count) in b {
+        *a.entry(key).or_insert(0) += count;
+    }
+    a
+}
+
+/// Count allocations per size bucket, optionally in parallel.
+pub fn calculate_size_distribution_parallel(
+    allocations: &[AllocationInfo],
+    config: &ParallelConfig,
+) -> HashMap<String, usize> {
+    config.apply_thread_count();
+
+    if config.use_parallel(allocations.len()) {
+        allocations
+            .par_iter()
+            .fold(HashMap::new, |mut acc, allocation| {
+                *acc.entry(size_bucket(allocation.size).to_string())
+                    .or_insert(0) += 1;
+                acc
+            })
+            .reduce(HashMap::new, merge_counts)
+    } else {
+        allocations
+            .iter()
+            .fold(HashMap::new(), |mut acc, allocation| {
+                *acc.entry(size_bucket(allocation.size).to_string())
+                    .or_insert(0) += 1;
+                acc
+            })
+    }
+}
+
+/// Classify one allocation into a [`SystemLibraryStats`] category, folding
+/// its contribution into `stats`. Mirrors the substring heuristics used
+/// elsewhere in this crate for classifying allocations by type/variable name.
+fn fold_library_allocation(
+    mut stats: SystemLibraryStats,
+    allocation: &AllocationInfo,
+) -> SystemLibraryStats {
+    let type_name = allocation.type_name.as_deref().unwrap_or("");
+    let var_name = allocation.var_name.as_deref().unwrap_or("");
+
+    let usage = if type_name.contains("HashMap")
+        || type_name.contains("BTreeMap")
+        || type_name.contains("HashSet")
+        || type_name.contains("Vec")
+    {
+        Some(&mut stats.std_collections)
+    } else if type_name.contains("tokio")
+        || var_name.contains("async")
+        || var_name.contains("tokio")
+    {
+        Some(&mut stats.async_runtime)
+    } else if var_name.contains("net_") || var_name.contains("tcp_") || var_name.contains("udp_") {
+        Some(&mut stats.network_io)
+    } else if var_name.contains("fs_") || var_name.contains("file_") {
+        Some(&mut stats.file_system)
+    } else if var_name.contains("json_") || var_name.contains("serde") {
+        Some(&mut stats.serialization)
+    } else if var_name.contains("regex") {
+        Some(&mut stats.regex_engine)
+    } else if var_name.contains("crypto") || var_name.contains("hash") {
+        Some(&mut stats.crypto_security)
+    } else {
+        None
+    };
+
+    if let Some(usage) = usage {
+        usage.allocation_count += 1;
+        usage.total_bytes += allocation.size;
+        usage.peak_bytes = usage.peak_bytes.max(allocation.size);
+        if let Some(var_name) = &allocation.var_name {
+            if usage.hotspot_functions.len() < 10 && !usage.hotspot_functions.contains(var_name) {
+                usage.hotspot_functions.push(var_name.clone());
+            }
+        }
+    }
+
+    stats
+}
+
+fn merge_library_usage(
+    mut a: crate::core::types::LibraryUsage,
+    b: crate::core::types::LibraryUsage,
+) -> crate::core::types::LibraryUsage {
+    a.allocation_count += b.allocation_count;
+    a.total_bytes += b.total_bytes;
+    a.peak_bytes = a.peak_bytes.max(b.peak_bytes);
+    for (category, bytes) in b.categories {
+        *a.categories.entry(category).or_insert(0) += bytes;
+    }
+    for hotspot in b.hotspot_functions {
+        if a.hotspot_functions.len() < 10 && !a.hotspot_functions.contains(&hotspot) {
+            a.hotspot_functions.push(hotspot);
+        }
+    }
+    a
+}
+
+fn merge_library_stats(a: SystemLibraryStats, b: SystemLibraryStats) -> SystemLibraryStats {
+    let mut merged = SystemLibraryStats {
+        std_collections: merge_library_usage(a.std_collections, b.std_collections),
+        async_runtime: merge_library_usage(a.async_runtime, b.async_runtime),
+        network_io: merge_library_usage(a.network_io, b.network_io),
+        file_system: merge_library_usage(a.file_system, b.file_system),
+        serialization: merge_library_usage(a.serialization, b.serialization),
+        regex_engine: merge_library_usage(a.regex_engine, b.regex_engine),
+        crypto_security: merge_library_usage(a.crypto_security, b.crypto_security),
+        database: merge_library_usage(a.database, b.database),
+        graphics_ui: merge_library_usage(a.graphics_ui, b.graphics_ui),
+        http_stack: merge_library_usage(a.http_stack, b.http_stack),
+    };
+    finalize_library_averages(&mut merged);
+    merged
+}
+
+fn finalize_library_averages(stats: &mut SystemLibraryStats) {
+    for usage in [
+        &mut stats.std_collections,
+        &mut stats.async_runtime,
+        &mut stats.network_io,
+        &mut stats.file_system,
+        &mut stats.serialization,
+        &mut stats.regex_engine,
+        &mut stats.crypto_security,
+        &mut stats.database,
+        &mut stats.graphics_ui,
+        &mut stats.http_stack,
+    ] {
+        if usage.allocation_count > 0 {
+            usage.average_size = usage.total_bytes as f64 / usage.allocation_count as f64;
+        }
+    }
+}
+
+/// Classify every allocation into a [`SystemLibraryStats`] category,
+/// optionally in parallel.
+pub fn analyze_system_libraries_parallel(
+    allocations: &[AllocationInfo],
+    config: &ParallelConfig,
+) -> SystemLibraryStats {
+    config.apply_thread_count();
+
+    let mut stats = if config.use_parallel(allocations.len()) {
+        allocations
+            .par_iter()
+            .fold(SystemLibraryStats::default, fold_library_allocation)
+            .reduce(SystemLibraryStats::default, merge_library_stats)
+    } else {
+        allocations
+            .iter()
+            .fold(SystemLibraryStats::default(), fold_library_allocation)
+    };
+
+    finalize_library_averages(&mut stats);
+    stats
+}
+
+/// Classify one allocation into a [`ConcurrencyAnalysis`] bucket, folding its
+/// contribution into `analysis`.
+fn fold_concurrency_allocation(
+    mut analysis: ConcurrencyAnalysis,
+    allocation: &AllocationInfo,
+) -> ConcurrencyAnalysis {
+    let type_name = allocation.type_name.as_deref().unwrap_or("");
+    let var_name = allocation.var_name.as_deref().unwrap_or("");
+
+    if type_name.contains("Arc") || var_name.contains("arc_") {
+        analysis.arc_shared += allocation.size;
+        analysis.shared_memory_bytes += allocation.size;
+        analysis.thread_safety_allocations += 1;
+    } else if type_name.contains("Mutex") || var_name.contains("mutex_") {
+        analysis.mutex_protected += allocation.size;
+        analysis.thread_safety_allocations += 1;
+    } else if type_name.contains("Rc") || var_name.contains("rc_") {
+        analysis.rc_shared += allocation.size;
+    } else if var_name.contains("channel_") {
+        analysis.channel_buffers += allocation.size;
+        analysis.thread_safety_allocations += 1;
+    } else if var_name.contains("thread_local") {
+        analysis.thread_local_storage += allocation.size;
+    } else if var_name.contains("atomic_") {
+        analysis.atomic_operations += allocation.size;
+        analysis.thread_safety_allocations += 1;
+    }
+
+    analysis
+}
+
+fn merge_concurrency(a: ConcurrencyAnalysis, b: ConcurrencyAnalysis) -> ConcurrencyAnalysis {
+    ConcurrencyAnalysis {
+        thread_safety_allocations: a.thread_safety_allocations + b.thread_safety_allocations,
+        shared_memory_bytes: a.shared_memory_bytes + b.shared_memory_bytes,
+        mutex_protected: a.mutex_protected + b.mutex_protected,
+        arc_shared: a.arc_shared + b.arc_shared,
+        rc_shared: a.rc_shared + b.rc_shared,
+        channel_buffers: a.channel_buffers + b.channel_buffers,
+        thread_local_storage: a.thread_local_storage + b.thread_local_storage,
+        atomic_operations: a.atomic_operations + b.atomic_operations,
+        lock_contention_risk: String::new(),
+    }
+}
+
+/// Bucket a concurrency analysis's shared-memory footprint into a
+/// human-readable contention risk level.
+fn assess_lock_contention_risk(analysis: &ConcurrencyAnalysis) -> String {
+    if analysis.thread_safety_allocations == 0 {
+        return "low".to_string();
+    }
+
+    let protected = analysis.mutex_protected + analysis.arc_shared;
+    let ratio = protected as f64 / analysis.shared_memory_bytes.max(1) as f64;
+
+    if analysis.thread_safety_allocations > 1000 && ratio > 0.5 {
+        "high".to_string()
+    } else if analysis.thread_safety_allocations > 100 {
+        "medium".to_string()
+    } else {
+        "low".to_string()
+    }
+}
+
+/// Classify every allocation into a [`ConcurrencyAnalysis`], optionally in
+/// parallel.
+pub fn analyze_concurrency_safety_parallel(
+    allocations: &[AllocationInfo],
+    config: &ParallelConfig,
+) -> ConcurrencyAnalysis {
+    config.apply_thread_count();
+
+    let mut analysis = if config.use_parallel(allocations.len()) {
+        allocations
+            .par_iter()
+            .fold(ConcurrencyAnalysis::default, fold_concurrency_allocation)
+            .reduce(ConcurrencyAnalysis::default, merge_concurrency)
+    } else {
+        allocations
+            .iter()
+            .fold(ConcurrencyAnalysis::default(), fold_concurrency_allocation)
+    };
+
+    analysis.lock_contention_risk = assess_lock_contention_risk(&analysis);
+    analysis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alloc(ptr: usize, size: usize, type_name: &str, var_name: &str) -> AllocationInfo {
+        let mut info = AllocationInfo::new(ptr, size);
+        info.type_name = Some(type_name.to_string());
+        info.var_name = Some(var_name.to_string());
+        info
+    }
+
+    fn small_config() -> ParallelConfig {
+        // Force the parallel path even for tiny test inputs.
+        ParallelConfig {
+            parallel_threshold: 0,
+            max_threads: None,
+        }
+    }
+
+    #[test]
+    fn test_size_distribution_matches_serial_and_parallel() {
+        let allocations = vec![
+            alloc(0x1000, 10, "Vec<u8>", "a"),
+            alloc(0x2000, 2000, "String", "b"),
+            alloc(0x3000, 100_000, "Vec<u8>", "c"),
+        ];
+
+        let serial = calculate_size_distribution_parallel(&allocations, &ParallelConfig::default());
+        let parallel = calculate_size_distribution_parallel(&allocations, &small_config());
+        assert_eq!(serial, parallel);
+        assert_eq!(*serial.get("tiny (0-64B)").unwrap(), 1);
+        assert_eq!(*serial.get("large (1-4KB)").unwrap(), 1);
+        assert_eq!(*serial.get("massive (>64KB)").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_system_libraries_parallel_matches_serial() {
+        let allocations = vec![
+            alloc(0x1000, 100, "HashMap<String, i32>", "map"),
+            alloc(0x2000, 200, "HashMap<String, i32>", "map2"),
+            alloc(0x3000, 50, "String", "regex_pattern"),
+        ];
+
+        let serial = analyze_system_libraries_parallel(&allocations, &ParallelConfig::default());
+        let parallel = analyze_system_libraries_parallel(&allocations, &small_config());
+
+        assert_eq!(serial.std_collections.allocation_count, 2);
+        assert_eq!(serial.std_collections.total_bytes, 300);
+        assert_eq!(parallel.std_collections.allocation_count, 2);
+        assert_eq!(parallel.std_collections.total_bytes, 300);
+        assert_eq!(serial.regex_engine.allocation_count, 1);
+        assert_eq!(parallel.regex_engine.allocation_count, 1);
+    }
+
+    #[test]
+    fn test_concurrency_analysis_parallel_matches_serial() {
+        let allocations = vec![
+            alloc(0x1000, 64, "Arc<Mutex<i32>>", "arc_counter"),
+            alloc(0x2000, 128, "Arc<Mutex<i32>>", "arc_state"),
+            alloc(0x3000, 32, "Rc<Node>", "rc_node"),
+        ];
+
+        let serial = analyze_concurrency_safety_parallel(&allocations, &ParallelConfig::default());
+        let parallel = analyze_concurrency_safety_parallel(&allocations, &small_config());
+
+        assert_eq!(
+            serial.thread_safety_allocations,
+            parallel.thread_safety_allocations
+        );
+        assert_eq!(serial.arc_shared, parallel.arc_shared);
+        assert_eq!(serial.arc_shared, 192);
+        assert_eq!(serial.rc_shared, 32);
+        assert_eq!(serial.lock_contention_risk, parallel.lock_contention_risk);
+    }
+
+    #[test]
+    fn test_empty_allocations_produce_default_reports() {
+        let allocations: Vec<AllocationInfo> = vec![];
+        let config = ParallelConfig::default();
+
+        assert!(calculate_size_distribution_parallel(&allocations, &config).is_empty());
+        assert_eq!(
+            analyze_system_libraries_parallel(&allocations, &config)
+                .std_collections
+                .allocation_count,
+            0
+        );
+        assert_eq!(
+            analyze_concurrency_safety_parallel(&allocations, &config).lock_contention_risk,
+            "low"
+        );
+    }
+}
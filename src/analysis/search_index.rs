@@ -0,0 +1,242 @@
+//! In-memory inverted index over a dashboard's `allocation_details`, for
+//! boolean term search without a full client-side scan.
+//!
+//! Built once per export from `allocation_details` and emitted as a
+//! `"search_index"` section mapping each term to the list of allocation ids
+//! (an allocation's position within `allocation_details`) it occurs in. A
+//! `BTreeMap` backs the index instead of a `HashMap` so prefix queries (e.g.
+//! `"buf"` matching the indexed term `"buffer"`) are a cheap ordered range
+//! scan rather than a full-index walk. Type names are split into terms, so
+//! `"Vec"` finds `"Vec<i32>"` through its exact term `"vec"`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A single dashboard allocation as presented in a search result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MatchedAllocation {
+    /// Position of this allocation within `allocation_details`
+    pub id: usize,
+    /// Allocation pointer, formatted as in `allocation_details`
+    pub ptr: String,
+    /// Allocation size in bytes
+    pub size: u64,
+    /// Type name of the allocated value
+    pub type_name: String,
+    /// Inferred variable name of the allocated value
+    pub var_name: String,
+}
+
+/// An inverted index over a dashboard's allocations: each term maps to the
+/// ids of allocations whose type name, variable name, or backtrace location
+/// contains it. Serializes transparently as a `term -> [id, ...]` map.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchIndex(BTreeMap<String, BTreeSet<usize>>);
+
+/// Split `text` on non-alphanumeric characters and lowercase each piece, so
+/// e.g. `"Vec<i32>"` tokenizes to `["vec", "i32"]`.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+impl SearchIndex {
+    fn insert(&mut self, term: &str, id: usize) {
+        self.0.entry(term.to_string()).or_default().insert(id);
+    }
+
+    fn index_allocation(
+        &mut self,
+        id: usize,
+        type_name: &str,
+        var_name: &str,
+        location: Option<&str>,
+    ) {
+        for token in tokenize(type_name) {
+            self.insert(&token, id);
+        }
+        for token in tokenize(var_name) {
+            self.insert(&token, id);
+        }
+        if let Some(location) = location {
+            for token in tokenize(location) {
+                self.insert(&token, id);
+            }
+        }
+    }
+
+    /// Ids whose indexed terms include `term` exactly, or start with `term`
+    /// when no exact term is indexed (prefix matching).
+    fn lookup(&self, term: &str) -> BTreeSet<usize> {
+        let term = term.to_lowercase();
+        if let Some(ids) = self.0.get(&term) {
+            return ids.clone();
+        }
+        self.0
+            .range(term.clone()..)
+            .take_while(|(key, _)| key.starts_with(&term))
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect()
+    }
+}
+
+/// Build a search index over `allocation_details` as produced by
+/// `build_unified_dashboard_structure`. Indexes each entry's `type_name`,
+/// `var_name`, and (when present) its innermost resolved backtrace frame's
+/// `filename`.
+pub fn build_search_index(allocation_details: &[serde_json::Value]) -> SearchIndex {
+    let mut index = SearchIndex::default();
+    for (id, entry) in allocation_details.iter().enumerate() {
+        let type_name = entry
+            .get("type_name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let var_name = entry.get("var_name").and_then(|v| v.as_str()).unwrap_or("");
+        let location = entry
+            .get("backtrace")
+            .and_then(|v| v.as_array())
+            .and_then(|frames| frames.first())
+            .and_then(|frame| frame.get("filename"))
+            .and_then(|v| v.as_str());
+        index.index_allocation(id, type_name, var_name, location);
+    }
+    index
+}
+
+/// Run a simple boolean term query (`"term1 AND term2"`, `"term1 OR
+/// term2"`, or space-separated terms defaulting to AND) against a
+/// previously exported dashboard `serde_json::Value`, using its
+/// `"search_index"` section. Matches are ordered by allocation `size`
+/// descending.
+pub fn query_dashboard(dashboard: &serde_json::Value, query: &str) -> Vec<MatchedAllocation> {
+    let index = match dashboard.get("search_index") {
+        Some(value) => serde_json::from_value::<SearchIndex>(value.clone()).unwrap_or_default(),
+        None => SearchIndex::default(),
+    };
+    let Some(allocation_details) = dashboard
+        .get("allocation_details")
+        .and_then(|v| v.as_array())
+    else {
+        return Vec::new();
+    };
+
+    let is_or = query.to_uppercase().contains(" OR ");
+    let terms: Vec<&str> = query
+        .split_whitespace()
+        .filter(|t| !t.eq_ignore_ascii_case("and") && !t.eq_ignore_ascii_case("or"))
+        .collect();
+
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ids: Option<BTreeSet<usize>> = None;
+    for term in &terms {
+        let matched = index.lookup(term);
+        ids = Some(match ids {
+            None => matched,
+            Some(acc) => {
+                if is_or {
+                    acc.union(&matched).copied().collect()
+                } else {
+                    acc.intersection(&matched).copied().collect()
+                }
+            }
+        });
+    }
+
+    let mut results: Vec<MatchedAllocation> = ids
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|id| {
+            let entry = allocation_details.get(id)?;
+            Some(MatchedAllocation {
+                id,
+                ptr: entry
+                    .get("ptr")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+                size: entry.get("size").and_then(|v| v.as_u64()).unwrap_or(0),
+                type_name: entry
+                    .get("type_name")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                var_name: entry
+                    .get("var_name")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown")
+                    .to_string(),
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.size.cmp(&a.size));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_dashboard() -> serde_json::Value {
+        let allocation_details = vec![
+            serde_json::json!({"ptr": "0x1", "size": 100, "type_name": "Vec<i32>", "var_name": "buffer"}),
+            serde_json::json!({"ptr": "0x2", "size": 300, "type_name": "Vec<u8>", "var_name": "data"}),
+            serde_json::json!({"ptr": "0x3", "size": 50, "type_name": "String", "var_name": "name"}),
+        ];
+        let search_index = build_search_index(&allocation_details);
+        serde_json::json!({
+            "allocation_details": allocation_details,
+            "search_index": search_index,
+        })
+    }
+
+    #[test]
+    fn test_tokenize_splits_on_non_alphanumeric_and_lowercases() {
+        assert_eq!(tokenize("Vec<i32>"), vec!["vec", "i32"]);
+    }
+
+    #[test]
+    fn test_exact_term_query_returns_matching_allocations() {
+        let dashboard = sample_dashboard();
+        let results = query_dashboard(&dashboard, "string");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].var_name, "name");
+    }
+
+    #[test]
+    fn test_prefix_query_matches_generic_type_names() {
+        let dashboard = sample_dashboard();
+        let results = query_dashboard(&dashboard, "vec");
+        assert_eq!(results.len(), 2);
+        // Ordered by size descending
+        assert_eq!(results[0].var_name, "data");
+        assert_eq!(results[1].var_name, "buffer");
+    }
+
+    #[test]
+    fn test_and_query_intersects_terms() {
+        let dashboard = sample_dashboard();
+        let results = query_dashboard(&dashboard, "vec AND buffer");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].var_name, "buffer");
+    }
+
+    #[test]
+    fn test_or_query_unions_terms() {
+        let dashboard = sample_dashboard();
+        let results = query_dashboard(&dashboard, "string OR data");
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_query_with_no_matches_returns_empty() {
+        let dashboard = sample_dashboard();
+        let results = query_dashboard(&dashboard, "nonexistent");
+        assert!(results.is_empty());
+    }
+}
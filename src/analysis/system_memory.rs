@@ -0,0 +1,227 @@
+//! System-memory-aware budget check.
+//!
+//! [`memory_budget`](crate::analysis::memory_budget) caps memory per
+//! caller-configured owner; this module instead caps the whole process
+//! against the machine it's actually running on, using `sysinfo` to read
+//! total physical RAM. By default the ceiling is two thirds of total RAM,
+//! overridable with an absolute byte count or a percentage via
+//! [`MaxMemory`]. [`check_system_memory`] compares `peak_memory`/
+//! `active_memory` against the resolved ceiling and estimates time-to-OOM
+//! from the observed net allocation rate, so
+//! `generate_optimization_recommendations` can warn before a process is
+//! actually killed by the OS rather than only after the fact.
+
+use crate::core::types::MemoryStats;
+
+/// A memory ceiling expressed either as an absolute byte count or as a
+/// percentage of total system RAM.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaxMemory {
+    /// An absolute byte ceiling
+    Bytes(usize),
+    /// A percentage of total system RAM, e.g. `66.0` for two thirds
+    PercentOfTotal(f64),
+}
+
+impl Default for MaxMemory {
+    /// Two thirds of total system RAM -- enough headroom for most workloads
+    /// while still catching a process before it starts swapping or gets
+    /// OOM-killed.
+    fn default() -> Self {
+        MaxMemory::PercentOfTotal(200.0 / 3.0)
+    }
+}
+
+impl MaxMemory {
+    /// Parse a percentage (`"66%"`) or a human-readable byte size (`"4GiB"`,
+    /// see [`crate::export::benchmark::parse_byte_size`]) into a [`MaxMemory`].
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        if let Some(pct) = spec.strip_suffix('%') {
+            return pct
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .map(MaxMemory::PercentOfTotal);
+        }
+        crate::export::benchmark::parse_byte_size(spec).map(MaxMemory::Bytes)
+    }
+
+    /// Resolve this ceiling to an absolute byte count given the machine's
+    /// total physical RAM.
+    pub fn resolve(&self, total_system_memory: usize) -> usize {
+        match self {
+            MaxMemory::Bytes(bytes) => *bytes,
+            MaxMemory::PercentOfTotal(pct) => {
+                ((total_system_memory as f64) * (pct / 100.0)) as usize
+            }
+        }
+    }
+}
+
+/// Total physical RAM on this machine, in bytes. Returns `0` ("unknown")
+/// when the `system-metrics` feature is disabled -- callers should treat a
+/// `0` total as "skip the budget check" rather than "no RAM available".
+pub fn total_system_memory() -> usize {
+    #[cfg(feature = "system-metrics")]
+    {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        system.total_memory() as usize
+    }
+    #[cfg(not(feature = "system-metrics"))]
+    {
+        0
+    }
+}
+
+/// Outcome of checking current memory usage against a [`MaxMemory`] ceiling.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SystemMemoryCheck {
+    /// Total physical RAM on this machine, in bytes (`0` if unknown)
+    pub total_system_memory: usize,
+    /// The ceiling, resolved to an absolute byte count
+    pub ceiling_bytes: usize,
+    /// `stats.peak_memory` as a fraction of `ceiling_bytes`
+    pub peak_fraction_of_ceiling: f64,
+    /// True once `peak_fraction_of_ceiling` reaches 80% but hasn't gone over
+    pub approaching_limit: bool,
+    /// True once active memory has exceeded the ceiling
+    pub over_limit: bool,
+    /// Estimated seconds until active memory would reach the ceiling, given
+    /// the net allocation rate observed so far. `None` when memory isn't
+    /// growing, the ceiling is unknown, or there's nothing to extrapolate
+    /// from.
+    pub estimated_seconds_to_oom: Option<f64>,
+}
+
+/// Check `stats`' peak/active memory against `ceiling` (resolved against
+/// this machine's total RAM), estimating time-to-OOM from `bytes_per_sec`,
+/// the net allocation rate (bytes allocated minus deallocated, per second of
+/// observed runtime).
+pub fn check_system_memory(
+    stats: &MemoryStats,
+    ceiling: MaxMemory,
+    bytes_per_sec: f64,
+) -> SystemMemoryCheck {
+    let total_system_memory = total_system_memory();
+    let ceiling_bytes = ceiling.resolve(total_system_memory);
+
+    if ceiling_bytes == 0 {
+        // A percentage ceiling with no known total RAM (e.g. the
+        // `system-metrics` feature is disabled) resolves to nothing
+        // meaningful -- report "unknown" rather than falsely flagging every
+        // allocation as over an unresolved zero-byte ceiling.
+        return SystemMemoryCheck {
+            total_system_memory,
+            ceiling_bytes: 0,
+            peak_fraction_of_ceiling: 0.0,
+            approaching_limit: false,
+            over_limit: false,
+            estimated_seconds_to_oom: None,
+        };
+    }
+
+    let peak_fraction_of_ceiling = stats.peak_memory as f64 / ceiling_bytes as f64;
+    let over_limit = stats.active_memory > ceiling_bytes;
+    let approaching_limit = !over_limit && peak_fraction_of_ceiling >= 0.8;
+
+    let estimated_seconds_to_oom = if bytes_per_sec > 0.0 && stats.active_memory < ceiling_bytes {
+        Some((ceiling_bytes - stats.active_memory) as f64 / bytes_per_sec)
+    } else {
+        None
+    };
+
+    SystemMemoryCheck {
+        total_system_memory,
+        ceiling_bytes,
+        peak_fraction_of_ceiling,
+        approaching_limit,
+        over_limit,
+        estimated_seconds_to_oom,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_with(peak_memory: usize, active_memory: usize) -> MemoryStats {
+        let mut stats = MemoryStats::new();
+        stats.peak_memory = peak_memory;
+        stats.active_memory = active_memory;
+        stats
+    }
+
+    #[test]
+    fn test_max_memory_parse_percentage() {
+        assert_eq!(
+            MaxMemory::parse("66%"),
+            Some(MaxMemory::PercentOfTotal(66.0))
+        );
+    }
+
+    #[test]
+    fn test_max_memory_parse_byte_size() {
+        assert_eq!(
+            MaxMemory::parse("4GiB"),
+            Some(MaxMemory::Bytes(4 * 1024 * 1024 * 1024))
+        );
+    }
+
+    #[test]
+    fn test_max_memory_resolve_bytes_ignores_total() {
+        let max = MaxMemory::Bytes(1000);
+        assert_eq!(max.resolve(999_999_999), 1000);
+    }
+
+    #[test]
+    fn test_max_memory_resolve_percent_of_total() {
+        let max = MaxMemory::PercentOfTotal(50.0);
+        assert_eq!(max.resolve(1000), 500);
+    }
+
+    #[test]
+    fn test_check_system_memory_under_ceiling_is_not_over_or_approaching() {
+        let stats = stats_with(100, 100);
+        let check = check_system_memory(&stats, MaxMemory::Bytes(10_000), 0.0);
+        assert!(!check.over_limit);
+        assert!(!check.approaching_limit);
+    }
+
+    #[test]
+    fn test_check_system_memory_flags_approaching_limit() {
+        let stats = stats_with(850, 850);
+        let check = check_system_memory(&stats, MaxMemory::Bytes(1000), 0.0);
+        assert!(check.approaching_limit);
+        assert!(!check.over_limit);
+    }
+
+    #[test]
+    fn test_check_system_memory_flags_over_limit() {
+        let stats = stats_with(1200, 1200);
+        let check = check_system_memory(&stats, MaxMemory::Bytes(1000), 0.0);
+        assert!(check.over_limit);
+    }
+
+    #[test]
+    fn test_check_system_memory_estimates_time_to_oom() {
+        // An absolute byte ceiling doesn't depend on total system RAM being
+        // known, so this estimate is deterministic even when the
+        // `system-metrics` feature is disabled.
+        let stats = stats_with(500, 500);
+        let check = check_system_memory(&stats, MaxMemory::Bytes(1000), 100.0);
+        assert_eq!(check.estimated_seconds_to_oom, Some(5.0));
+    }
+
+    #[test]
+    fn test_check_system_memory_unresolvable_percent_ceiling_reports_unknown() {
+        // With no way to resolve a percentage ceiling (no known total RAM),
+        // the check must not fabricate a zero-byte ceiling that every
+        // allocation would trivially exceed.
+        let stats = stats_with(500, 500);
+        let check = check_system_memory(&stats, MaxMemory::PercentOfTotal(0.0), 0.0);
+        assert!(!check.over_limit);
+        assert!(!check.approaching_limit);
+    }
+}
@@ -0,0 +1,182 @@
+//! Type-name interning to cut the profiler's own memory footprint.
+//!
+//! Millions of allocations across a handful of distinct types otherwise mean
+//! millions of duplicate copies of the same long generic type string. A
+//! [`TypeNameInterner`] hands out cheap, copyable [`TypeNameHandle`]s backed
+//! by a dedup table, so repeated type names cost one table lookup instead of
+//! a fresh heap allocation, and later aggregation by type (see
+//! [`count_allocations_by_type`]) becomes an integer group-by over handles
+//! rather than a string group-by.
+//!
+//! This is deliberately additive: [`crate::core::types::AllocationInfo`] and
+//! [`crate::core::types::MemoryStats`] keep storing `type_name` as `String`
+//! so none of their many existing call sites need to change. Callers that
+//! want the reduced footprint intern on their own terms via
+//! [`TypeNameInterner::intern`] or [`intern_allocation_type_names`].
+
+use std::collections::HashMap;
+
+/// Inline capacity for [`TypeNameInterner`]'s dedup table entries, chosen so
+/// that common short type names (`"i32"`, `"String"`, `"Vec<u8>"`) never
+/// need a heap allocation; longer generic names fall back to a boxed `str`.
+const INLINE_CAPACITY: usize = 23;
+
+/// A small-string-optimized storage for one interned type name: inline for
+/// names up to [`INLINE_CAPACITY`] bytes, heap-boxed otherwise.
+#[derive(Debug, Clone)]
+enum SmallTypeName {
+    Inline { buf: [u8; INLINE_CAPACITY], len: u8 },
+    Heap(Box<str>),
+}
+
+impl SmallTypeName {
+    fn new(name: &str) -> Self {
+        if name.len() <= INLINE_CAPACITY {
+            let mut buf = [0u8; INLINE_CAPACITY];
+            buf[..name.len()].copy_from_slice(name.as_bytes());
+            SmallTypeName::Inline {
+                buf,
+                len: name.len() as u8,
+            }
+        } else {
+            SmallTypeName::Heap(name.into())
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            SmallTypeName::Inline { buf, len } => {
+                std::str::from_utf8(&buf[..*len as usize]).unwrap_or("")
+            }
+            SmallTypeName::Heap(name) => name,
+        }
+    }
+}
+
+/// A cheap, `Copy`able handle to an interned type name. Resolve back to a
+/// `&str` via [`TypeNameInterner::resolve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TypeNameHandle(u32);
+
+/// Dedup table mapping type-name strings to [`TypeNameHandle`]s.
+#[derive(Debug, Default)]
+pub struct TypeNameInterner {
+    table: Vec<SmallTypeName>,
+    lookup: HashMap<String, TypeNameHandle>,
+}
+
+impl TypeNameInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intern `name`, returning its existing handle if already interned or
+    /// allocating a new table slot otherwise.
+    pub fn intern(&mut self, name: &str) -> TypeNameHandle {
+        if let Some(&handle) = self.lookup.get(name) {
+            return handle;
+        }
+        let handle = TypeNameHandle(self.table.len() as u32);
+        self.table.push(SmallTypeName::new(name));
+        self.lookup.insert(name.to_string(), handle);
+        handle
+    }
+
+    /// Resolve a handle back to its type-name string, for reporting and JSON
+    /// export. Returns `"unknown"` for a handle whose index is past this
+    /// interner's table; a handle from another interner whose index is in
+    /// range resolves to whichever name this interner holds at that index.
+    pub fn resolve(&self, handle: TypeNameHandle) -> &str {
+        self.table
+            .get(handle.0 as usize)
+            .map(SmallTypeName::as_str)
+            .unwrap_or("unknown")
+    }
+
+    /// Number of distinct type names interned so far.
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+}
+
+/// Intern every allocation's `type_name` (defaulting to `"unknown"` when
+/// absent, matching the rest of the export pipeline's convention), returning
+/// one handle per allocation in the same order.
+pub fn intern_allocation_type_names(
+    interner: &mut TypeNameInterner,
+    allocations: &[crate::core::types::AllocationInfo],
+) -> Vec<TypeNameHandle> {
+    allocations
+        .iter()
+        .map(|alloc| interner.intern(alloc.type_name.as_deref().unwrap_or("unknown")))
+        .collect()
+}
+
+/// Group-by-type allocation counts as an integer group-by over handles,
+/// rather than a string group-by over `type_name`.
+pub fn count_allocations_by_type(
+    handles: &[TypeNameHandle],
+) -> std::collections::BTreeMap<TypeNameHandle, usize> {
+    let mut counts = std::collections::BTreeMap::new();
+    for &handle in handles {
+        *counts.entry(handle).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interning_same_name_twice_returns_same_handle() {
+        let mut interner = TypeNameInterner::new();
+        let a = interner.intern("Vec<i32>");
+        let b = interner.intern("Vec<i32>");
+        assert_eq!(a, b);
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn test_interning_distinct_names_returns_distinct_handles() {
+        let mut interner = TypeNameInterner::new();
+        let a = interner.intern("String");
+        let b = interner.intern("i32");
+        assert_ne!(a, b);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_resolve_round_trips_short_and_long_names() {
+        let mut interner = TypeNameInterner::new();
+        let short = interner.intern("u8");
+        let long =
+            interner.intern("std::collections::HashMap<String, Vec<SomeVeryLongGenericType>>");
+        assert_eq!(interner.resolve(short), "u8");
+        assert_eq!(
+            interner.resolve(long),
+            "std::collections::HashMap<String, Vec<SomeVeryLongGenericType>>"
+        );
+    }
+
+    #[test]
+    fn test_resolve_unknown_handle_returns_unknown() {
+        let interner = TypeNameInterner::new();
+        assert_eq!(interner.resolve(TypeNameHandle(42)), "unknown");
+    }
+
+    #[test]
+    fn test_count_allocations_by_type_groups_by_handle() {
+        let mut interner = TypeNameInterner::new();
+        let vec_handle = interner.intern("Vec<u8>");
+        let string_handle = interner.intern("String");
+        let handles = vec![vec_handle, string_handle, vec_handle];
+        let counts = count_allocations_by_type(&handles);
+        assert_eq!(counts.get(&vec_handle), Some(&2));
+        assert_eq!(counts.get(&string_handle), Some(&1));
+    }
+}
@@ -0,0 +1,336 @@
+//! Event-time windowed batching for timestamped, possibly out-of-order events
+//!
+//! Unlike [`crate::core::threshold_batch_processor::ThresholdBatchProcessor`], which
+//! groups items purely by arrival order, this module groups items by the timestamp
+//! they themselves carry. This produces deterministic, timestamp-correct batches when
+//! replaying or re-buffering allocation traces where arrival order does not match
+//! event order.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Implemented by items that carry their own event timestamp, as opposed to
+/// being batched purely by arrival order.
+pub trait Batchable {
+    fn event_time(&self) -> Instant;
+}
+
+/// Configuration for event-time windowing.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowConfig {
+    /// Width of each window.
+    pub window: Duration,
+    /// Grace period after a window's nominal end during which late-but-not-too-late
+    /// events are still accepted into it, and after which the window is emitted.
+    pub delivery_jitter: Duration,
+    /// How far into the future an event's timestamp may be before it is discarded
+    /// as too-futuristic.
+    pub leap_limit: Duration,
+}
+
+impl WindowConfig {
+    pub fn new(window: Duration, delivery_jitter: Duration, leap_limit: Duration) -> Self {
+        Self {
+            window,
+            delivery_jitter,
+            leap_limit,
+        }
+    }
+}
+
+struct Window<T> {
+    start: Instant,
+    end: Instant,
+    items: Vec<T>,
+}
+
+/// Statistics for an [`EventTimeBatcher`].
+#[derive(Debug, Clone, Default)]
+pub struct EventTimeBatcherStats {
+    pub windows_emitted: u64,
+    pub late_discarded: u64,
+    pub early_discarded: u64,
+}
+
+/// Batches `Batchable` items into fixed-width windows keyed by event time,
+/// emitting each window to the processor closure once it closes.
+pub struct EventTimeBatcher<T> {
+    config: WindowConfig,
+    processor: Box<dyn Fn(&[T]) + Send + Sync>,
+    windows: Mutex<VecDeque<Window<T>>>,
+
+    windows_emitted: AtomicU64,
+    late_discarded: AtomicU64,
+    early_discarded: AtomicU64,
+}
+
+impl<T> EventTimeBatcher<T> {
+    /// Create a new event-time batcher.
+    pub fn new<F>(config: WindowConfig, processor: F) -> Self
+    where
+        F: Fn(&[T]) + Send + Sync + 'static,
+    {
+        Self {
+            config,
+            processor: Box::new(processor),
+            windows: Mutex::new(VecDeque::new()),
+            windows_emitted: AtomicU64::new(0),
+            late_discarded: AtomicU64::new(0),
+            early_discarded: AtomicU64::new(0),
+        }
+    }
+
+    /// Route an event into its window by event time, discarding it if it is
+    /// too late or too far in the future, and emit any window whose grace
+    /// period has elapsed.
+    pub fn process(&self, item: T)
+    where
+        T: Batchable,
+    {
+        let now = Instant::now();
+        let event_time = item.event_time();
+
+        if now.saturating_duration_since(event_time) > self.config.delivery_jitter {
+            self.late_discarded.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        if event_time.saturating_duration_since(now) > self.config.leap_limit {
+            self.early_discarded.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        let ready = {
+            let mut windows = match self.windows.lock() {
+                Ok(windows) => windows,
+                Err(_) => return,
+            };
+
+            // An event past an open window's end closes that window (split)
+            // rather than letting it accumulate indefinitely.
+            let mut closed = VecDeque::new();
+            let mut i = 0;
+            while i < windows.len() {
+                if event_time >= windows[i].end {
+                    closed.push_back(windows.remove(i).expect("index in bounds"));
+                } else {
+                    i += 1;
+                }
+            }
+
+            match windows
+                .iter_mut()
+                .find(|w| event_time >= w.start && event_time < w.end)
+            {
+                Some(w) => w.items.push(item),
+                None => windows.push_back(Window {
+                    start: event_time,
+                    end: event_time + self.config.window,
+                    items: vec![item],
+                }),
+            }
+
+            closed.extend(self.drain_ready_windows(&mut windows, now));
+            closed
+        };
+
+        self.emit(ready);
+    }
+
+    /// Emit any window whose grace period (`window_end + delivery_jitter`)
+    /// has elapsed, without requiring a new event to trigger the check.
+    pub fn flush_ready_windows(&self) {
+        let ready = match self.windows.lock() {
+            Ok(mut windows) => self.drain_ready_windows(&mut windows, Instant::now()),
+            Err(_) => return,
+        };
+        self.emit(ready);
+    }
+
+    /// Force-emit every open window regardless of how much of its grace
+    /// period remains. Intended for shutdown paths.
+    pub fn flush_all(&self) {
+        let ready = match self.windows.lock() {
+            Ok(mut windows) => std::mem::take(&mut *windows),
+            Err(_) => return,
+        };
+        self.emit(ready);
+    }
+
+    fn drain_ready_windows(
+        &self,
+        windows: &mut VecDeque<Window<T>>,
+        now: Instant,
+    ) -> VecDeque<Window<T>> {
+        let mut ready = VecDeque::new();
+        let mut i = 0;
+        while i < windows.len() {
+            if now >= windows[i].end + self.config.delivery_jitter {
+                ready.push_back(windows.remove(i).expect("index in bounds"));
+            } else {
+                i += 1;
+            }
+        }
+        ready
+    }
+
+    fn emit(&self, ready: VecDeque<Window<T>>) {
+        for window in ready {
+            if !window.items.is_empty() {
+                (self.processor)(&window.items);
+                self.windows_emitted.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Get current statistics.
+    pub fn stats(&self) -> EventTimeBatcherStats {
+        EventTimeBatcherStats {
+            windows_emitted: self.windows_emitted.load(Ordering::Relaxed),
+            late_discarded: self.late_discarded.load(Ordering::Relaxed),
+            early_discarded: self.early_discarded.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// Safety: EventTimeBatcher is Send if T is Send
+unsafe impl<T: Send> Send for EventTimeBatcher<T> {}
+
+// Safety: EventTimeBatcher is Sync if T is Send
+unsafe impl<T: Send> Sync for EventTimeBatcher<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::safe_operations::SafeLock;
+    use std::sync::Arc;
+
+    struct TimedEvent {
+        id: u32,
+        at: Instant,
+    }
+
+    impl Batchable for TimedEvent {
+        fn event_time(&self) -> Instant {
+            self.at
+        }
+    }
+
+    #[test]
+    fn test_events_in_same_window_batch_together() {
+        let processed = Arc::new(Mutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = WindowConfig::new(
+            Duration::from_millis(50),
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        );
+        let batcher = EventTimeBatcher::new(config, move |items: &[TimedEvent]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.push(items.iter().map(|e| e.id).collect::<Vec<u32>>());
+        });
+
+        let base = Instant::now();
+        batcher.process(TimedEvent { id: 1, at: base });
+        batcher.process(TimedEvent {
+            id: 2,
+            at: base + Duration::from_millis(5),
+        });
+
+        assert!(processed.safe_lock().unwrap().is_empty());
+
+        std::thread::sleep(Duration::from_millis(70));
+        batcher.flush_ready_windows();
+
+        let batches = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0], vec![1, 2]);
+    }
+
+    #[test]
+    fn test_too_late_event_is_discarded() {
+        let processed: Arc<Mutex<Vec<Vec<u32>>>> = Arc::new(Mutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = WindowConfig::new(
+            Duration::from_millis(50),
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        );
+        let batcher = EventTimeBatcher::new(config, move |items: &[TimedEvent]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.push(items.iter().map(|e| e.id).collect::<Vec<u32>>());
+        });
+
+        let ancient = Instant::now() - Duration::from_secs(1);
+        batcher.process(TimedEvent { id: 1, at: ancient });
+
+        assert_eq!(batcher.stats().late_discarded, 1);
+    }
+
+    #[test]
+    fn test_too_futuristic_event_is_discarded() {
+        let processed: Arc<Mutex<Vec<Vec<u32>>>> = Arc::new(Mutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = WindowConfig::new(
+            Duration::from_millis(50),
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        );
+        let batcher = EventTimeBatcher::new(config, move |items: &[TimedEvent]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.push(items.iter().map(|e| e.id).collect::<Vec<u32>>());
+        });
+
+        let far_future = Instant::now() + Duration::from_secs(1);
+        batcher.process(TimedEvent {
+            id: 1,
+            at: far_future,
+        });
+
+        assert_eq!(batcher.stats().early_discarded, 1);
+    }
+
+    #[test]
+    fn test_event_past_window_end_splits_and_closes_prior_window() {
+        let processed = Arc::new(Mutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = WindowConfig::new(
+            Duration::from_millis(20),
+            Duration::from_millis(10),
+            Duration::from_millis(500),
+        );
+        let batcher = EventTimeBatcher::new(config, move |items: &[TimedEvent]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.push(items.iter().map(|e| e.id).collect::<Vec<u32>>());
+        });
+
+        let base = Instant::now();
+        batcher.process(TimedEvent { id: 1, at: base });
+        // Falls past the first window's end (base + 20ms): closes it.
+        batcher.process(TimedEvent {
+            id: 2,
+            at: base + Duration::from_millis(25),
+        });
+
+        let batches = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0], vec![1]);
+    }
+}
@@ -0,0 +1,228 @@
+//! Lock-free bounded staging buffer for high-frequency batch producers
+//!
+//! Producers claim a slot with an atomic `fetch_add` instead of contending on
+//! a `Mutex`, so a `push` never blocks and never needs a "process directly to
+//! avoid blocking" fallback. Two physical slot arrays are ping-ponged by
+//! generation: a flusher takes ownership of the currently-active generation's
+//! slots while producers keep filling the other one, without a global lock.
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Bit offset separating the generation (high bits) from the claimed-slot
+/// count within that generation (low bits) in the packed state word.
+const INDEX_SHIFT: u32 = 32;
+const INDEX_MASK: usize = (1usize << INDEX_SHIFT) - 1;
+
+struct Generation<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    /// `ready[i]` is set once `slots[i]` has been written, so a flusher can
+    /// tell a claimed-but-still-in-flight slot apart from a written one.
+    ready: Box<[AtomicBool]>,
+}
+
+// Safety: access to `slots`/`ready` is coordinated entirely through the
+// atomic claim counter and ready flags in `LockFreeBatchBuffer`; see `push`
+// and `take_ready`.
+unsafe impl<T: Send> Send for Generation<T> {}
+unsafe impl<T: Send> Sync for Generation<T> {}
+
+impl<T> Generation<T> {
+    fn new(capacity: usize) -> Self {
+        let mut slots = Vec::with_capacity(capacity);
+        let mut ready = Vec::with_capacity(capacity);
+        for _ in 0..capacity {
+            slots.push(UnsafeCell::new(MaybeUninit::uninit()));
+            ready.push(AtomicBool::new(false));
+        }
+        Self {
+            slots: slots.into_boxed_slice(),
+            ready: ready.into_boxed_slice(),
+        }
+    }
+}
+
+/// A bounded, lock-free staging buffer for many concurrent producers and a
+/// single flusher.
+pub struct LockFreeBatchBuffer<T> {
+    capacity: usize,
+    generations: [Generation<T>; 2],
+    /// Packed `(generation << INDEX_SHIFT) | claimed_count` for the
+    /// currently active generation. `generation & 1` selects which of
+    /// `generations` producers are currently claiming slots in.
+    state: AtomicUsize,
+    /// Guards `take_ready` so only one flusher drains at a time.
+    draining: AtomicBool,
+}
+
+// Safety: same reasoning as `Generation`; `state` and `draining` are the
+// single source of truth for which thread may touch which slot.
+unsafe impl<T: Send> Send for LockFreeBatchBuffer<T> {}
+unsafe impl<T: Send> Sync for LockFreeBatchBuffer<T> {}
+
+impl<T> LockFreeBatchBuffer<T> {
+    /// Create a buffer that can hold up to `capacity` claimed-but-undrained
+    /// items at once.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be non-zero");
+        Self {
+            capacity,
+            generations: [Generation::new(capacity), Generation::new(capacity)],
+            state: AtomicUsize::new(0),
+            draining: AtomicBool::new(false),
+        }
+    }
+
+    /// Claim a slot in the active generation and write `item` into it.
+    /// Returns the item back via `Err` if the active generation is already
+    /// full, rather than blocking.
+    pub fn push(&self, item: T) -> Result<(), T> {
+        let old_state = self.state.fetch_add(1, Ordering::AcqRel);
+        let generation = old_state >> INDEX_SHIFT;
+        let index = old_state & INDEX_MASK;
+
+        if index >= self.capacity {
+            return Err(item);
+        }
+
+        let generation = &self.generations[generation & 1];
+        // Safety: `index` was exclusively claimed by this call via the
+        // fetch_add above, so no other producer writes this slot, and a
+        // flusher only reads it after observing `ready[index]` set below.
+        unsafe {
+            (*generation.slots[index].get()).write(item);
+        }
+        generation.ready[index].store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Number of items claimed (written or still in-flight) in the active
+    /// generation.
+    pub fn len(&self) -> usize {
+        (self.state.load(Ordering::Acquire) & INDEX_MASK).min(self.capacity)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Take ownership of every item fully written into the active
+    /// generation, advancing to the other generation so producers calling
+    /// `push` afterward land in a fresh buffer. Returns `None` if another
+    /// flush is already in progress.
+    pub fn take_ready(&self) -> Option<Vec<T>> {
+        if self
+            .draining
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return None;
+        }
+
+        let old_generation = self.state.load(Ordering::Acquire) >> INDEX_SHIFT;
+        let next_generation = old_generation + 1;
+        let old_state = self
+            .state
+            .swap(next_generation << INDEX_SHIFT, Ordering::AcqRel);
+        let claimed = (old_state & INDEX_MASK).min(self.capacity);
+
+        let items = if claimed == 0 {
+            Vec::new()
+        } else {
+            let generation = &self.generations[old_generation & 1];
+            let mut items = Vec::with_capacity(claimed);
+            for slot in generation
+                .ready
+                .iter()
+                .zip(generation.slots.iter())
+                .take(claimed)
+            {
+                let (ready, cell) = slot;
+                // Every one of the first `claimed` producers already won its
+                // fetch_add before the swap above, so it is guaranteed to
+                // write and flip `ready` shortly; spin rather than block.
+                while !ready.swap(false, Ordering::Acquire) {
+                    std::hint::spin_loop();
+                }
+                // Safety: `ready` just confirmed this slot was written, and
+                // flipping it back to `false` hands exclusive ownership of
+                // the slot's contents to this read.
+                let value = unsafe { (*cell.get()).assume_init_read() };
+                items.push(value);
+            }
+            items
+        };
+
+        self.draining.store(false, Ordering::Release);
+        Some(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_push_then_take_ready_returns_items_in_claim_order() {
+        let buffer = LockFreeBatchBuffer::new(4);
+        buffer.push(1).unwrap();
+        buffer.push(2).unwrap();
+        buffer.push(3).unwrap();
+
+        let items = buffer.take_ready().expect("no concurrent drain");
+        assert_eq!(items, vec![1, 2, 3]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_push_past_capacity_returns_item_back() {
+        let buffer = LockFreeBatchBuffer::new(2);
+        buffer.push(1).unwrap();
+        buffer.push(2).unwrap();
+
+        assert_eq!(buffer.push(3), Err(3));
+    }
+
+    #[test]
+    fn test_generations_ping_pong_across_drains() {
+        let buffer = LockFreeBatchBuffer::new(2);
+        buffer.push(1).unwrap();
+        buffer.push(2).unwrap();
+        assert_eq!(buffer.take_ready().unwrap(), vec![1, 2]);
+
+        buffer.push(3).unwrap();
+        buffer.push(4).unwrap();
+        assert_eq!(buffer.take_ready().unwrap(), vec![3, 4]);
+    }
+
+    #[test]
+    fn test_concurrent_producers_all_land_exactly_once() {
+        let buffer = Arc::new(LockFreeBatchBuffer::new(64));
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let buffer = buffer.clone();
+                std::thread::spawn(move || {
+                    for i in 0..8 {
+                        while buffer.push(t * 8 + i).is_err() {
+                            std::thread::yield_now();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("producer thread panicked");
+        }
+
+        let mut items = buffer.take_ready().expect("no concurrent drain");
+        items.sort_unstable();
+        assert_eq!(items, (0..64).collect::<Vec<_>>());
+    }
+}
@@ -19,7 +19,9 @@ pub mod enhanced_pointer_extractor;
 pub mod enhanced_type_inference;
 pub mod error;
 pub mod error_adapter;
+pub mod event_time_batcher;
 pub mod lifecycle_summary;
+pub mod lock_free_batch_buffer;
 pub mod optimized_locks;
 pub mod optimized_tracker;
 pub mod optimized_types;
@@ -98,6 +100,12 @@ pub use targeted_optimizations::{efficient_string_concat, BatchProcessor, FastSt
 // Re-export threshold batch processor
 pub use threshold_batch_processor::{BatchConfig, ProcessingStats, ThresholdBatchProcessor};
 
+// Re-export event-time windowed batcher
+pub use event_time_batcher::{Batchable, EventTimeBatcher, EventTimeBatcherStats, WindowConfig};
+
+// Re-export lock-free batch staging buffer
+pub use lock_free_batch_buffer::LockFreeBatchBuffer;
+
 // Re-export allocation adapter for compatibility
 pub use allocation_adapter::{AllocationCollection, AllocationInfoAdapter, CollectionMemoryStats};
 
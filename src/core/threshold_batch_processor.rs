@@ -3,10 +3,13 @@
 //! This module provides a batch processor that automatically switches between
 //! direct processing and batching based on operation frequency.
 
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Mutex;
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
+use crate::core::lock_free_batch_buffer::LockFreeBatchBuffer;
 
 /// Configuration for batch processing behavior
 #[derive(Debug, Clone)]
@@ -14,37 +17,57 @@ pub struct BatchConfig {
     pub batch_size: usize,
     pub frequency_threshold: u64, // operations per second
     pub measurement_window: Duration,
+    /// Upper bound on how long an item may sit in the buffer before it is
+    /// flushed, even if `batch_size` hasn't been reached. `None` means
+    /// batches only ever flush by size, matching the historical behavior.
+    pub max_batch_latency: Option<Duration>,
+    /// Upper bound on how many items may sit in the buffer at once.
+    /// `usize::MAX` (the default) sizes the buffer at eight times the
+    /// largest configured batch size, and `overflow_policy` applies once
+    /// that fills.
+    pub max_buffer_capacity: usize,
+    /// What to do when `process_batched` would push the buffer past
+    /// `max_buffer_capacity`.
+    pub overflow_policy: OverflowPolicy,
+    /// Smoothing factor for the frequency EWMA: `ewma = alpha * sample +
+    /// (1 - alpha) * ewma`. Higher values track the instantaneous rate more
+    /// closely; lower values smooth out bursts.
+    pub ewma_alpha: f64,
+    /// Smoothed frequency must rise above this to enable batching.
+    pub enable_threshold: u64,
+    /// Smoothed frequency must fall below this to disable batching. Kept
+    /// below `enable_threshold` to give the switch hysteresis, so a
+    /// workload hovering near the threshold doesn't flap every window.
+    pub disable_threshold: u64,
+    /// Effective batch size used once the smoothed frequency is at or below
+    /// `enable_threshold`.
+    pub min_batch_size: usize,
+    /// Effective batch size used once the smoothed frequency has saturated
+    /// at high sustained load.
+    pub max_batch_size: usize,
 }
 
 impl BatchConfig {
     /// Low frequency configuration (100 ops/sec threshold)
     pub fn low_frequency() -> Self {
-        Self {
-            batch_size: 10,
-            frequency_threshold: 100,
-            measurement_window: Duration::from_secs(1),
-        }
+        Self::custom(10, 100, Duration::from_secs(1))
     }
 
     /// Medium frequency configuration (500 ops/sec threshold)
     pub fn medium_frequency() -> Self {
-        Self {
-            batch_size: 25,
-            frequency_threshold: 500,
-            measurement_window: Duration::from_secs(1),
-        }
+        Self::custom(25, 500, Duration::from_secs(1))
     }
 
     /// High frequency configuration (1000 ops/sec threshold)
     pub fn high_frequency() -> Self {
-        Self {
-            batch_size: 50,
-            frequency_threshold: 1000,
-            measurement_window: Duration::from_secs(1),
-        }
+        Self::custom(50, 1000, Duration::from_secs(1))
     }
 
-    /// Create custom configuration
+    /// Create custom configuration. `frequency_threshold` seeds both
+    /// `enable_threshold` and a `disable_threshold` 20% below it, and
+    /// `batch_size` seeds both `min_batch_size` and `max_batch_size`
+    /// (i.e. the effective batch size stays fixed at `batch_size` unless
+    /// `with_batch_size_range` is used to let it scale with load).
     pub fn custom(
         batch_size: usize,
         frequency_threshold: u64,
@@ -54,8 +77,77 @@ impl BatchConfig {
             batch_size,
             frequency_threshold,
             measurement_window,
+            max_batch_latency: None,
+            max_buffer_capacity: usize::MAX,
+            overflow_policy: OverflowPolicy::default(),
+            ewma_alpha: 0.3,
+            enable_threshold: frequency_threshold,
+            disable_threshold: frequency_threshold - frequency_threshold / 5,
+            min_batch_size: batch_size,
+            max_batch_size: batch_size,
         }
     }
+
+    /// Set the enable/disable frequency thresholds directly, overriding the
+    /// defaults derived from `frequency_threshold`. `disable_threshold`
+    /// should stay below `enable_threshold` to provide hysteresis.
+    pub fn with_hysteresis(mut self, enable_threshold: u64, disable_threshold: u64) -> Self {
+        self.enable_threshold = enable_threshold;
+        self.disable_threshold = disable_threshold;
+        self
+    }
+
+    /// Let the effective batch size scale continuously between
+    /// `min_batch_size` and `max_batch_size` with the smoothed frequency,
+    /// instead of staying fixed at `batch_size`.
+    pub fn with_batch_size_range(mut self, min_batch_size: usize, max_batch_size: usize) -> Self {
+        self.min_batch_size = min_batch_size;
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// Override the EWMA smoothing factor (default 0.3).
+    pub fn with_ewma_alpha(mut self, ewma_alpha: f64) -> Self {
+        self.ewma_alpha = ewma_alpha;
+        self
+    }
+
+    /// Set a maximum time a buffered batch may sit before being flushed,
+    /// regardless of whether `batch_size` has been reached. Guarantees a
+    /// bounded delivery delay for low-but-nonzero traffic.
+    pub fn with_max_batch_latency(mut self, max_batch_latency: Duration) -> Self {
+        self.max_batch_latency = Some(max_batch_latency);
+        self
+    }
+
+    /// Bound the buffer to at most `max_buffer_capacity` items and apply
+    /// `overflow_policy` once a `process` call would push past it, instead
+    /// of the default capacity of eight times the largest batch size.
+    pub fn with_bounded_buffer(
+        mut self,
+        max_buffer_capacity: usize,
+        overflow_policy: OverflowPolicy,
+    ) -> Self {
+        self.max_buffer_capacity = max_buffer_capacity;
+        self.overflow_policy = overflow_policy;
+        self
+    }
+}
+
+/// What to do when the buffer is full and another item needs to be
+/// buffered. See [`BatchConfig::with_bounded_buffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Spin until another thread drains space in the buffer.
+    Block,
+    /// Evict the oldest buffered item to make room for the new one.
+    DropOldest,
+    /// Discard the incoming item, leaving the buffer unchanged.
+    DropNewest,
+    /// Drain the buffer through the processor closure immediately on the
+    /// calling thread, then buffer the new item in the now-empty buffer.
+    #[default]
+    ForceFlush,
 }
 
 impl Default for BatchConfig {
@@ -67,17 +159,38 @@ impl Default for BatchConfig {
 /// Threshold-based batch processor
 pub struct ThresholdBatchProcessor<T> {
     config: BatchConfig,
-    buffer: Mutex<Vec<T>>,
+    /// Lock-free bounded staging buffer: producers claim a slot with a
+    /// `fetch_add` instead of contending on a `Mutex`, so `process` never
+    /// falls back to unbatched processing under contention. See
+    /// [`LockFreeBatchBuffer`].
+    buffer: LockFreeBatchBuffer<T>,
     processor: Box<dyn Fn(&[T]) + Send + Sync>,
 
     // Frequency tracking
     operation_count: AtomicU64,
     last_measurement: Mutex<Instant>,
     batching_enabled: AtomicBool,
+    /// EWMA-smoothed operation frequency, updated alongside
+    /// `last_measurement` in `update_batching_mode`.
+    smoothed_frequency: Mutex<f64>,
+    /// Effective batch size for the current smoothed frequency, recomputed
+    /// each time `update_batching_mode` takes a sample.
+    effective_batch_size: AtomicUsize,
+
+    /// When the first item landed in an otherwise-empty buffer, used to
+    /// enforce `BatchConfig::max_batch_latency`.
+    first_buffered_at: Mutex<Option<Instant>>,
 
     // Statistics
     total_operations: AtomicU64,
     batched_operations: AtomicU64,
+    dropped_operations: AtomicU64,
+
+    /// Set by `shutdown` to signal a running `start_timer` thread to stop.
+    timer_stop: AtomicBool,
+    /// Handle of the thread spawned by `start_timer`, if any, so `shutdown`
+    /// can join it within a deadline.
+    timer_handle: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl<T> ThresholdBatchProcessor<T> {
@@ -86,15 +199,37 @@ impl<T> ThresholdBatchProcessor<T> {
     where
         F: Fn(&[T]) + Send + Sync + 'static,
     {
+        let effective_batch_size = AtomicUsize::new(config.min_batch_size);
+        // `LockFreeBatchBuffer` needs a fixed capacity up front, unlike the
+        // `Vec` it replaces. `max_buffer_capacity` provides one directly;
+        // with no bound configured, size generously off the largest batch
+        // size so bursts ahead of a flush don't spuriously trip the
+        // overflow policy.
+        let buffer_capacity = if config.max_buffer_capacity == usize::MAX {
+            config
+                .max_batch_size
+                .max(config.min_batch_size)
+                .max(config.batch_size)
+                .max(1)
+                * 8
+        } else {
+            config.max_buffer_capacity.max(1)
+        };
         Self {
-            config,
-            buffer: Mutex::new(Vec::new()),
+            buffer: LockFreeBatchBuffer::new(buffer_capacity),
             processor: Box::new(processor),
             operation_count: AtomicU64::new(0),
             last_measurement: Mutex::new(Instant::now()),
             batching_enabled: AtomicBool::new(false),
+            smoothed_frequency: Mutex::new(0.0),
+            effective_batch_size,
+            first_buffered_at: Mutex::new(None),
+            config,
             total_operations: AtomicU64::new(0),
             batched_operations: AtomicU64::new(0),
+            dropped_operations: AtomicU64::new(0),
+            timer_stop: AtomicBool::new(false),
+            timer_handle: Mutex::new(None),
         }
     }
 
@@ -114,6 +249,10 @@ impl<T> ThresholdBatchProcessor<T> {
         // Check if we should update batching mode
         self.update_batching_mode();
 
+        // Guarantee bounded delivery delay: flush a stale partial batch
+        // before it has a chance to sit indefinitely.
+        self.flush_if_latency_exceeded();
+
         if self.batching_enabled.load(Ordering::Relaxed) {
             self.process_batched(item);
         } else {
@@ -127,39 +266,109 @@ impl<T> ThresholdBatchProcessor<T> {
         (self.processor)(&items);
     }
 
-    /// Process item via batching
+    /// Process item via batching, applying `BatchConfig::overflow_policy`
+    /// once the buffer has reached capacity. Never falls back to
+    /// `process_direct`: a full buffer is handled by the configured
+    /// overflow policy instead.
     fn process_batched(&self, item: T) {
-        let should_flush = {
-            if let Ok(mut buffer) = self.buffer.try_lock() {
-                buffer.push(item);
-                let should_flush = buffer.len() >= self.config.batch_size;
-                should_flush
-            } else {
-                // If we can't get the lock, process directly to avoid blocking
-                self.process_direct(item);
-                return;
+        let mut item = item;
+        loop {
+            match self.buffer.push(item) {
+                Ok(()) => {
+                    if self.buffer.len() == 1 {
+                        if let Ok(mut first_buffered_at) = self.first_buffered_at.try_lock() {
+                            *first_buffered_at = Some(Instant::now());
+                        }
+                    }
+                    let should_flush =
+                        self.buffer.len() >= self.effective_batch_size.load(Ordering::Relaxed);
+                    if should_flush {
+                        self.flush_batch();
+                    }
+                    self.batched_operations.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                Err(rejected) => {
+                    item = rejected;
+                    match self.config.overflow_policy {
+                        OverflowPolicy::DropNewest => {
+                            self.dropped_operations.fetch_add(1, Ordering::Relaxed);
+                            return;
+                        }
+                        OverflowPolicy::DropOldest => {
+                            // The buffer can't drop a single queued item in
+                            // place, so drain it, discard the oldest entry,
+                            // and requeue the rest alongside the new item.
+                            let Some(mut items) = self.buffer.take_ready() else {
+                                std::thread::yield_now();
+                                continue;
+                            };
+                            if !items.is_empty() {
+                                items.remove(0);
+                                self.dropped_operations.fetch_add(1, Ordering::Relaxed);
+                            }
+                            items.push(item);
+                            for requeued in items {
+                                if self.buffer.push(requeued).is_err() {
+                                    self.dropped_operations.fetch_add(1, Ordering::Relaxed);
+                                }
+                            }
+                            if self.buffer.len() >= self.effective_batch_size.load(Ordering::Relaxed)
+                            {
+                                self.flush_batch();
+                            }
+                            self.batched_operations.fetch_add(1, Ordering::Relaxed);
+                            return;
+                        }
+                        OverflowPolicy::ForceFlush => {
+                            self.flush_batch();
+                            continue;
+                        }
+                        OverflowPolicy::Block => {
+                            std::thread::yield_now();
+                            continue;
+                        }
+                    }
+                }
             }
-        };
-
-        if should_flush {
-            self.flush_batch();
         }
-
-        self.batched_operations.fetch_add(1, Ordering::Relaxed);
     }
 
     /// Flush the current batch
     pub fn flush_batch(&self) {
-        if let Ok(mut buffer) = self.buffer.try_lock() {
-            if !buffer.is_empty() {
-                let items = std::mem::take(&mut *buffer);
-                drop(buffer); // Release lock before processing
+        if let Some(items) = self.buffer.take_ready() {
+            if !items.is_empty() {
+                if let Ok(mut first_buffered_at) = self.first_buffered_at.try_lock() {
+                    *first_buffered_at = None;
+                }
                 (self.processor)(&items);
             }
         }
     }
 
-    /// Update batching mode based on current frequency
+    /// Flush the buffer if its oldest item has aged past
+    /// `BatchConfig::max_batch_latency`. No-op if no latency bound is
+    /// configured or the buffer is empty.
+    fn flush_if_latency_exceeded(&self) {
+        let Some(max_batch_latency) = self.config.max_batch_latency else {
+            return;
+        };
+        let is_stale = match self.first_buffered_at.try_lock() {
+            Ok(first_buffered_at) => first_buffered_at
+                .map(|buffered_at| buffered_at.elapsed() >= max_batch_latency)
+                .unwrap_or(false),
+            Err(_) => false,
+        };
+        if is_stale {
+            self.flush_batch();
+        }
+    }
+
+    /// Update batching mode based on the EWMA-smoothed frequency, applying
+    /// hysteresis between `enable_threshold` and `disable_threshold` so a
+    /// workload hovering around the threshold doesn't flap modes every
+    /// measurement window. Also recomputes `effective_batch_size` so it
+    /// scales continuously with the smoothed frequency.
     fn update_batching_mode(&self) {
         if let Ok(mut last_measurement) = self.last_measurement.try_lock() {
             let now = Instant::now();
@@ -167,21 +376,59 @@ impl<T> ThresholdBatchProcessor<T> {
 
             if elapsed >= self.config.measurement_window {
                 let ops_count = self.operation_count.swap(0, Ordering::Relaxed);
-                let frequency = if elapsed.as_secs() > 0 {
+                let sample = if elapsed.as_secs() > 0 {
                     ops_count / elapsed.as_secs()
                 } else {
                     ops_count * 1000 / elapsed.as_millis().max(1) as u64
                 };
 
-                // Enable batching if frequency exceeds threshold
-                let should_batch = frequency > self.config.frequency_threshold;
+                let smoothed =
+                    if let Ok(mut smoothed_frequency) = self.smoothed_frequency.try_lock() {
+                        *smoothed_frequency = self.config.ewma_alpha * sample as f64
+                            + (1.0 - self.config.ewma_alpha) * *smoothed_frequency;
+                        *smoothed_frequency
+                    } else {
+                        sample as f64
+                    };
+
+                // Hysteresis: only flip modes when the smoothed rate crosses
+                // cleanly past the threshold for the current mode, not the
+                // midpoint between them.
+                let currently_batching = self.batching_enabled.load(Ordering::Relaxed);
+                let should_batch = if currently_batching {
+                    smoothed >= self.config.disable_threshold as f64
+                } else {
+                    smoothed > self.config.enable_threshold as f64
+                };
                 self.batching_enabled.store(should_batch, Ordering::Relaxed);
+                self.effective_batch_size
+                    .store(self.scaled_batch_size(smoothed), Ordering::Relaxed);
 
                 *last_measurement = now;
             }
         }
     }
 
+    /// Linearly interpolate the effective batch size between
+    /// `min_batch_size` (at or below `enable_threshold`) and
+    /// `max_batch_size` (at or above `enable_threshold * 2`, used as a
+    /// simple saturation point for "high sustained load").
+    fn scaled_batch_size(&self, smoothed_frequency: f64) -> usize {
+        let low = self.config.enable_threshold as f64;
+        let high = (self.config.enable_threshold as f64 * 2.0).max(low + 1.0);
+
+        if self.config.max_batch_size <= self.config.min_batch_size || smoothed_frequency <= low {
+            return self.config.min_batch_size;
+        }
+        if smoothed_frequency >= high {
+            return self.config.max_batch_size;
+        }
+
+        let ratio = (smoothed_frequency - low) / (high - low);
+        let span = (self.config.max_batch_size - self.config.min_batch_size) as f64;
+        self.config.min_batch_size + (ratio * span).round() as usize
+    }
+
     /// Get current frequency (operations per second)
     pub fn current_frequency(&self) -> u64 {
         if let Ok(last_measurement) = self.last_measurement.try_lock() {
@@ -219,6 +466,13 @@ impl<T> ThresholdBatchProcessor<T> {
             },
             current_frequency: self.current_frequency(),
             batching_enabled: self.is_batching_enabled(),
+            dropped_operations: self.dropped_operations.load(Ordering::Relaxed),
+            smoothed_frequency: self
+                .smoothed_frequency
+                .try_lock()
+                .map(|smoothed| *smoothed)
+                .unwrap_or(0.0),
+            effective_batch_size: self.effective_batch_size.load(Ordering::Relaxed),
         }
     }
 
@@ -226,14 +480,92 @@ impl<T> ThresholdBatchProcessor<T> {
     pub fn reset_stats(&self) {
         self.total_operations.store(0, Ordering::Relaxed);
         self.batched_operations.store(0, Ordering::Relaxed);
+        self.dropped_operations.store(0, Ordering::Relaxed);
         self.operation_count.store(0, Ordering::Relaxed);
+        self.effective_batch_size
+            .store(self.config.min_batch_size, Ordering::Relaxed);
 
         if let Ok(mut last_measurement) = self.last_measurement.try_lock() {
             *last_measurement = Instant::now();
         }
+        if let Ok(mut smoothed_frequency) = self.smoothed_frequency.try_lock() {
+            *smoothed_frequency = 0.0;
+        }
+    }
+
+    /// Spawn a background thread that periodically flushes a stale partial
+    /// batch, so `max_batch_latency` is honored even if no further `process`
+    /// calls arrive to trigger the check. The thread runs until `shutdown`
+    /// is called (or the process exits).
+    pub fn start_timer(self: &std::sync::Arc<Self>, tick: Duration)
+    where
+        T: Send + 'static,
+    {
+        let processor = std::sync::Arc::clone(self);
+        let handle = std::thread::spawn(move || loop {
+            std::thread::sleep(tick);
+            if processor.timer_stop.load(Ordering::Acquire) {
+                break;
+            }
+            processor.flush_if_latency_exceeded();
+        });
+
+        if let Ok(mut timer_handle) = self.timer_handle.lock() {
+            *timer_handle = Some(handle);
+        }
+    }
+
+    /// Flush any remaining items and, if a background timer thread was
+    /// started via `start_timer`, signal it to stop and join it within
+    /// `timeout`. Returns `FlushTimeout` if the deadline elapses before the
+    /// timer thread stops; the timer thread keeps running in that case.
+    pub fn shutdown(&self, timeout: Duration) -> Result<ProcessingStats, FlushTimeout> {
+        self.flush_batch();
+        self.timer_stop.store(true, Ordering::Release);
+
+        let handle = self
+            .timer_handle
+            .lock()
+            .ok()
+            .and_then(|mut handle| handle.take());
+
+        if let Some(handle) = handle {
+            let deadline = Instant::now() + timeout;
+            while !handle.is_finished() {
+                if Instant::now() >= deadline {
+                    return Err(FlushTimeout);
+                }
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            let _ = handle.join();
+        }
+
+        Ok(self.stats())
+    }
+}
+
+impl<T> Drop for ThresholdBatchProcessor<T> {
+    fn drop(&mut self) {
+        self.flush_batch();
+    }
+}
+
+/// Returned by `ThresholdBatchProcessor::shutdown` when the background timer
+/// thread did not stop within the requested deadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlushTimeout;
+
+impl fmt::Display for FlushTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "timed out waiting for the background timer thread to stop"
+        )
     }
 }
 
+impl std::error::Error for FlushTimeout {}
+
 /// Processing statistics
 #[derive(Debug, Clone)]
 pub struct ProcessingStats {
@@ -243,6 +575,16 @@ pub struct ProcessingStats {
     pub batching_ratio: f64,
     pub current_frequency: u64,
     pub batching_enabled: bool,
+    /// Items discarded due to `OverflowPolicy::DropOldest` or
+    /// `OverflowPolicy::DropNewest` when the buffer was at capacity.
+    pub dropped_operations: u64,
+    /// Current EWMA-smoothed operation frequency driving the hysteresis
+    /// switch and `effective_batch_size`. See [`BatchConfig::with_hysteresis`].
+    pub smoothed_frequency: f64,
+    /// Batch size currently in effect, scaled between `min_batch_size` and
+    /// `max_batch_size` by the smoothed frequency. See
+    /// [`BatchConfig::with_batch_size_range`].
+    pub effective_batch_size: usize,
 }
 
 // Safety: ThresholdBatchProcessor is Send if T is Send
@@ -266,7 +608,9 @@ mod tests {
 
         let config = BatchConfig::custom(5, 100, Duration::from_millis(100));
         let processor = ThresholdBatchProcessor::new(config, move |items: &[i32]| {
-            let mut p = processed_clone.safe_lock().expect("Failed to acquire lock on processed");
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
             p.extend_from_slice(items);
         });
 
@@ -284,7 +628,9 @@ mod tests {
         // Should mostly use direct processing
         assert!(!processor.is_batching_enabled());
 
-        let processed_items = processed.safe_lock().expect("Failed to acquire lock on processed");
+        let processed_items = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
         assert_eq!(processed_items.len(), 10);
     }
 
@@ -318,10 +664,357 @@ mod tests {
         let stats = processor.stats();
         println!("High frequency stats: {:?}", stats);
 
-        let processed_items = processed.safe_lock().expect("Failed to acquire lock on processed");
+        let processed_items = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
         assert_eq!(processed_items.len(), 25);
     }
 
+    #[test]
+    fn test_stale_batch_flushes_on_next_process_call_before_batch_size_reached() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        // batch_size is unreachable by this test, but max_batch_latency is tiny.
+        let config = BatchConfig::custom(1000, 1, Duration::from_millis(1))
+            .with_max_batch_latency(Duration::from_millis(20));
+        let processor = ThresholdBatchProcessor::new(config, move |items: &[i32]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.extend_from_slice(items);
+        });
+
+        // First call: batching is still off until the first measurement
+        // window has elapsed, so 1 is processed directly. The frequency
+        // threshold of 1 then flips batching on for 2, which is buffered.
+        processor.process(1);
+        std::thread::sleep(Duration::from_millis(5));
+        processor.process(2);
+
+        assert_eq!(processed.safe_lock().unwrap().as_slice(), &[1]);
+
+        std::thread::sleep(Duration::from_millis(25));
+        // This call should see the stale buffer and flush it before pushing 3.
+        processor.process(3);
+
+        let processed_items = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
+        assert!(processed_items.contains(&2));
+    }
+
+    #[test]
+    fn test_no_latency_bound_never_flushes_early() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = BatchConfig::custom(1000, 1, Duration::from_millis(1));
+        assert!(config.max_batch_latency.is_none());
+        let processor = ThresholdBatchProcessor::new(config, move |items: &[i32]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.extend_from_slice(items);
+        });
+
+        processor.process(1);
+        std::thread::sleep(Duration::from_millis(10));
+        processor.process(2);
+
+        // 1 was processed directly before batching switched on. Without a
+        // latency bound, the buffered 2 should not have flushed yet since
+        // batch_size (1000) was never reached.
+        assert_eq!(processed.safe_lock().unwrap().as_slice(), &[1]);
+    }
+
+    #[test]
+    fn test_start_timer_flushes_stale_batch_without_further_process_calls() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = BatchConfig::custom(1000, 1, Duration::from_millis(1))
+            .with_max_batch_latency(Duration::from_millis(10));
+        let processor = Arc::new(ThresholdBatchProcessor::new(
+            config,
+            move |items: &[i32]| {
+                let mut p = processed_clone
+                    .safe_lock()
+                    .expect("Failed to acquire lock on processed");
+                p.extend_from_slice(items);
+            },
+        ));
+
+        processor.process(1);
+        std::thread::sleep(Duration::from_millis(5));
+        processor.process(2);
+
+        let _timer = processor.start_timer(Duration::from_millis(5));
+        std::thread::sleep(Duration::from_millis(40));
+
+        let processed_items = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
+        assert!(processed_items.contains(&2));
+    }
+
+    #[test]
+    fn test_drop_newest_discards_item_and_increments_dropped_operations() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = BatchConfig::custom(100, 1, Duration::from_millis(1))
+            .with_bounded_buffer(2, OverflowPolicy::DropNewest);
+        let processor = ThresholdBatchProcessor::new(config, move |items: &[i32]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.extend_from_slice(items);
+        });
+
+        processor.process(1);
+        std::thread::sleep(Duration::from_millis(5));
+        // 1 was processed directly before batching switched on; 2 and 3
+        // fill the buffer to capacity (2), so 4 should be dropped rather
+        // than grow the buffer.
+        processor.process(2);
+        processor.process(3);
+        processor.process(4);
+
+        processor.flush_batch();
+
+        let processed_items = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
+        assert_eq!(processed_items.as_slice(), &[1, 2, 3]);
+        assert_eq!(processor.stats().dropped_operations, 1);
+    }
+
+    #[test]
+    fn test_drop_oldest_evicts_front_to_make_room() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = BatchConfig::custom(100, 1, Duration::from_millis(1))
+            .with_bounded_buffer(2, OverflowPolicy::DropOldest);
+        let processor = ThresholdBatchProcessor::new(config, move |items: &[i32]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.extend_from_slice(items);
+        });
+
+        // 1 is processed directly before batching switches on; 2 and 3
+        // fill the buffer (capacity 2), so 4 evicts 2.
+        processor.process(1);
+        std::thread::sleep(Duration::from_millis(5));
+        processor.process(2);
+        processor.process(3);
+        processor.process(4);
+
+        processor.flush_batch();
+
+        let processed_items = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
+        assert_eq!(processed_items.as_slice(), &[1, 3, 4]);
+        assert_eq!(processor.stats().dropped_operations, 1);
+    }
+
+    #[test]
+    fn test_force_flush_drains_buffer_on_overflow_then_buffers_new_item() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = BatchConfig::custom(100, 1, Duration::from_millis(1))
+            .with_bounded_buffer(2, OverflowPolicy::ForceFlush);
+        let processor = ThresholdBatchProcessor::new(config, move |items: &[i32]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.extend_from_slice(items);
+        });
+
+        processor.process(1);
+        std::thread::sleep(Duration::from_millis(5));
+        processor.process(2);
+        processor.process(3);
+        // 1 went directly; 2 and 3 fill capacity 2, so the fourth item
+        // forces an immediate flush of [2, 3].
+        processor.process(4);
+
+        let processed_items = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
+        assert_eq!(processed_items.as_slice(), &[1, 2, 3]);
+        assert_eq!(processor.stats().dropped_operations, 0);
+    }
+
+    #[test]
+    fn test_block_waits_for_space_freed_by_another_thread() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = BatchConfig::custom(100, 1, Duration::from_millis(1))
+            .with_bounded_buffer(1, OverflowPolicy::Block);
+        let processor = Arc::new(ThresholdBatchProcessor::new(
+            config,
+            move |items: &[i32]| {
+                let mut p = processed_clone
+                    .safe_lock()
+                    .expect("Failed to acquire lock on processed");
+                p.extend_from_slice(items);
+            },
+        ));
+
+        processor.process(1);
+        std::thread::sleep(Duration::from_millis(5));
+        // 1 went directly; batching is now on and 2 fills the buffer.
+        processor.process(2);
+
+        let blocked_processor = processor.clone();
+        let handle = std::thread::spawn(move || {
+            // Buffer is at capacity (1), so this blocks until the main
+            // thread below flushes and frees a slot.
+            blocked_processor.process(3);
+        });
+
+        std::thread::sleep(Duration::from_millis(20));
+        processor.flush_batch();
+        handle.join().expect("blocked process() thread panicked");
+
+        assert_eq!(processor.stats().dropped_operations, 0);
+    }
+
+    #[test]
+    fn test_drop_flushes_remaining_buffered_items() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        {
+            let config = BatchConfig::custom(100, 1, Duration::from_millis(1));
+            let processor = ThresholdBatchProcessor::new(config, move |items: &[i32]| {
+                let mut p = processed_clone
+                    .safe_lock()
+                    .expect("Failed to acquire lock on processed");
+                p.extend_from_slice(items);
+            });
+
+            processor.process(1);
+            std::thread::sleep(Duration::from_millis(5));
+            processor.process(2);
+            // processor drops here without an explicit flush_batch() call.
+        }
+
+        let processed_items = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
+        assert!(processed_items.contains(&2));
+    }
+
+    #[test]
+    fn test_shutdown_flushes_and_stops_timer_within_timeout() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config = BatchConfig::custom(100, 1, Duration::from_millis(1));
+        let processor = Arc::new(ThresholdBatchProcessor::new(
+            config,
+            move |items: &[i32]| {
+                let mut p = processed_clone
+                    .safe_lock()
+                    .expect("Failed to acquire lock on processed");
+                p.extend_from_slice(items);
+            },
+        ));
+
+        processor.process(1);
+        std::thread::sleep(Duration::from_millis(5));
+        processor.process(2);
+
+        processor.start_timer(Duration::from_millis(5));
+
+        let stats = processor
+            .shutdown(Duration::from_secs(1))
+            .expect("timer thread should stop well within 1s");
+
+        assert_eq!(stats.total_operations, 2);
+
+        let processed_items = processed
+            .safe_lock()
+            .expect("Failed to acquire lock on processed");
+        assert!(processed_items.contains(&2));
+    }
+
+    #[test]
+    fn test_effective_batch_size_scales_with_sustained_load() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        let config =
+            BatchConfig::custom(10, 100, Duration::from_millis(1)).with_batch_size_range(10, 100);
+        let processor = ThresholdBatchProcessor::new(config, move |items: &[i32]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.extend_from_slice(items);
+        });
+
+        // First window: modest load, should leave the effective batch size
+        // near min_batch_size.
+        processor.process(1);
+        std::thread::sleep(Duration::from_millis(5));
+
+        // Subsequent windows under sustained heavy load should push the
+        // smoothed frequency, and therefore effective_batch_size, upward.
+        for _ in 0..20 {
+            for i in 0..50 {
+                processor.process(i);
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let stats = processor.stats();
+        assert!(
+            stats.effective_batch_size > 10,
+            "expected effective_batch_size to grow under sustained load, got {}",
+            stats.effective_batch_size
+        );
+        assert!(stats.effective_batch_size <= 100);
+    }
+
+    #[test]
+    fn test_hysteresis_keeps_batching_enabled_between_thresholds() {
+        let processed = Arc::new(StdMutex::new(Vec::new()));
+        let processed_clone = processed.clone();
+
+        // Wide gap between enable/disable so a single dip in frequency
+        // between them does not flip batching back off.
+        let config =
+            BatchConfig::custom(10, 100, Duration::from_millis(1)).with_hysteresis(100, 10);
+        let processor = ThresholdBatchProcessor::new(config, move |items: &[i32]| {
+            let mut p = processed_clone
+                .safe_lock()
+                .expect("Failed to acquire lock on processed");
+            p.extend_from_slice(items);
+        });
+
+        // Drive the smoothed frequency well above enable_threshold.
+        for _ in 0..5 {
+            for i in 0..50 {
+                processor.process(i);
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(processor.is_batching_enabled());
+
+        // A single quiet window drops the instantaneous rate, but the
+        // smoothed rate should still sit above disable_threshold (10).
+        processor.process(0);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(processor.is_batching_enabled());
+    }
+
     #[test]
     fn test_config_presets() {
         let low = BatchConfig::low_frequency();
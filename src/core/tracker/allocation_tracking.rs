@@ -48,6 +48,8 @@ impl MemoryTracker {
         // Only do basic tracking for system allocations, save advanced features for user variables
         let is_user_variable = false; // This is a system allocation from global allocator
 
+        self.check_memory_limit(size)?;
+
         // Create allocation info first (no locks needed)
         let mut allocation = AllocationInfo::new(ptr, size);
 
@@ -85,6 +87,16 @@ impl MemoryTracker {
             drop(bounded_stats);
             drop(active);
 
+            self.emit_allocation_event(
+                super::event_reporter::AllocationEventKind::Alloc,
+                allocation.ptr,
+                allocation.size,
+                allocation.type_name.clone(),
+                allocation.var_name.clone(),
+                allocation.scope_name.clone(),
+                allocation.timestamp_alloc,
+            );
+
             // Add to bounded history manager (automatically handles bounds)
             if !self.is_fast_mode() && std::env::var("MEMSCOPE_FULL_HISTORY").is_ok() {
                 if let Ok(mut history_manager) = self.history_manager.try_lock() {
@@ -107,6 +119,8 @@ impl MemoryTracker {
         inferred_var_name: String,
         inferred_type_name: String,
     ) -> TrackingResult<()> {
+        self.check_memory_limit(size)?;
+
         // Create allocation info with enhanced context
         let mut allocation = AllocationInfo::new(ptr, size);
 
@@ -146,6 +160,16 @@ impl MemoryTracker {
             drop(bounded_stats);
             drop(active);
 
+            self.emit_allocation_event(
+                super::event_reporter::AllocationEventKind::Alloc,
+                allocation.ptr,
+                allocation.size,
+                allocation.type_name.clone(),
+                allocation.var_name.clone(),
+                allocation.scope_name.clone(),
+                allocation.timestamp_alloc,
+            );
+
             // Add to bounded history manager (automatically handles bounds)
             if !self.is_fast_mode() && std::env::var("MEMSCOPE_FULL_HISTORY").is_ok() {
                 if let Ok(mut history_manager) = self.history_manager.try_lock() {
@@ -204,6 +228,19 @@ impl MemoryTracker {
                     // Update bounded statistics (automatically handles bounds)
                     bounded_stats.add_allocation(&allocation);
 
+                    drop(bounded_stats);
+                    drop(active);
+
+                    self.emit_allocation_event(
+                        super::event_reporter::AllocationEventKind::Alloc,
+                        allocation.ptr,
+                        allocation.size,
+                        allocation.type_name.clone(),
+                        allocation.var_name.clone(),
+                        allocation.scope_name.clone(),
+                        allocation.timestamp_alloc,
+                    );
+
                     return Ok(());
                 }
                 _ => {
@@ -243,6 +280,16 @@ impl MemoryTracker {
                     // Update bounded statistics (automatically handles bounds)
                     bounded_stats.add_allocation(&allocation);
 
+                    self.emit_allocation_event(
+                        super::event_reporter::AllocationEventKind::Alloc,
+                        allocation.ptr,
+                        allocation.size,
+                        allocation.type_name.clone(),
+                        allocation.var_name.clone(),
+                        allocation.scope_name.clone(),
+                        allocation.timestamp_alloc,
+                    );
+
                     // Try to add to history manager if possible
                     if let Ok(mut history_manager) = self.history_manager.try_lock() {
                         history_manager.add_allocation(allocation);
@@ -297,6 +344,16 @@ impl MemoryTracker {
             drop(bounded_stats);
             drop(active);
 
+            self.emit_allocation_event(
+                super::event_reporter::AllocationEventKind::Dealloc,
+                allocation.ptr,
+                allocation.size,
+                allocation.type_name.clone(),
+                allocation.var_name.clone(),
+                allocation.scope_name.clone(),
+                dealloc_timestamp,
+            );
+
             // Update allocation history with deallocation timestamp
             if let Ok(mut history_manager) = self.history_manager.try_lock() {
                 history_manager.add_allocation(allocation);
@@ -334,6 +391,16 @@ impl MemoryTracker {
                         drop(bounded_stats);
                         drop(active);
 
+                        self.emit_allocation_event(
+                            super::event_reporter::AllocationEventKind::Dealloc,
+                            allocation.ptr,
+                            allocation.size,
+                            allocation.type_name.clone(),
+                            allocation.var_name.clone(),
+                            allocation.scope_name.clone(),
+                            dealloc_timestamp,
+                        );
+
                         // Update allocation history with deallocation timestamp
                         if let Ok(mut history_manager) = self.history_manager.try_lock() {
                             history_manager.add_allocation(allocation);
@@ -937,6 +1004,19 @@ impl MemoryTracker {
             Err("Failed to acquire ownership history lock".to_string())
         }
     }
+
+    /// Snapshot the real recorded event history for every tracked allocation,
+    /// keyed by allocation pointer. Used by exports and the ownership dataflow
+    /// analysis instead of synthesizing events from heuristics.
+    pub fn get_all_ownership_events(
+        &self,
+    ) -> std::collections::HashMap<usize, Vec<crate::core::ownership_history::OwnershipEvent>> {
+        if let Ok(ownership_history) = self.ownership_history.try_lock() {
+            ownership_history.get_all_events().clone()
+        } else {
+            std::collections::HashMap::new()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,346 @@
+//! Push-based streaming reporters for live allocation/deallocation events.
+//!
+//! [`MemoryTracker`] normally only exposes allocation data through post-hoc
+//! queries (`get_stats`, `get_active_allocations`, ...) or the batch
+//! `.memscope` export. Some consumers -- a live dashboard, an external
+//! profiler, a test harness streaming per-event results -- want every
+//! alloc/dealloc as it happens instead. Registering an [`EventReporter`] via
+//! [`MemoryTracker::register_event_reporter`] gets it invoked under the same
+//! lock path that already updates `bounded_stats`, one [`AllocationEvent`]
+//! per tracked alloc or dealloc.
+//!
+//! [`NdjsonEventReporter`] is the bundled implementation: it serializes each
+//! event as one newline-delimited JSON line and writes it to an arbitrary
+//! `io::Write` sink. Writes go through a bounded internal buffer with a
+//! drop-oldest-on-overflow policy, so a slow or stalled consumer degrades to
+//! lost events rather than blocking allocation tracking.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Which side of an allocation's lifecycle an [`AllocationEvent`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationEventKind {
+    Alloc,
+    Dealloc,
+}
+
+impl AllocationEventKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AllocationEventKind::Alloc => "alloc",
+            AllocationEventKind::Dealloc => "dealloc",
+        }
+    }
+}
+
+/// One allocation or deallocation, as delivered to registered [`EventReporter`]s.
+#[derive(Debug, Clone)]
+pub struct AllocationEvent {
+    pub kind: AllocationEventKind,
+    pub ptr: usize,
+    pub size: usize,
+    pub type_name: Option<String>,
+    pub var_name: Option<String>,
+    pub scope_name: Option<String>,
+    pub thread: String,
+    pub timestamp: u64,
+}
+
+impl AllocationEvent {
+    /// Render as a single newline-delimited JSON line (no trailing `\n`).
+    pub fn to_ndjson_line(&self) -> String {
+        serde_json::json!({
+            "event": self.kind.as_str(),
+            "ptr": self.ptr,
+            "size": self.size,
+            "type": self.type_name,
+            "var": self.var_name,
+            "scope": self.scope_name,
+            "thread": self.thread,
+            "ts": self.timestamp,
+        })
+        .to_string()
+    }
+}
+
+/// Receives every [`AllocationEvent`] the tracker emits.
+///
+/// Implementations must not block the calling (tracking) thread for long --
+/// `report_event` runs under the same lock path that updates tracker stats.
+/// [`NdjsonEventReporter`] satisfies this by buffering and only making a
+/// best-effort, non-blocking attempt to drain to its sink.
+pub trait EventReporter: Send + Sync {
+    fn report_event(&self, event: &AllocationEvent);
+}
+
+/// Streams [`AllocationEvent`]s as NDJSON to an `io::Write` sink.
+///
+/// Incoming events are pushed onto a bounded `VecDeque`; once it reaches
+/// `max_buffered` entries, the oldest buffered line is dropped to make room
+/// (and counted in [`NdjsonEventReporter::dropped_events`]). Each
+/// `report_event` call also makes a non-blocking attempt (`try_lock`) to
+/// drain the buffer to the sink, so a consumer that is keeping up sees
+/// events immediately while a stalled one simply falls behind.
+pub struct NdjsonEventReporter<W: Write + Send> {
+    sink: Mutex<W>,
+    buffer: Mutex<VecDeque<String>>,
+    max_buffered: usize,
+    dropped_events: AtomicUsize,
+}
+
+impl<W: Write + Send> NdjsonEventReporter<W> {
+    pub fn new(sink: W, max_buffered: usize) -> Self {
+        Self {
+            sink: Mutex::new(sink),
+            buffer: Mutex::new(VecDeque::with_capacity(max_buffered.min(1024))),
+            max_buffered,
+            dropped_events: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of events dropped so far because the buffer was full.
+    pub fn dropped_events(&self) -> usize {
+        self.dropped_events.load(Ordering::Relaxed)
+    }
+
+    /// Number of NDJSON lines currently buffered and not yet written.
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.lock().map(|buf| buf.len()).unwrap_or(0)
+    }
+
+    /// Drain every currently-buffered line to the sink, blocking on both
+    /// internal locks. Returns the number of lines written.
+    pub fn flush(&self) -> io::Result<usize> {
+        let lines: Vec<String> = {
+            let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+            buffer.drain(..).collect()
+        };
+        let count = lines.len();
+        if count == 0 {
+            return Ok(0);
+        }
+        let mut sink = self.sink.lock().unwrap_or_else(|e| e.into_inner());
+        for line in lines {
+            writeln!(sink, "{line}")?;
+        }
+        sink.flush()?;
+        Ok(count)
+    }
+
+    /// Best-effort, non-blocking drain: does nothing if the sink is
+    /// currently locked by another thread (e.g. a concurrent `flush`).
+    /// Lines that fail to write go back to the front of the buffer.
+    fn try_flush(&self) {
+        let Ok(mut sink) = self.sink.try_lock() else {
+            return;
+        };
+        let mut lines: VecDeque<String> = {
+            let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+            buffer.drain(..).collect()
+        };
+        while let Some(line) = lines.pop_front() {
+            if writeln!(sink, "{line}").is_err() {
+                lines.push_front(line);
+                let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+                while let Some(unwritten) = lines.pop_back() {
+                    buffer.push_front(unwritten);
+                }
+                while buffer.len() > self.max_buffered {
+                    buffer.pop_front();
+                    self.dropped_events.fetch_add(1, Ordering::Relaxed);
+                }
+                return;
+            }
+        }
+        let _ = sink.flush();
+    }
+}
+
+impl<W: Write + Send> EventReporter for NdjsonEventReporter<W> {
+    fn report_event(&self, event: &AllocationEvent) {
+        let line = event.to_ndjson_line();
+        {
+            let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+            if buffer.len() >= self.max_buffered {
+                buffer.pop_front();
+                self.dropped_events.fetch_add(1, Ordering::Relaxed);
+            }
+            buffer.push_back(line);
+        }
+        self.try_flush();
+    }
+}
+
+impl super::memory_tracker::MemoryTracker {
+    /// Register a reporter to receive every future [`AllocationEvent`].
+    pub fn register_event_reporter(&self, reporter: std::sync::Arc<dyn EventReporter>) {
+        if let Ok(mut reporters) = self.event_reporters.lock() {
+            reporters.push(reporter);
+        }
+    }
+
+    /// Remove every previously registered event reporter.
+    pub fn clear_event_reporters(&self) {
+        if let Ok(mut reporters) = self.event_reporters.lock() {
+            reporters.clear();
+        }
+    }
+
+    /// Build an [`AllocationEvent`] and hand it to every registered reporter.
+    ///
+    /// Uses `try_lock` so a reporter registration happening concurrently
+    /// cannot stall the allocation-tracking path this is called from.
+    pub(crate) fn emit_allocation_event(
+        &self,
+        kind: AllocationEventKind,
+        ptr: usize,
+        size: usize,
+        type_name: Option<String>,
+        var_name: Option<String>,
+        scope_name: Option<String>,
+        timestamp: u64,
+    ) {
+        let Ok(reporters) = self.event_reporters.try_lock() else {
+            return;
+        };
+        if reporters.is_empty() {
+            return;
+        }
+        let event = AllocationEvent {
+            kind,
+            ptr,
+            size,
+            type_name,
+            var_name,
+            scope_name,
+            thread: format!("{:?}", std::thread::current().id()),
+            timestamp,
+        };
+        for reporter in reporters.iter() {
+            reporter.report_event(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn event(kind: AllocationEventKind, ptr: usize, size: usize) -> AllocationEvent {
+        AllocationEvent {
+            kind,
+            ptr,
+            size,
+            type_name: Some("String".to_string()),
+            var_name: Some("v".to_string()),
+            scope_name: None,
+            thread: "main".to_string(),
+            timestamp: 1,
+        }
+    }
+
+    #[test]
+    fn test_to_ndjson_line_contains_expected_fields() {
+        let line = event(AllocationEventKind::Alloc, 0x1000, 32).to_ndjson_line();
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["event"], "alloc");
+        assert_eq!(parsed["ptr"], 0x1000);
+        assert_eq!(parsed["size"], 32);
+        assert_eq!(parsed["type"], "String");
+    }
+
+    #[test]
+    fn test_report_event_drains_buffer_to_sink() {
+        let sink: Vec<u8> = Vec::new();
+        let reporter = NdjsonEventReporter::new(sink, 16);
+        reporter.report_event(&event(AllocationEventKind::Alloc, 0x1, 8));
+        reporter.report_event(&event(AllocationEventKind::Dealloc, 0x1, 8));
+        // report_event already best-effort flushes, but buffer should be empty now.
+        assert_eq!(reporter.buffered_len(), 0);
+    }
+
+    #[test]
+    fn test_overflow_drops_oldest_and_increments_counter() {
+        // A sink that always fails to write keeps every event stuck in the buffer.
+        struct FailingSink;
+        impl Write for FailingSink {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("sink unavailable"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let reporter = NdjsonEventReporter::new(FailingSink, 2);
+        reporter.report_event(&event(AllocationEventKind::Alloc, 0x1, 8));
+        reporter.report_event(&event(AllocationEventKind::Alloc, 0x2, 8));
+        reporter.report_event(&event(AllocationEventKind::Alloc, 0x3, 8));
+
+        assert_eq!(reporter.buffered_len(), 2);
+        assert_eq!(reporter.dropped_events(), 1);
+    }
+
+    struct RecordingReporter {
+        events: Mutex<Vec<AllocationEventKind>>,
+    }
+
+    impl EventReporter for RecordingReporter {
+        fn report_event(&self, event: &AllocationEvent) {
+            self.events.lock().unwrap().push(event.kind);
+        }
+    }
+
+    #[test]
+    fn test_tracker_emits_alloc_and_dealloc_events_to_registered_reporter() {
+        let tracker = super::super::memory_tracker::MemoryTracker::new();
+        let reporter = Arc::new(RecordingReporter {
+            events: Mutex::new(Vec::new()),
+        });
+        tracker.register_event_reporter(reporter.clone());
+
+        tracker.track_allocation(0x5000, 16).unwrap();
+        tracker.track_deallocation(0x5000).unwrap();
+
+        let events = reporter.events.lock().unwrap();
+        assert_eq!(
+            events.as_slice(),
+            [AllocationEventKind::Alloc, AllocationEventKind::Dealloc]
+        );
+    }
+
+    #[test]
+    fn test_clear_event_reporters_stops_delivery() {
+        let tracker = super::super::memory_tracker::MemoryTracker::new();
+        let reporter = Arc::new(RecordingReporter {
+            events: Mutex::new(Vec::new()),
+        });
+        tracker.register_event_reporter(reporter.clone());
+        tracker.clear_event_reporters();
+
+        tracker.track_allocation(0x6000, 16).unwrap();
+
+        assert!(reporter.events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_shared_reporter_across_threads_via_arc() {
+        let reporter = Arc::new(NdjsonEventReporter::new(Vec::new(), 64));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let reporter = Arc::clone(&reporter);
+                std::thread::spawn(move || {
+                    reporter.report_event(&event(AllocationEventKind::Alloc, i, 8));
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(reporter.dropped_events() == 0);
+    }
+}
@@ -243,6 +243,13 @@ impl MemoryTracker {
                 </ul>
             </div>
 
+            <div class="section">
+                <h2>Padding Waste by Type</h2>
+                <ul class="type-list">
+                    {{{{PADDING_WASTE_REPORT}}}}
+                </ul>
+            </div>
+
             <div class="recommendations">
                 <h3>💡 Optimization Recommendations</h3>
                 <ul>
@@ -276,9 +283,38 @@ impl MemoryTracker {
             active_allocations.len()
         );
 
+        let padding_waste_report = crate::analysis::analyze_padding_waste(active_allocations);
+        let html = html.replace(
+            "{{PADDING_WASTE_REPORT}}",
+            &self.generate_padding_waste_html(&padding_waste_report),
+        );
+
         Ok(html)
     }
 
+    /// Render a ranked padding-waste report as `<li>` rows for the
+    /// "Padding Waste by Type" section.
+    fn generate_padding_waste_html(&self, report: &crate::analysis::LayoutWasteReport) -> String {
+        if report.types.is_empty() {
+            return r#"<li class="type-item"><span class="type-name">No layout data captured</span></li>"#.to_string();
+        }
+
+        report
+            .types
+            .iter()
+            .take(10)
+            .map(|summary| {
+                format!(
+                    r#"<li class="type-item"><span class="type-name">{} ({} instances)</span><span class="type-size">{} wasted</span></li>"#,
+                    summary.type_name,
+                    summary.instance_count,
+                    self.format_bytes(summary.total_wasted_bytes)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
     /// Format bytes in human-readable format
     fn format_bytes(&self, bytes: usize) -> String {
         const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
@@ -303,8 +339,14 @@ impl MemoryTracker {
         stats: &crate::core::types::MemoryStats,
         memory_by_type: &[crate::core::types::TypeMemoryUsage],
     ) -> String {
-        let recommendations =
-            super::export_json::generate_optimization_recommendations(stats, memory_by_type);
+        let allocation_history = self.get_allocation_history().unwrap_or_default();
+        let recommendations = super::export_json::generate_optimization_recommendations(
+            stats,
+            &allocation_history,
+            memory_by_type,
+            &Default::default(),
+            None,
+        );
 
         recommendations
             .iter()
@@ -0,0 +1,228 @@
+//! Configurable memory ceiling / allocation-limit enforcement.
+//!
+//! Stress tests and long-running consumers often want to know not just "how
+//! much did we allocate" after the fact, but "stop me (or warn me) before we
+//! cross a ceiling" -- the same shape as allocator-capping crates. This
+//! module adds that enforcement layer on top of [`MemoryTracker`] without
+//! touching its existing allocation bookkeeping: [`MemoryTracker::track_allocation`]
+//! consults [`MemoryTracker::check_memory_limit`] before recording an
+//! allocation, so the limit is enforced on the same path real allocations
+//! take.
+//!
+//! The high-water mark itself isn't new -- [`crate::core::types::MemoryStats::peak_memory`]
+//! already tracks it as a monotonic max on every tracked allocation via
+//! [`crate::core::bounded_memory_stats::BoundedMemoryStats::add_allocation`] --
+//! this module just exposes it under the name this feature calls for
+//! ([`MemoryTracker::peak_allocated`]).
+
+use super::memory_tracker::MemoryTracker;
+use crate::core::types::{TrackingError, TrackingResult};
+
+/// How a crossed memory limit is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemoryLimitEnforcement {
+    /// Fire the overflow callback and record a pressure event, but let the
+    /// allocation through.
+    #[default]
+    Soft,
+    /// Reject the allocation with [`TrackingError::MemoryLimitExceeded`].
+    Hard,
+}
+
+/// Configuration for [`MemoryTracker`]'s memory-limit enforcement.
+#[derive(Clone, Copy, Default)]
+pub struct MemoryLimitConfig {
+    /// Ceiling on `active_memory`, in bytes. `None` disables enforcement.
+    pub limit_bytes: Option<usize>,
+    /// Behavior once `limit_bytes` would be crossed.
+    pub enforcement: MemoryLimitEnforcement,
+}
+
+/// Overflow callback invoked when an allocation would cross the configured
+/// limit, called with `(active_memory_before, limit_bytes)`.
+pub type OverflowCallback = Box<dyn Fn(usize, usize) + Send + Sync>;
+
+/// Mutable state backing [`MemoryTracker`]'s memory-limit enforcement.
+#[derive(Default)]
+pub struct MemoryLimitState {
+    config: MemoryLimitConfig,
+    overflow_callback: Option<OverflowCallback>,
+    /// Number of times an allocation crossed the limit under soft enforcement.
+    pressure_events: usize,
+}
+
+impl MemoryTracker {
+    /// Cap `active_memory` at `bytes`, using the tracker's current enforcement
+    /// mode (soft by default; see [`MemoryTracker::set_memory_limit_with_enforcement`]).
+    pub fn set_memory_limit(&self, bytes: usize) {
+        if let Ok(mut state) = self.memory_limit.lock() {
+            state.config.limit_bytes = Some(bytes);
+        }
+    }
+
+    /// Cap `active_memory` at `bytes` with an explicit enforcement mode.
+    pub fn set_memory_limit_with_enforcement(
+        &self,
+        bytes: usize,
+        enforcement: MemoryLimitEnforcement,
+    ) {
+        if let Ok(mut state) = self.memory_limit.lock() {
+            state.config.limit_bytes = Some(bytes);
+            state.config.enforcement = enforcement;
+        }
+    }
+
+    /// Remove any configured memory limit.
+    pub fn clear_memory_limit(&self) {
+        if let Ok(mut state) = self.memory_limit.lock() {
+            state.config.limit_bytes = None;
+        }
+    }
+
+    /// Register a callback invoked whenever an allocation would cross the
+    /// configured limit, replacing any previously registered callback.
+    pub fn set_memory_limit_overflow_callback(
+        &self,
+        callback: impl Fn(usize, usize) + Send + Sync + 'static,
+    ) {
+        if let Ok(mut state) = self.memory_limit.lock() {
+            state.overflow_callback = Some(Box::new(callback));
+        }
+    }
+
+    /// Bytes remaining before the configured limit is crossed, or `None` if
+    /// no limit is configured.
+    pub fn remaining(&self) -> Option<usize> {
+        let limit_bytes = self.memory_limit.lock().ok()?.config.limit_bytes?;
+        let active_memory = self
+            .bounded_stats
+            .lock()
+            .map(|stats| stats.active_memory)
+            .unwrap_or(0);
+        Some(limit_bytes.saturating_sub(active_memory))
+    }
+
+    /// High-water mark of `active_memory` across the tracker's lifetime.
+    pub fn peak_allocated(&self) -> usize {
+        self.bounded_stats
+            .lock()
+            .map(|stats| stats.peak_memory)
+            .unwrap_or(0)
+    }
+
+    /// Number of times an allocation crossed the limit under soft enforcement.
+    pub fn memory_pressure_events(&self) -> usize {
+        self.memory_limit
+            .lock()
+            .map(|state| state.pressure_events)
+            .unwrap_or(0)
+    }
+
+    /// Check `incoming_size` against the configured limit before an
+    /// allocation is recorded. Under [`MemoryLimitEnforcement::Soft`], fires
+    /// the overflow callback, records a pressure event, and returns `Ok`.
+    /// Under [`MemoryLimitEnforcement::Hard`], returns
+    /// [`TrackingError::MemoryLimitExceeded`] instead of recording the
+    /// allocation.
+    pub(crate) fn check_memory_limit(&self, incoming_size: usize) -> TrackingResult<()> {
+        let mut state = match self.memory_limit.lock() {
+            Ok(state) => state,
+            Err(_) => return Ok(()),
+        };
+        let Some(limit_bytes) = state.config.limit_bytes else {
+            return Ok(());
+        };
+
+        let active_memory = self
+            .bounded_stats
+            .lock()
+            .map(|stats| stats.active_memory)
+            .unwrap_or(0);
+
+        if active_memory.saturating_add(incoming_size) <= limit_bytes {
+            return Ok(());
+        }
+
+        if let Some(callback) = state.overflow_callback.as_ref() {
+            callback(active_memory, limit_bytes);
+        }
+        match state.config.enforcement {
+            MemoryLimitEnforcement::Soft => {
+                state.pressure_events += 1;
+                Ok(())
+            }
+            MemoryLimitEnforcement::Hard => Err(TrackingError::MemoryLimitExceeded(format!(
+                "allocation of {incoming_size} bytes would bring active memory to {} bytes, exceeding the {limit_bytes}-byte limit",
+                active_memory + incoming_size
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_remaining_is_none_without_a_limit() {
+        let tracker = MemoryTracker::new();
+        assert_eq!(tracker.remaining(), None);
+    }
+
+    #[test]
+    fn test_remaining_reflects_configured_limit() {
+        let tracker = MemoryTracker::new();
+        tracker.set_memory_limit(1024);
+        assert_eq!(tracker.remaining(), Some(1024));
+    }
+
+    #[test]
+    fn test_soft_limit_allows_allocation_and_records_pressure_event() {
+        let tracker = MemoryTracker::new();
+        tracker.set_memory_limit_with_enforcement(10, MemoryLimitEnforcement::Soft);
+        let result = tracker.track_allocation(0x1000, 100);
+        assert!(result.is_ok());
+        assert_eq!(tracker.memory_pressure_events(), 1);
+    }
+
+    #[test]
+    fn test_hard_limit_rejects_allocation_with_memory_limit_exceeded() {
+        let tracker = MemoryTracker::new();
+        tracker.set_memory_limit_with_enforcement(10, MemoryLimitEnforcement::Hard);
+        let result = tracker.track_allocation(0x2000, 100);
+        assert!(matches!(result, Err(TrackingError::MemoryLimitExceeded(_))));
+    }
+
+    #[test]
+    fn test_overflow_callback_is_invoked_on_soft_limit_crossing() {
+        let tracker = MemoryTracker::new();
+        let invoked = Arc::new(AtomicUsize::new(0));
+        let invoked_clone = Arc::clone(&invoked);
+        tracker.set_memory_limit_overflow_callback(move |_active, _limit| {
+            invoked_clone.fetch_add(1, Ordering::SeqCst);
+        });
+        tracker.set_memory_limit_with_enforcement(10, MemoryLimitEnforcement::Soft);
+        let _ = tracker.track_allocation(0x3000, 100);
+        assert_eq!(invoked.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_peak_allocated_tracks_high_water_mark() {
+        let tracker = MemoryTracker::new();
+        tracker.track_allocation(0x4000, 100).unwrap();
+        tracker.track_allocation(0x5000, 200).unwrap();
+        tracker.track_deallocation(0x4000).unwrap();
+        assert_eq!(tracker.peak_allocated(), 300);
+    }
+
+    #[test]
+    fn test_clear_memory_limit_disables_enforcement() {
+        let tracker = MemoryTracker::new();
+        tracker.set_memory_limit_with_enforcement(10, MemoryLimitEnforcement::Hard);
+        tracker.clear_memory_limit();
+        let result = tracker.track_allocation(0x6000, 100);
+        assert!(result.is_ok());
+    }
+}
@@ -3,13 +3,15 @@
 //! This module contains the main MemoryTracker struct and its basic methods
 //! for creating, configuring, and managing the memory tracking system.
 
+use crate::core::bounded_memory_stats::{
+    AllocationHistoryManager, BoundedMemoryStats, BoundedStatsConfig,
+};
+use crate::core::ownership_history::{HistoryConfig, OwnershipEventType, OwnershipHistoryRecorder};
 use crate::core::types::{
     AllocationInfo, DropChainNode, DropChainPerformanceMetrics, EnhancedPotentialLeak,
     LeakEvidence, LeakEvidenceType, LeakImpact, LeakRiskLevel, LeakType, MemoryStats,
     ResourceLeakAnalysis, TrackingResult,
 };
-use crate::core::bounded_memory_stats::{BoundedMemoryStats, AllocationHistoryManager, BoundedStatsConfig};
-use crate::core::ownership_history::{OwnershipHistoryRecorder, OwnershipEventType, HistoryConfig};
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
@@ -62,6 +64,11 @@ pub struct MemoryTracker {
     pub(crate) stats: Mutex<MemoryStats>,
     /// Fast mode flag for testing (reduces overhead)
     pub(crate) fast_mode: std::sync::atomic::AtomicBool,
+    /// Configurable memory ceiling / allocation-limit enforcement state
+    pub(crate) memory_limit: Mutex<super::memory_limit::MemoryLimitState>,
+    /// Reporters fed one [`super::event_reporter::AllocationEvent`] per
+    /// tracked alloc/dealloc, for live streaming consumers.
+    pub(crate) event_reporters: Mutex<Vec<Arc<dyn super::event_reporter::EventReporter>>>,
 }
 
 impl MemoryTracker {
@@ -69,7 +76,7 @@ impl MemoryTracker {
     pub fn new() -> Self {
         let fast_mode =
             std::env::var("MEMSCOPE_TEST_MODE").is_ok() || cfg!(test) || cfg!(feature = "test");
-        
+
         // Configure bounded stats based on environment
         let config = if fast_mode {
             // Smaller limits for testing
@@ -83,7 +90,7 @@ impl MemoryTracker {
             // Production limits
             BoundedStatsConfig::default()
         };
-        
+
         // Configure ownership history based on mode
         let history_config = if fast_mode {
             HistoryConfig {
@@ -103,6 +110,8 @@ impl MemoryTracker {
             ownership_history: Mutex::new(OwnershipHistoryRecorder::with_config(history_config)),
             stats: Mutex::new(MemoryStats::default()),
             fast_mode: std::sync::atomic::AtomicBool::new(fast_mode),
+            memory_limit: Mutex::new(super::memory_limit::MemoryLimitState::default()),
+            event_reporters: Mutex::new(Vec::new()),
         }
     }
 
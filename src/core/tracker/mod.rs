@@ -6,16 +6,23 @@
 
 pub mod allocation_tracking;
 pub mod config;
+pub mod event_reporter;
 pub mod export_html;
 pub mod export_json;
 pub mod global_functions;
 pub mod memory_analysis;
+pub mod memory_limit;
 pub mod memory_tracker;
+pub mod snapshot;
+pub mod spill;
 pub mod tracking_manager;
 
 // Re-export public types for backward compatibility
 pub use config::{ExportMode, ExportOptions};
+pub use event_reporter::{AllocationEvent, AllocationEventKind, EventReporter, NdjsonEventReporter};
 pub use export_json::build_unified_dashboard_structure;
 pub use global_functions::*;
+pub use memory_limit::{MemoryLimitConfig, MemoryLimitEnforcement};
+pub use snapshot::{diff as diff_snapshots, AllocationIdentity, Snapshot, SnapshotDiff, SnapshotEntry, SnapshotTypeDelta};
 pub use memory_tracker::{get_global_tracker, MemoryTracker};
 pub use tracking_manager::{ComprehensiveTrackingReport, TrackingManager};
@@ -0,0 +1,287 @@
+//! Snapshot + diff API for leak detection between two program points.
+//!
+//! Captures the set of currently-live allocations at a point in time via
+//! [`MemoryTracker::snapshot`], and compares two snapshots with [`diff`] to
+//! classify every allocation as allocated-and-still-live, freed, or
+//! retained, plus aggregate byte/count deltas. This gives callers a
+//! "wrap a suspect region in two `snapshot()` calls" leak-bisection
+//! workflow:
+//!
+//! ```ignore
+//! let before = tracker.snapshot();
+//! suspect_region();
+//! let after = tracker.snapshot();
+//! let report = diff(&before, &after);
+//! ```
+//!
+//! Ptrs can be reused once freed and reallocated, so entries are keyed on
+//! `(ptr, timestamp_alloc)` rather than `ptr` alone -- otherwise a freed
+//! block and a later, unrelated allocation at the same address would be
+//! mis-paired as "the same" allocation.
+
+use super::memory_tracker::MemoryTracker;
+use crate::core::types::{AllocationInfo, TrackingResult};
+use std::collections::HashMap;
+
+/// Identity of a live allocation at the moment a [`Snapshot`] was taken:
+/// the pointer plus its allocation timestamp, since a ptr alone can be
+/// reused across a free/realloc cycle.
+pub type AllocationIdentity = (usize, u64);
+
+/// One live allocation as captured by [`MemoryTracker::snapshot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotEntry {
+    pub ptr: usize,
+    pub size: usize,
+    pub type_name: Option<String>,
+    pub var_name: Option<String>,
+    pub scope_name: Option<String>,
+    pub timestamp_alloc: u64,
+}
+
+impl From<&AllocationInfo> for SnapshotEntry {
+    fn from(allocation: &AllocationInfo) -> Self {
+        Self {
+            ptr: allocation.ptr,
+            size: allocation.size,
+            type_name: allocation.type_name.clone(),
+            var_name: allocation.var_name.clone(),
+            scope_name: allocation.scope_name.clone(),
+            timestamp_alloc: allocation.timestamp_alloc,
+        }
+    }
+}
+
+/// A point-in-time capture of every currently-live allocation, keyed by
+/// [`AllocationIdentity`] to survive ptr reuse.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    entries: HashMap<AllocationIdentity, SnapshotEntry>,
+}
+
+impl Snapshot {
+    fn from_allocations(allocations: &[AllocationInfo]) -> Self {
+        let entries = allocations
+            .iter()
+            .map(|allocation| {
+                (
+                    (allocation.ptr, allocation.timestamp_alloc),
+                    SnapshotEntry::from(allocation),
+                )
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Number of live allocations captured in this snapshot.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Per-type net byte/count growth between two snapshots.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotTypeDelta {
+    pub type_name: String,
+    pub net_bytes: i64,
+    pub net_count: i64,
+}
+
+/// Result of [`diff`]ing two snapshots.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotDiff {
+    /// Present only in the later snapshot.
+    pub allocated: Vec<SnapshotEntry>,
+    /// Present only in the earlier snapshot.
+    pub freed: Vec<SnapshotEntry>,
+    /// Present in both snapshots (same `(ptr, timestamp_alloc)` identity).
+    pub retained: Vec<SnapshotEntry>,
+    /// Net bytes across `allocated` minus `freed`.
+    pub net_bytes: i64,
+    /// Net allocation count across `allocated` minus `freed`.
+    pub net_count: i64,
+    /// Per-type net growth, sorted by `net_bytes` descending.
+    pub by_type: Vec<SnapshotTypeDelta>,
+}
+
+/// Diff two snapshots, classifying every allocation identity into
+/// allocated-and-still-live, freed, or retained buckets.
+pub fn diff(before: &Snapshot, after: &Snapshot) -> SnapshotDiff {
+    let mut allocated = Vec::new();
+    let mut freed = Vec::new();
+    let mut retained = Vec::new();
+
+    for (identity, entry) in &after.entries {
+        if before.entries.contains_key(identity) {
+            retained.push(entry.clone());
+        } else {
+            allocated.push(entry.clone());
+        }
+    }
+    for (identity, entry) in &before.entries {
+        if !after.entries.contains_key(identity) {
+            freed.push(entry.clone());
+        }
+    }
+
+    let net_bytes: i64 = allocated.iter().map(|e| e.size as i64).sum::<i64>()
+        - freed.iter().map(|e| e.size as i64).sum::<i64>();
+    let net_count = allocated.len() as i64 - freed.len() as i64;
+
+    let mut by_type_map: HashMap<String, SnapshotTypeDelta> = HashMap::new();
+    for entry in &allocated {
+        let type_name = entry
+            .type_name
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+        let delta = by_type_map
+            .entry(type_name.clone())
+            .or_insert_with(|| SnapshotTypeDelta {
+                type_name,
+                ..Default::default()
+            });
+        delta.net_bytes += entry.size as i64;
+        delta.net_count += 1;
+    }
+    for entry in &freed {
+        let type_name = entry
+            .type_name
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+        let delta = by_type_map
+            .entry(type_name.clone())
+            .or_insert_with(|| SnapshotTypeDelta {
+                type_name,
+                ..Default::default()
+            });
+        delta.net_bytes -= entry.size as i64;
+        delta.net_count -= 1;
+    }
+
+    let mut by_type: Vec<SnapshotTypeDelta> = by_type_map.into_values().collect();
+    by_type.sort_by(|a, b| b.net_bytes.cmp(&a.net_bytes));
+
+    SnapshotDiff {
+        allocated,
+        freed,
+        retained,
+        net_bytes,
+        net_count,
+        by_type,
+    }
+}
+
+impl MemoryTracker {
+    /// Capture a [`Snapshot`] of every currently-live allocation.
+    pub fn snapshot(&self) -> TrackingResult<Snapshot> {
+        self.get_active_allocations()
+            .map(|allocations| Snapshot::from_allocations(&allocations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ptr: usize, timestamp_alloc: u64, size: usize, type_name: &str) -> SnapshotEntry {
+        SnapshotEntry {
+            ptr,
+            size,
+            type_name: Some(type_name.to_string()),
+            var_name: None,
+            scope_name: None,
+            timestamp_alloc,
+        }
+    }
+
+    fn snapshot_of(entries: Vec<SnapshotEntry>) -> Snapshot {
+        Snapshot {
+            entries: entries
+                .into_iter()
+                .map(|e| ((e.ptr, e.timestamp_alloc), e))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_classifies_allocated_freed_and_retained() {
+        let before = snapshot_of(vec![
+            entry(0x1000, 1, 16, "String"),
+            entry(0x2000, 2, 32, "Vec<u8>"),
+        ]);
+        let after = snapshot_of(vec![
+            entry(0x2000, 2, 32, "Vec<u8>"),
+            entry(0x3000, 3, 64, "String"),
+        ]);
+        let report = diff(&before, &after);
+        assert_eq!(report.allocated.len(), 1);
+        assert_eq!(report.allocated[0].ptr, 0x3000);
+        assert_eq!(report.freed.len(), 1);
+        assert_eq!(report.freed[0].ptr, 0x1000);
+        assert_eq!(report.retained.len(), 1);
+        assert_eq!(report.retained[0].ptr, 0x2000);
+    }
+
+    #[test]
+    fn test_diff_reports_net_bytes_and_count() {
+        let before = snapshot_of(vec![entry(0x1000, 1, 16, "String")]);
+        let after = snapshot_of(vec![
+            entry(0x2000, 2, 32, "Vec<u8>"),
+            entry(0x3000, 3, 64, "String"),
+        ]);
+        let report = diff(&before, &after);
+        assert_eq!(report.net_bytes, 32 + 64 - 16);
+        assert_eq!(report.net_count, 2 - 1);
+    }
+
+    #[test]
+    fn test_reused_ptr_with_different_alloc_timestamp_is_not_retained() {
+        // Same ptr, but a different timestamp_alloc means a freed block was
+        // reallocated, not that the original allocation is still live.
+        let before = snapshot_of(vec![entry(0x1000, 1, 16, "String")]);
+        let after = snapshot_of(vec![entry(0x1000, 99, 16, "String")]);
+        let report = diff(&before, &after);
+        assert_eq!(report.retained.len(), 0);
+        assert_eq!(report.freed.len(), 1);
+        assert_eq!(report.allocated.len(), 1);
+    }
+
+    #[test]
+    fn test_diff_groups_net_growth_by_type() {
+        let before = snapshot_of(vec![]);
+        let after = snapshot_of(vec![
+            entry(0x1000, 1, 16, "String"),
+            entry(0x2000, 2, 32, "String"),
+            entry(0x3000, 3, 8, "Vec<u8>"),
+        ]);
+        let report = diff(&before, &after);
+        let string_delta = report
+            .by_type
+            .iter()
+            .find(|d| d.type_name == "String")
+            .unwrap();
+        assert_eq!(string_delta.net_bytes, 48);
+        assert_eq!(string_delta.net_count, 2);
+    }
+
+    #[test]
+    fn test_empty_snapshots_diff_to_nothing() {
+        let report = diff(&Snapshot::default(), &Snapshot::default());
+        assert!(report.allocated.is_empty());
+        assert!(report.freed.is_empty());
+        assert!(report.retained.is_empty());
+        assert_eq!(report.net_bytes, 0);
+    }
+
+    #[test]
+    fn test_tracker_snapshot_captures_live_allocations() {
+        let tracker = MemoryTracker::new();
+        tracker.track_allocation(0x9000, 128).unwrap();
+        let snapshot = tracker.snapshot().unwrap();
+        assert_eq!(snapshot.len(), 1);
+    }
+}
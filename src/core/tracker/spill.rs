@@ -0,0 +1,224 @@
+//! Spill-to-disk subsystem for bounding peak memory during large JSON exports.
+//!
+//! The enhanced export batch path normally collects every processed
+//! `serde_json::Value` into one `Vec` before writing, which can blow the heap for
+//! very large captures. [`SpillManager`] lets the batch path serialize the current
+//! in-memory buffer to a numbered segment file once it grows past a configured
+//! limit, freeing the buffer so memory stays bounded regardless of allocation count.
+
+use crate::core::types::{TrackingError, TrackingResult};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Serializes processed allocation batches to numbered temp files once the running
+/// in-memory size crosses a budget, and streams them back in original order at write
+/// time.
+pub struct SpillManager {
+    spill_dir: PathBuf,
+    reserved_disk_ratio: f64,
+    segment_paths: Vec<PathBuf>,
+    total_entries: usize,
+}
+
+impl SpillManager {
+    /// Create a manager rooted at `spill_dir`, creating the directory if needed.
+    pub fn new(spill_dir: PathBuf, reserved_disk_ratio: f64) -> TrackingResult<Self> {
+        std::fs::create_dir_all(&spill_dir).map_err(|e| {
+            TrackingError::IoError(format!(
+                "Failed to create spill directory {}: {e}",
+                spill_dir.display()
+            ))
+        })?;
+        Ok(Self {
+            spill_dir,
+            reserved_disk_ratio,
+            segment_paths: Vec::new(),
+            total_entries: 0,
+        })
+    }
+
+    /// Whether any batch has been spilled to disk yet.
+    pub fn has_segments(&self) -> bool {
+        !self.segment_paths.is_empty()
+    }
+
+    /// Total number of entries spilled across all segments so far.
+    pub fn total_entries(&self) -> usize {
+        self.total_entries
+    }
+
+    /// Serialize `batch` to the next numbered segment file, after checking that
+    /// doing so would not drop the free-disk ratio below `reserved_disk_ratio`.
+    pub fn spill(&mut self, batch: &[serde_json::Value]) -> TrackingResult<()> {
+        self.check_disk_budget()?;
+
+        let segment_path = self
+            .spill_dir
+            .join(format!("segment_{:06}.json", self.segment_paths.len()));
+        let file = File::create(&segment_path).map_err(|e| {
+            TrackingError::IoError(format!(
+                "Failed to create spill segment {}: {e}",
+                segment_path.display()
+            ))
+        })?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, batch).map_err(|e| {
+            TrackingError::SerializationError(format!("Failed to serialize spill segment: {e}"))
+        })?;
+        writer
+            .flush()
+            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+
+        self.segment_paths.push(segment_path);
+        self.total_entries += batch.len();
+        Ok(())
+    }
+
+    fn check_disk_budget(&self) -> TrackingResult<()> {
+        if let Some((available, total)) = available_disk_space(&self.spill_dir) {
+            if total > 0 {
+                let free_ratio = available as f64 / total as f64;
+                if free_ratio < self.reserved_disk_ratio {
+                    return Err(TrackingError::IoError(format!(
+                        "refusing to spill export batch: free disk ratio {free_ratio:.3} \
+                         would drop below the reserved ratio {:.3}",
+                        self.reserved_disk_ratio
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stream every spilled segment's entries into `writer`, in the order they were
+    /// spilled, as comma-separated JSON array elements (no surrounding brackets).
+    /// Writes a leading comma before the first element of each segment after the
+    /// first, so callers can splice this between other array elements.
+    pub fn write_segments_into<W: Write>(&self, writer: &mut W) -> TrackingResult<()> {
+        let mut wrote_any = false;
+        for path in &self.segment_paths {
+            let file = File::open(path).map_err(|e| {
+                TrackingError::IoError(format!(
+                    "Failed to open spill segment {}: {e}",
+                    path.display()
+                ))
+            })?;
+            let batch: Vec<serde_json::Value> = serde_json::from_reader(BufReader::new(file))
+                .map_err(|e| {
+                    TrackingError::SerializationError(format!(
+                        "Failed to read spill segment {}: {e}",
+                        path.display()
+                    ))
+                })?;
+            for entry in &batch {
+                if wrote_any {
+                    writer
+                        .write_all(b",")
+                        .map_err(|e| TrackingError::IoError(e.to_string()))?;
+                }
+                serde_json::to_writer(&mut *writer, entry)
+                    .map_err(|e| TrackingError::SerializationError(e.to_string()))?;
+                wrote_any = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete every spilled temp file (and the spill directory, if now empty).
+    pub fn cleanup(&mut self) -> TrackingResult<()> {
+        for path in self.segment_paths.drain(..) {
+            let _ = std::fs::remove_file(&path);
+        }
+        let _ = std::fs::remove_dir(&self.spill_dir);
+        Ok(())
+    }
+}
+
+impl Drop for SpillManager {
+    fn drop(&mut self) {
+        // Best-effort cleanup on every exit path, including errors raised mid-export.
+        let _ = self.cleanup();
+    }
+}
+
+/// Best-effort `(available_bytes, total_bytes)` for the filesystem backing `path`.
+/// Returns `None` when the platform call is unavailable, so callers treat it as
+/// "no budget information" rather than a hard error.
+fn available_disk_space(path: &Path) -> Option<(u64, u64)> {
+    #[cfg(unix)]
+    {
+        use std::ffi::CString;
+        use std::mem::MaybeUninit;
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        // SAFETY: `c_path` is a valid NUL-terminated string and `stat` is a
+        // correctly-sized, zero-initialized buffer for the platform's statvfs layout.
+        unsafe {
+            let mut stat: MaybeUninit<libc::statvfs> = MaybeUninit::zeroed();
+            if libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) != 0 {
+                return None;
+            }
+            let stat = stat.assume_init();
+            let available = stat.f_bavail as u64 * stat.f_frsize as u64;
+            let total = stat.f_blocks as u64 * stat.f_frsize as u64;
+            Some((available, total))
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_spill_and_write_segments_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager =
+            SpillManager::new(temp_dir.path().join("spill"), 0.0).expect("spill manager");
+
+        manager
+            .spill(&[serde_json::json!({"address": "0x1"})])
+            .unwrap();
+        manager
+            .spill(&[
+                serde_json::json!({"address": "0x2"}),
+                serde_json::json!({"address": "0x3"}),
+            ])
+            .unwrap();
+        assert!(manager.has_segments());
+
+        let mut buf = Vec::new();
+        manager.write_segments_into(&mut buf).unwrap();
+        let joined = format!("[{}]", String::from_utf8(buf).unwrap());
+        let values: Vec<serde_json::Value> = serde_json::from_str(&joined).unwrap();
+        assert_eq!(
+            values,
+            vec![
+                serde_json::json!({"address": "0x1"}),
+                serde_json::json!({"address": "0x2"}),
+                serde_json::json!({"address": "0x3"}),
+            ]
+        );
+
+        manager.cleanup().unwrap();
+        assert!(!temp_dir.path().join("spill").exists());
+    }
+
+    #[test]
+    fn test_spill_rejects_when_reserved_ratio_unmet() {
+        let temp_dir = TempDir::new().unwrap();
+        // A reserved ratio of 1.0 demands 100% free disk, which is never true.
+        let mut manager =
+            SpillManager::new(temp_dir.path().join("spill"), 1.0).expect("spill manager");
+        let result = manager.spill(&[serde_json::json!({"address": "0x1"})]);
+        assert!(result.is_err());
+    }
+}
@@ -71,6 +71,8 @@ pub enum TrackingError {
     InvalidOperation(String),
     /// Validation error
     ValidationError(String),
+    /// A configured memory limit would be exceeded by this allocation
+    MemoryLimitExceeded(String),
 }
 
 impl Clone for TrackingError {
@@ -106,6 +108,7 @@ impl Clone for TrackingError {
             TrackingError::NotImplemented(s) => TrackingError::NotImplemented(s.clone()),
             TrackingError::ValidationError(s) => TrackingError::ValidationError(s.clone()),
             TrackingError::InvalidOperation(s) => TrackingError::InvalidOperation(s.clone()),
+            TrackingError::MemoryLimitExceeded(s) => TrackingError::MemoryLimitExceeded(s.clone()),
         }
     }
 }
@@ -143,6 +146,7 @@ impl std::fmt::Display for TrackingError {
             TrackingError::NotImplemented(msg) => write!(f, "Not implemented: {msg}"),
             TrackingError::ValidationError(msg) => write!(f, "Validation error: {msg}"),
             TrackingError::InvalidOperation(msg) => write!(f, "Invalid operation: {msg}"),
+            TrackingError::MemoryLimitExceeded(msg) => write!(f, "Memory limit exceeded: {msg}"),
         }
     }
 }
@@ -0,0 +1,225 @@
+//! Pluggable export backends.
+//!
+//! [`ExportSink`](crate::export::sink::ExportSink) abstracts *where* export
+//! bytes go (a file, an in-memory buffer, object storage) but every caller
+//! still assembles one big `serde_json::Value` per output file first. This
+//! module adds an [`ExportBackend`] layer one step higher: named sections
+//! (`"allocation_details"`, `"type_usage"`, ...) are handed to the backend one
+//! at a time, and the backend decides how to realize them -- as sibling JSON
+//! files via [`JsonFileBackend`] (today's behavior), streamed directly onto a
+//! single writer via [`WriterBackend`] without ever holding the whole export
+//! in memory as one `Value` tree, or, with the `columnar-export` feature, as
+//! Arrow/Parquet record batches for the two genuinely tabular sections.
+
+#[cfg(feature = "columnar-export")]
+use crate::core::types::TrackingError;
+use crate::core::types::TrackingResult;
+use crate::export::sink::ExportSink;
+use std::io::Write;
+
+/// A destination that receives an export as a sequence of named sections
+/// rather than one monolithic document.
+pub trait ExportBackend {
+    /// Emit `value` under `name` (e.g. `"allocation_details"`).
+    fn write_section(&mut self, name: &str, value: &serde_json::Value) -> TrackingResult<()>;
+
+    /// Called once after every section has been written. The default is a
+    /// no-op; backends that hold buffered state (streaming, columnar) use
+    /// this to flush it.
+    fn finalize(&mut self) -> TrackingResult<()> {
+        Ok(())
+    }
+}
+
+/// Writes each section as its own `{name}.json` file through an
+/// [`ExportSink`] -- the same one-file-per-concern layout the crate has
+/// always produced.
+pub struct JsonFileBackend<'a> {
+    sink: &'a dyn ExportSink,
+    pretty: bool,
+}
+
+impl<'a> JsonFileBackend<'a> {
+    /// Create a backend that writes compact JSON through `sink`.
+    pub fn new(sink: &'a dyn ExportSink) -> Self {
+        Self {
+            sink,
+            pretty: false,
+        }
+    }
+
+    /// Write human-readable (pretty-printed) JSON instead of compact.
+    pub fn pretty(mut self, enabled: bool) -> Self {
+        self.pretty = enabled;
+        self
+    }
+}
+
+impl ExportBackend for JsonFileBackend<'_> {
+    fn write_section(&mut self, name: &str, value: &serde_json::Value) -> TrackingResult<()> {
+        let writer = self.sink.create_writer(&format!("{name}.json"))?;
+        let mut writer = std::io::BufWriter::new(writer);
+        if self.pretty {
+            serde_json::to_writer_pretty(&mut writer, value)?;
+        } else {
+            serde_json::to_writer(&mut writer, value)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> TrackingResult<()> {
+        self.sink.finalize()
+    }
+}
+
+/// Streams every section directly onto a single `W: Write` as one
+/// newline-delimited `{"section": "<name>", "data": <value>}` record per
+/// section, via `serde_json::to_writer` -- no intermediate `String` and no
+/// second `serde_json::Value` combining every section into one document.
+/// Suited to piping a live export over a socket or into a process that reads
+/// NDJSON incrementally.
+pub struct WriterBackend<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> WriterBackend<W> {
+    /// Create a backend that streams sections onto `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Consume the backend and return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> ExportBackend for WriterBackend<W> {
+    fn write_section(&mut self, name: &str, value: &serde_json::Value) -> TrackingResult<()> {
+        serde_json::to_writer(
+            &mut self.writer,
+            &serde_json::json!({
+                "section": name,
+                "data": value,
+            }),
+        )?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> TrackingResult<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Accepts one already-decoded table of rows per call. Implemented by callers
+/// against whatever Arrow/Parquet writer they already depend on, so this
+/// crate doesn't need an Arrow dependency itself to support the
+/// `columnar-export` feature -- mirrors [`ObjectUploader`](crate::export::sink::ObjectUploader)
+/// for the same reason.
+#[cfg(feature = "columnar-export")]
+pub trait ColumnarSink: Send {
+    /// Write one record batch for `table_name` (e.g. `"allocation_details"`).
+    /// Every row shares the same set of keys within a single `write_section` call.
+    fn write_batch(
+        &mut self,
+        table_name: &str,
+        rows: &[serde_json::Map<String, serde_json::Value>],
+    ) -> TrackingResult<()>;
+}
+
+/// Routes the two genuinely tabular sections (`allocation_details`,
+/// `type_usage`) to a caller-supplied [`ColumnarSink`] as row batches, and
+/// falls back to `{name}.json` via an inner [`JsonFileBackend`] for every
+/// other section so nothing is silently dropped.
+#[cfg(feature = "columnar-export")]
+pub struct ColumnarBackend<'a, C: ColumnarSink> {
+    columnar: C,
+    fallback: JsonFileBackend<'a>,
+}
+
+#[cfg(feature = "columnar-export")]
+const TABULAR_SECTIONS: &[&str] = &["allocation_details", "type_usage"];
+
+#[cfg(feature = "columnar-export")]
+impl<'a, C: ColumnarSink> ColumnarBackend<'a, C> {
+    /// Create a backend that sends `allocation_details`/`type_usage` to
+    /// `columnar` and everything else through `fallback`.
+    pub fn new(columnar: C, fallback: JsonFileBackend<'a>) -> Self {
+        Self { columnar, fallback }
+    }
+}
+
+#[cfg(feature = "columnar-export")]
+impl<C: ColumnarSink> ExportBackend for ColumnarBackend<'_, C> {
+    fn write_section(&mut self, name: &str, value: &serde_json::Value) -> TrackingResult<()> {
+        if TABULAR_SECTIONS.contains(&name) {
+            let rows = value.as_array().ok_or_else(|| {
+                TrackingError::ExportError(format!(
+                    "columnar export expected '{name}' to be a JSON array of row objects"
+                ))
+            })?;
+            let rows: Vec<serde_json::Map<String, serde_json::Value>> = rows
+                .iter()
+                .filter_map(|row| row.as_object().cloned())
+                .collect();
+            self.columnar.write_batch(name, &rows)
+        } else {
+            self.fallback.write_section(name, value)
+        }
+    }
+
+    fn finalize(&mut self) -> TrackingResult<()> {
+        self.fallback.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::sink::LocalFsSink;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_json_file_backend_writes_one_file_per_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let sink = LocalFsSink::new(temp_dir.path().to_path_buf());
+        let mut backend = JsonFileBackend::new(&sink);
+
+        backend
+            .write_section("allocation_details", &serde_json::json!([{"ptr": "0x1"}]))
+            .unwrap();
+        backend
+            .write_section("type_usage", &serde_json::json!([{"type_name": "String"}]))
+            .unwrap();
+
+        let allocation_details =
+            std::fs::read_to_string(temp_dir.path().join("allocation_details.json")).unwrap();
+        assert_eq!(allocation_details, r#"[{"ptr":"0x1"}]"#);
+        assert!(temp_dir.path().join("type_usage.json").exists());
+    }
+
+    #[test]
+    fn test_writer_backend_streams_one_ndjson_record_per_section() {
+        let mut buffer = Vec::new();
+        {
+            let mut backend = WriterBackend::new(&mut buffer);
+            backend
+                .write_section("metadata", &serde_json::json!({"total": 2}))
+                .unwrap();
+            backend
+                .write_section("allocation_details", &serde_json::json!([1, 2]))
+                .unwrap();
+            backend.finalize().unwrap();
+        }
+
+        let text = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["section"], "metadata");
+        assert_eq!(first["data"]["total"], 2);
+    }
+}
@@ -0,0 +1,1014 @@
+//! Export benchmark harness.
+//!
+//! Generates synthetic [`AllocationInfo`] workloads, exercises
+//! [`MemoryTracker::export_to_json_with_options`] across every [`OptimizationLevel`],
+//! and records throughput into a structured, diffable results file. This turns the
+//! ad-hoc `tracing::debug!` timing in `process_allocation_batch_enhanced` into a
+//! reproducible benchmark so export performance regressions can be caught across
+//! crate versions.
+
+use crate::core::tracker::export_json::ExportJsonOptions;
+use crate::core::tracker::memory_tracker::MemoryTracker;
+use crate::core::types::TrackingResult;
+use crate::export::optimized_json_export::OptimizationLevel;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Instant;
+
+/// Type-mix for a synthetic workload, as a relative weight for each type name.
+#[derive(Debug, Clone)]
+pub struct TypeMix {
+    /// Type names to draw from, in round-robin proportion to their weight
+    pub type_names: Vec<(&'static str, usize)>,
+}
+
+impl Default for TypeMix {
+    fn default() -> Self {
+        Self {
+            type_names: vec![
+                ("String", 4),
+                ("Vec<u8>", 3),
+                ("HashMap<String, i32>", 2),
+                ("Box<dyn Trait>", 1),
+            ],
+        }
+    }
+}
+
+/// Configuration for a synthetic benchmark workload.
+#[derive(Debug, Clone)]
+pub struct WorkloadConfig {
+    /// Number of synthetic allocations to generate
+    pub allocation_count: usize,
+    /// Size, in bytes, given to each synthetic allocation
+    pub allocation_size: usize,
+    /// Relative mix of type names assigned to allocations
+    pub type_mix: TypeMix,
+}
+
+impl Default for WorkloadConfig {
+    fn default() -> Self {
+        Self {
+            allocation_count: 10_000,
+            allocation_size: 128,
+            type_mix: TypeMix::default(),
+        }
+    }
+}
+
+/// Populate a fresh [`MemoryTracker`] with a synthetic workload matching `config`.
+pub fn generate_workload(config: &WorkloadConfig) -> TrackingResult<MemoryTracker> {
+    let tracker = MemoryTracker::new();
+    let total_weight: usize = config.type_mix.type_names.iter().map(|(_, w)| w).sum();
+    let total_weight = total_weight.max(1);
+
+    let mut type_cursor = 0usize;
+    for i in 0..config.allocation_count {
+        let mut pick = i % total_weight;
+        let mut type_name = config.type_mix.type_names[0].0;
+        for (name, weight) in &config.type_mix.type_names {
+            if pick < *weight {
+                type_name = name;
+                break;
+            }
+            pick -= weight;
+        }
+        type_cursor += 1;
+
+        let ptr = 0x1000 + i * config.allocation_size.max(8);
+        tracker.track_allocation_with_context(
+            ptr,
+            config.allocation_size,
+            format!("bench_var_{type_cursor}"),
+            type_name.to_string(),
+        )?;
+    }
+
+    Ok(tracker)
+}
+
+/// Throughput and resource measurements for a single `OptimizationLevel` run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelBenchmarkResult {
+    /// The optimization level this result was measured under
+    pub level: String,
+    /// Allocations processed per second
+    pub allocations_per_sec: f64,
+    /// Total wall-clock time for the export, in milliseconds
+    pub wall_time_ms: f64,
+    /// Tracker-reported peak memory usage, in bytes
+    pub peak_memory_bytes: usize,
+    /// Total size of the written output files, in bytes
+    pub output_bytes: u64,
+}
+
+/// A complete benchmark run across every `OptimizationLevel`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResults {
+    /// Number of allocations in the workload that produced this run
+    pub allocation_count: usize,
+    /// Per-level results, in the order the levels were benchmarked
+    pub levels: Vec<LevelBenchmarkResult>,
+}
+
+/// Run the full `OptimizationLevel` sweep against a synthetic workload, exporting
+/// into a fresh subdirectory of `output_dir` per level.
+pub fn run_benchmark<P: AsRef<Path>>(
+    config: &WorkloadConfig,
+    output_dir: P,
+) -> TrackingResult<BenchmarkResults> {
+    let output_dir = output_dir.as_ref();
+    let levels = [
+        OptimizationLevel::Low,
+        OptimizationLevel::Medium,
+        OptimizationLevel::High,
+        OptimizationLevel::Maximum,
+    ];
+
+    let mut results = Vec::with_capacity(levels.len());
+    for level in levels {
+        let tracker = generate_workload(config)?;
+        let options = ExportJsonOptions::with_optimization_level(level);
+
+        let level_dir = output_dir.join(format!("{level:?}").to_lowercase());
+        // `export_to_json_with_options` always writes under a `MemoryAnalysis/`
+        // directory relative to `level_dir`; resolve the same way it does so the
+        // byte count below matches what actually landed on disk.
+        let actual_output_dir = tracker.ensure_memory_analysis_path(&level_dir);
+
+        let start = Instant::now();
+        tracker.export_to_json_with_options(&level_dir, options)?;
+        let wall_time = start.elapsed();
+
+        let stats = tracker.get_stats()?;
+        let output_bytes = directory_size(&actual_output_dir).unwrap_or(0);
+
+        results.push(LevelBenchmarkResult {
+            level: format!("{level:?}"),
+            allocations_per_sec: config.allocation_count as f64 / wall_time.as_secs_f64().max(1e-9),
+            wall_time_ms: wall_time.as_secs_f64() * 1000.0,
+            peak_memory_bytes: stats.peak_memory,
+            output_bytes,
+        });
+    }
+
+    Ok(BenchmarkResults {
+        allocation_count: config.allocation_count,
+        levels: results,
+    })
+}
+
+/// Sum the byte size of every file directly inside `dir`.
+fn directory_size(dir: &Path) -> std::io::Result<u64> {
+    let mut total = 0u64;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+/// Save benchmark results to `path` as pretty-printed JSON.
+pub fn save_results<P: AsRef<Path>>(results: &BenchmarkResults, path: P) -> TrackingResult<()> {
+    let json = serde_json::to_string_pretty(results).map_err(|e| {
+        crate::core::types::TrackingError::SerializationError(format!(
+            "Failed to serialize benchmark results: {e}"
+        ))
+    })?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+/// Load a previously saved results file.
+pub fn load_results<P: AsRef<Path>>(path: P) -> TrackingResult<BenchmarkResults> {
+    let contents = std::fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(|e| {
+        crate::core::types::TrackingError::SerializationError(format!(
+            "Failed to parse benchmark results: {e}"
+        ))
+    })
+}
+
+/// Per-metric regression/improvement for one `OptimizationLevel`, as a percentage
+/// change from `baseline` to `current` (positive means the metric increased).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelComparison {
+    /// The optimization level this comparison covers
+    pub level: String,
+    /// Percentage change in allocations/sec (positive is faster)
+    pub allocations_per_sec_change_pct: f64,
+    /// Percentage change in wall time (positive is slower, i.e. a regression)
+    pub wall_time_change_pct: f64,
+    /// Percentage change in peak memory (positive is more memory, i.e. a regression)
+    pub peak_memory_change_pct: f64,
+    /// Percentage change in output size
+    pub output_bytes_change_pct: f64,
+    /// Whether any metric above regressed beyond the configured threshold
+    pub regressed: bool,
+}
+
+fn pct_change(baseline: f64, current: f64) -> f64 {
+    if baseline == 0.0 {
+        0.0
+    } else {
+        (current - baseline) / baseline * 100.0
+    }
+}
+
+/// Compare `current` results against a `baseline`, flagging any level whose wall
+/// time, peak memory, or output size worsens by more than `threshold_pct` percent.
+pub fn compare_to_baseline(
+    baseline: &BenchmarkResults,
+    current: &BenchmarkResults,
+    threshold_pct: f64,
+) -> Vec<LevelComparison> {
+    current
+        .levels
+        .iter()
+        .filter_map(|current_level| {
+            let baseline_level = baseline
+                .levels
+                .iter()
+                .find(|level| level.level == current_level.level)?;
+
+            let allocations_per_sec_change_pct = pct_change(
+                baseline_level.allocations_per_sec,
+                current_level.allocations_per_sec,
+            );
+            let wall_time_change_pct =
+                pct_change(baseline_level.wall_time_ms, current_level.wall_time_ms);
+            let peak_memory_change_pct = pct_change(
+                baseline_level.peak_memory_bytes as f64,
+                current_level.peak_memory_bytes as f64,
+            );
+            let output_bytes_change_pct = pct_change(
+                baseline_level.output_bytes as f64,
+                current_level.output_bytes as f64,
+            );
+
+            let regressed = wall_time_change_pct > threshold_pct
+                || peak_memory_change_pct > threshold_pct
+                || output_bytes_change_pct > threshold_pct;
+
+            Some(LevelComparison {
+                level: current_level.level.clone(),
+                allocations_per_sec_change_pct,
+                wall_time_change_pct,
+                peak_memory_change_pct,
+                output_bytes_change_pct,
+                regressed,
+            })
+        })
+        .collect()
+}
+
+/// Parse a human-readable byte size like `"4KiB"`, `"512B"`, `"10MB"`, or
+/// `"1MiB"` into a byte count. Accepts a bare integer (bytes), a binary
+/// suffix (`KiB`/`MiB`/`GiB`, base 1024), or a decimal suffix
+/// (`KB`/`MB`/`GB`, base 1000), all case-insensitive.
+pub fn parse_byte_size(spec: &str) -> Option<usize> {
+    let spec = spec.trim();
+    let upper = spec.to_uppercase();
+
+    let (number_part, multiplier) = if let Some(n) = upper.strip_suffix("GIB") {
+        (n, 1024 * 1024 * 1024)
+    } else if let Some(n) = upper.strip_suffix("MIB") {
+        (n, 1024 * 1024)
+    } else if let Some(n) = upper.strip_suffix("KIB") {
+        (n, 1024)
+    } else if let Some(n) = upper.strip_suffix("GB") {
+        (n, 1_000_000_000)
+    } else if let Some(n) = upper.strip_suffix("MB") {
+        (n, 1_000_000)
+    } else if let Some(n) = upper.strip_suffix("KB") {
+        (n, 1_000)
+    } else if let Some(n) = upper.strip_suffix('B') {
+        (n, 1)
+    } else {
+        (upper.as_str(), 1)
+    };
+
+    number_part
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .map(|n| n * multiplier)
+}
+
+/// How allocation sizes are drawn for each operation in a [`Workload`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SizeDistribution {
+    /// Every allocation is exactly this many bytes, e.g. `"4KiB"`
+    Fixed { bytes: String },
+    /// Allocation size is spread evenly across `[min, max]` over the run,
+    /// e.g. `min: "4KiB", max: "1MiB"`
+    Uniform { min: String, max: String },
+}
+
+impl SizeDistribution {
+    /// Resolve the size for operation `index` out of `total` operations.
+    fn sample(&self, index: usize, total: usize) -> usize {
+        match self {
+            SizeDistribution::Fixed { bytes } => parse_byte_size(bytes).unwrap_or(128),
+            SizeDistribution::Uniform { min, max } => {
+                let min = parse_byte_size(min).unwrap_or(128);
+                let max = parse_byte_size(max).unwrap_or(min);
+                if max <= min || total <= 1 {
+                    return min;
+                }
+                min + (max - min) * index / (total - 1)
+            }
+        }
+    }
+}
+
+/// A JSON-describable benchmark workload, mirroring the way tools like
+/// `ekvsb` describe a key-value workload: operation counts, an allocation
+/// size distribution, and a type-name mix, replayed through the export
+/// pipeline with per-operation latency recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workload {
+    /// Human-readable name for this workload, carried into saved results
+    pub name: String,
+    /// Number of export operations to replay
+    pub operation_count: usize,
+    /// Allocations generated per operation before it is exported
+    pub batch_size: usize,
+    /// Allocation size distribution for this workload
+    pub size_distribution: SizeDistribution,
+    /// Type names to draw from, in round-robin proportion to their weight
+    pub type_mix: Vec<(String, usize)>,
+}
+
+impl Default for Workload {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            operation_count: 10,
+            batch_size: 1000,
+            size_distribution: SizeDistribution::Fixed {
+                bytes: "128B".to_string(),
+            },
+            type_mix: vec![
+                ("String".to_string(), 4),
+                ("Vec<u8>".to_string(), 3),
+                ("HashMap<String, i32>".to_string(), 2),
+                ("Box<dyn Trait>".to_string(), 1),
+            ],
+        }
+    }
+}
+
+/// Latency of a single replayed operation (one batch generated and exported).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationLatency {
+    /// Index of this operation within the workload run
+    pub operation_index: usize,
+    /// Number of allocations processed in this operation
+    pub allocations: usize,
+    /// Wall-clock time for `process_allocation_batch_enhanced` + the export
+    /// write, in milliseconds
+    pub latency_ms: f64,
+}
+
+/// Percentile summary of a workload run's per-operation latencies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencySummary {
+    /// Number of operations summarized
+    pub count: usize,
+    /// Sum of every operation's latency, in milliseconds
+    pub total_ms: f64,
+    /// Mean latency, in milliseconds
+    pub mean_ms: f64,
+    /// 50th percentile latency, in milliseconds
+    pub p50_ms: f64,
+    /// 90th percentile latency, in milliseconds
+    pub p90_ms: f64,
+    /// 99th percentile latency, in milliseconds
+    pub p99_ms: f64,
+    /// Maximum observed latency, in milliseconds
+    pub max_ms: f64,
+}
+
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+/// Summarize a workload run's per-operation latencies into count, total/mean,
+/// and p50/p90/p99/max percentiles.
+pub fn summarize(latencies: &[OperationLatency]) -> LatencySummary {
+    let mut sorted: Vec<f64> = latencies.iter().map(|l| l.latency_ms).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let count = sorted.len();
+    let total_ms: f64 = sorted.iter().sum();
+    let mean_ms = if count > 0 {
+        total_ms / count as f64
+    } else {
+        0.0
+    };
+
+    LatencySummary {
+        count,
+        total_ms,
+        mean_ms,
+        p50_ms: percentile(&sorted, 50.0),
+        p90_ms: percentile(&sorted, 90.0),
+        p99_ms: percentile(&sorted, 99.0),
+        max_ms: sorted.last().copied().unwrap_or(0.0),
+    }
+}
+
+/// Replay `workload` against the export pipeline: for each operation, build a
+/// fresh batch of allocations sized per `size_distribution` and mixed per
+/// `type_mix`, export it with `options`, and record the operation's
+/// wall-clock latency. Gives a reproducible way to compare `OptimizationLevel`
+/// presets and `ExportJsonOptions` settings instead of eyeballing ad-hoc runs.
+pub fn run<P: AsRef<Path>>(
+    workload: &Workload,
+    options: &ExportJsonOptions,
+    output_dir: P,
+) -> TrackingResult<Vec<OperationLatency>> {
+    let output_dir = output_dir.as_ref();
+    let total_weight: usize = workload
+        .type_mix
+        .iter()
+        .map(|(_, w)| w)
+        .sum::<usize>()
+        .max(1);
+
+    let mut latencies = Vec::with_capacity(workload.operation_count);
+    for op_index in 0..workload.operation_count {
+        let size = workload
+            .size_distribution
+            .sample(op_index, workload.operation_count);
+        let tracker = MemoryTracker::new();
+
+        for i in 0..workload.batch_size {
+            let mut pick = i % total_weight;
+            let mut type_name = workload.type_mix[0].0.as_str();
+            for (name, weight) in &workload.type_mix {
+                if pick < *weight {
+                    type_name = name;
+                    break;
+                }
+                pick -= weight;
+            }
+
+            let ptr = 0x1000 + i * size.max(8);
+            tracker.track_allocation_with_context(
+                ptr,
+                size,
+                format!("bench_op{op_index}_var{i}"),
+                type_name.to_string(),
+            )?;
+        }
+
+        let op_dir = output_dir.join(format!("op_{op_index}"));
+        let start = Instant::now();
+        tracker.export_to_json_with_options(&op_dir, options.clone())?;
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        latencies.push(OperationLatency {
+            operation_index: op_index,
+            allocations: workload.batch_size,
+            latency_ms,
+        });
+    }
+
+    Ok(latencies)
+}
+
+/// Render an SVG line chart of per-operation latency over the run, plus the
+/// `summary` percentiles as a text overlay.
+pub fn plot_latency_svg<P: AsRef<Path>>(
+    latencies: &[OperationLatency],
+    path: P,
+) -> TrackingResult<()> {
+    const WIDTH: f64 = 800.0;
+    const HEIGHT: f64 = 200.0;
+    const MARGIN: f64 = 20.0;
+
+    let max_latency = latencies
+        .iter()
+        .map(|l| l.latency_ms)
+        .fold(0.0_f64, f64::max)
+        .max(1e-9);
+
+    let mut points = String::new();
+    for (i, l) in latencies.iter().enumerate() {
+        let x = if latencies.len() > 1 {
+            MARGIN + (WIDTH - 2.0 * MARGIN) * i as f64 / (latencies.len() - 1) as f64
+        } else {
+            MARGIN
+        };
+        let y = HEIGHT - MARGIN - (HEIGHT - 2.0 * MARGIN) * (l.latency_ms / max_latency);
+        points.push_str(&format!("{x:.2},{y:.2} "));
+    }
+
+    let summary = summarize(latencies);
+    let svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}">
+  <rect width="100%" height="100%" fill="white"/>
+  <polyline points="{points}" fill="none" stroke="steelblue" stroke-width="1.5"/>
+  <text x="{MARGIN}" y="14" font-size="11" fill="black">p50={:.2}ms p90={:.2}ms p99={:.2}ms max={:.2}ms</text>
+</svg>
+"#,
+        summary.p50_ms, summary.p90_ms, summary.p99_ms, summary.max_ms
+    );
+
+    std::fs::write(path, svg)?;
+    Ok(())
+}
+
+/// Render an SVG bar-chart histogram of total bytes allocated per type name.
+pub fn plot_type_histogram_svg<P: AsRef<Path>>(
+    type_bytes: &std::collections::HashMap<String, u64>,
+    path: P,
+) -> TrackingResult<()> {
+    const WIDTH: f64 = 800.0;
+    const HEIGHT: f64 = 200.0;
+    const MARGIN: f64 = 20.0;
+
+    let mut entries: Vec<(&String, &u64)> = type_bytes.iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(a.1));
+
+    let max_bytes = entries.iter().map(|(_, b)| **b).max().unwrap_or(1).max(1);
+    let bar_width = if entries.is_empty() {
+        0.0
+    } else {
+        (WIDTH - 2.0 * MARGIN) / entries.len() as f64
+    };
+
+    let mut bars = String::new();
+    for (i, (name, bytes)) in entries.iter().enumerate() {
+        let bar_height = (HEIGHT - 2.0 * MARGIN) * (**bytes as f64 / max_bytes as f64);
+        let x = MARGIN + i as f64 * bar_width;
+        let y = HEIGHT - MARGIN - bar_height;
+        bars.push_str(&format!(
+            r#"<rect x="{x:.2}" y="{y:.2}" width="{:.2}" height="{bar_height:.2}" fill="steelblue"><title>{name}: {bytes} bytes</title></rect>"#,
+            bar_width * 0.9
+        ));
+    }
+
+    let svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}">
+  <rect width="100%" height="100%" fill="white"/>
+  {bars}
+</svg>
+"#
+    );
+
+    std::fs::write(path, svg)?;
+    Ok(())
+}
+
+/// Minimum number of per-iteration samples required to report a standard
+/// deviation; below this, variance is too noisy to be meaningful and
+/// [`BenchmarkSummary::stddev_ns`] is `None` instead.
+pub const MIN_SAMPLES_FOR_STDDEV: usize = 2;
+
+/// Statistical summary of one [`Benchmark::run`] invocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkSummary {
+    /// Name given to the benchmark
+    pub name: String,
+    /// Number of iterations timed
+    pub iterations: usize,
+    /// Fastest iteration, in nanoseconds
+    pub min_ns: u64,
+    /// Slowest iteration, in nanoseconds
+    pub max_ns: u64,
+    /// Arithmetic mean across all iterations, in nanoseconds
+    pub mean_ns: f64,
+    /// Median across all iterations, in nanoseconds
+    pub median_ns: f64,
+    /// Sample standard deviation, in nanoseconds; `None` when fewer than
+    /// [`MIN_SAMPLES_FOR_STDDEV`] iterations were run
+    pub stddev_ns: Option<f64>,
+    /// Iterations per second, computed from total elapsed time
+    pub throughput_ops_per_sec: f64,
+    /// `allocation_count` delta on the global tracker across the run
+    pub allocation_count_delta: i64,
+    /// `total_allocated` byte delta on the global tracker across the run
+    pub total_bytes_delta: i64,
+}
+
+/// Reusable micro-benchmark harness: times `iters` calls to `body`, captures
+/// a per-iteration sample vector, and derives summary statistics plus the
+/// tracker's allocation-count/total-bytes deltas across the run.
+pub struct Benchmark;
+
+impl Benchmark {
+    /// Run `body` for `iters` iterations under `name`, returning a
+    /// [`BenchmarkSummary`]. Panics from `body` propagate to the caller.
+    pub fn run<F: FnMut()>(name: &str, iters: usize, mut body: F) -> BenchmarkSummary {
+        let tracker = crate::core::tracker::memory_tracker::get_global_tracker();
+        let stats_before = tracker.get_stats().ok();
+
+        let mut samples: Vec<u64> = Vec::with_capacity(iters);
+        for _ in 0..iters {
+            let start = Instant::now();
+            body();
+            samples.push(start.elapsed().as_nanos() as u64);
+        }
+
+        let stats_after = tracker.get_stats().ok();
+        let (allocation_count_delta, total_bytes_delta) = match (stats_before, stats_after) {
+            (Some(before), Some(after)) => (
+                after.total_allocations as i64 - before.total_allocations as i64,
+                after.total_allocated as i64 - before.total_allocated as i64,
+            ),
+            _ => (0, 0),
+        };
+
+        Self::summarize(name, samples, allocation_count_delta, total_bytes_delta)
+    }
+
+    fn summarize(
+        name: &str,
+        mut samples: Vec<u64>,
+        allocation_count_delta: i64,
+        total_bytes_delta: i64,
+    ) -> BenchmarkSummary {
+        if samples.is_empty() {
+            return BenchmarkSummary {
+                name: name.to_string(),
+                iterations: 0,
+                min_ns: 0,
+                max_ns: 0,
+                mean_ns: 0.0,
+                median_ns: 0.0,
+                stddev_ns: None,
+                throughput_ops_per_sec: 0.0,
+                allocation_count_delta,
+                total_bytes_delta,
+            };
+        }
+
+        samples.sort_unstable();
+        let iterations = samples.len();
+        let min_ns = samples[0];
+        let max_ns = samples[iterations - 1];
+        let total_ns: u64 = samples.iter().sum();
+        let mean_ns = total_ns as f64 / iterations as f64;
+        let median_ns = if iterations % 2 == 0 {
+            (samples[iterations / 2 - 1] + samples[iterations / 2]) as f64 / 2.0
+        } else {
+            samples[iterations / 2] as f64
+        };
+
+        let stddev_ns = if iterations >= MIN_SAMPLES_FOR_STDDEV {
+            let variance = samples
+                .iter()
+                .map(|&sample| {
+                    let diff = sample as f64 - mean_ns;
+                    diff * diff
+                })
+                .sum::<f64>()
+                / (iterations - 1) as f64;
+            Some(variance.sqrt())
+        } else {
+            None
+        };
+
+        let throughput_ops_per_sec = if total_ns > 0 {
+            iterations as f64 / (total_ns as f64 / 1_000_000_000.0)
+        } else {
+            0.0
+        };
+
+        BenchmarkSummary {
+            name: name.to_string(),
+            iterations,
+            min_ns,
+            max_ns,
+            mean_ns,
+            median_ns,
+            stddev_ns,
+            throughput_ops_per_sec,
+            allocation_count_delta,
+            total_bytes_delta,
+        }
+    }
+}
+
+/// Pluggable output formatter for one or more [`BenchmarkSummary`] results.
+pub trait Reporter {
+    /// Render `summaries` into a complete report string.
+    fn report(&self, summaries: &[BenchmarkSummary]) -> String;
+}
+
+/// Machine-readable JSON formatter: one object per benchmark with every
+/// metric, as a JSON array.
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&self, summaries: &[BenchmarkSummary]) -> String {
+        serde_json::to_string_pretty(summaries).unwrap_or_else(|_| "[]".to_string())
+    }
+}
+
+/// Human-readable formatter with aligned columns.
+pub struct PrettyReporter;
+
+impl Reporter for PrettyReporter {
+    fn report(&self, summaries: &[BenchmarkSummary]) -> String {
+        let name_width = summaries
+            .iter()
+            .map(|s| s.name.len())
+            .max()
+            .unwrap_or(4)
+            .max("name".len());
+
+        let mut lines = vec![format!(
+            "{:<name_width$}  {:>10}  {:>12}  {:>12}  {:>12}  {:>12}  {:>14}",
+            "name", "iters", "min_ns", "max_ns", "mean_ns", "median_ns", "stddev_ns"
+        )];
+        for summary in summaries {
+            let stddev = summary
+                .stddev_ns
+                .map(|v| format!("{v:.1}"))
+                .unwrap_or_else(|| "null".to_string());
+            lines.push(format!(
+                "{:<name_width$}  {:>10}  {:>12}  {:>12}  {:>12.1}  {:>12.1}  {:>14}",
+                summary.name,
+                summary.iterations,
+                summary.min_ns,
+                summary.max_ns,
+                summary.mean_ns,
+                summary.median_ns,
+                stddev
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Compact one-line-per-benchmark formatter.
+pub struct TerseReporter;
+
+impl Reporter for TerseReporter {
+    fn report(&self, summaries: &[BenchmarkSummary]) -> String {
+        summaries
+            .iter()
+            .map(|summary| {
+                let stddev = summary
+                    .stddev_ns
+                    .map(|v| format!("{v:.1}"))
+                    .unwrap_or_else(|| "null".to_string());
+                format!(
+                    "{}: mean={:.1}ns median={:.1}ns stddev={}ns throughput={:.1}/s",
+                    summary.name,
+                    summary.mean_ns,
+                    summary.median_ns,
+                    stddev,
+                    summary.throughput_ops_per_sec
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_workload_tracks_requested_allocation_count() {
+        let config = WorkloadConfig {
+            allocation_count: 50,
+            ..Default::default()
+        };
+        let tracker = generate_workload(&config).unwrap();
+        assert_eq!(tracker.get_active_allocations().unwrap().len(), 50);
+    }
+
+    #[test]
+    fn test_compare_to_baseline_flags_regression_beyond_threshold() {
+        let baseline = BenchmarkResults {
+            allocation_count: 1000,
+            levels: vec![LevelBenchmarkResult {
+                level: "Medium".to_string(),
+                allocations_per_sec: 1000.0,
+                wall_time_ms: 100.0,
+                peak_memory_bytes: 1_000_000,
+                output_bytes: 500_000,
+            }],
+        };
+        let regressed = BenchmarkResults {
+            allocation_count: 1000,
+            levels: vec![LevelBenchmarkResult {
+                level: "Medium".to_string(),
+                allocations_per_sec: 500.0,
+                wall_time_ms: 200.0, // 2x slower
+                peak_memory_bytes: 1_000_000,
+                output_bytes: 500_000,
+            }],
+        };
+
+        let comparisons = compare_to_baseline(&baseline, &regressed, 10.0);
+        assert_eq!(comparisons.len(), 1);
+        assert!(comparisons[0].regressed);
+        assert!(comparisons[0].wall_time_change_pct > 10.0);
+    }
+
+    #[test]
+    fn test_compare_to_baseline_no_regression_within_threshold() {
+        let baseline = BenchmarkResults {
+            allocation_count: 1000,
+            levels: vec![LevelBenchmarkResult {
+                level: "Low".to_string(),
+                allocations_per_sec: 1000.0,
+                wall_time_ms: 100.0,
+                peak_memory_bytes: 1_000_000,
+                output_bytes: 500_000,
+            }],
+        };
+        let current = BenchmarkResults {
+            allocation_count: 1000,
+            levels: vec![LevelBenchmarkResult {
+                level: "Low".to_string(),
+                allocations_per_sec: 990.0,
+                wall_time_ms: 101.0,
+                peak_memory_bytes: 1_000_500,
+                output_bytes: 500_100,
+            }],
+        };
+
+        let comparisons = compare_to_baseline(&baseline, &current, 10.0);
+        assert!(!comparisons[0].regressed);
+    }
+
+    #[test]
+    fn test_parse_byte_size_supports_binary_suffixes() {
+        assert_eq!(parse_byte_size("512B"), Some(512));
+        assert_eq!(parse_byte_size("4KiB"), Some(4 * 1024));
+        assert_eq!(parse_byte_size("1MiB"), Some(1024 * 1024));
+        assert_eq!(parse_byte_size("2GiB"), Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_byte_size("128"), Some(128));
+    }
+
+    #[test]
+    fn test_parse_byte_size_supports_decimal_suffixes() {
+        assert_eq!(parse_byte_size("10KB"), Some(10_000));
+        assert_eq!(parse_byte_size("5MB"), Some(5_000_000));
+        assert_eq!(parse_byte_size("1GB"), Some(1_000_000_000));
+    }
+
+    #[test]
+    fn test_size_distribution_uniform_spans_min_to_max() {
+        let dist = SizeDistribution::Uniform {
+            min: "4KiB".to_string(),
+            max: "1MiB".to_string(),
+        };
+        assert_eq!(dist.sample(0, 5), parse_byte_size("4KiB").unwrap());
+        assert_eq!(dist.sample(4, 5), parse_byte_size("1MiB").unwrap());
+    }
+
+    #[test]
+    fn test_run_replays_workload_and_records_one_latency_per_operation() {
+        let workload = Workload {
+            operation_count: 3,
+            batch_size: 20,
+            ..Default::default()
+        };
+        let dir = std::env::temp_dir().join("memscope_bench_test_run");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let latencies = run(&workload, &ExportJsonOptions::default(), &dir).unwrap();
+
+        assert_eq!(latencies.len(), 3);
+        assert!(latencies.iter().all(|l| l.allocations == 20));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_summarize_computes_percentiles() {
+        let latencies: Vec<OperationLatency> = (1..=10)
+            .map(|i| OperationLatency {
+                operation_index: i,
+                allocations: 1,
+                latency_ms: i as f64,
+            })
+            .collect();
+
+        let summary = summarize(&latencies);
+        assert_eq!(summary.count, 10);
+        assert_eq!(summary.max_ms, 10.0);
+        assert!((summary.mean_ms - 5.5).abs() < f64::EPSILON);
+        assert!(summary.p50_ms >= 5.0 && summary.p50_ms <= 6.0);
+    }
+
+    #[test]
+    fn test_summarize_empty_latencies_does_not_panic() {
+        let summary = summarize(&[]);
+        assert_eq!(summary.count, 0);
+        assert_eq!(summary.max_ms, 0.0);
+    }
+
+    #[test]
+    fn test_plot_latency_svg_writes_valid_svg_markup() {
+        let latencies = vec![
+            OperationLatency {
+                operation_index: 0,
+                allocations: 10,
+                latency_ms: 1.0,
+            },
+            OperationLatency {
+                operation_index: 1,
+                allocations: 10,
+                latency_ms: 3.0,
+            },
+        ];
+        let path = std::env::temp_dir().join("memscope_bench_test_latency.svg");
+
+        plot_latency_svg(&latencies, &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("<svg"));
+        assert!(contents.contains("polyline"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_plot_type_histogram_svg_writes_one_bar_per_type() {
+        let mut type_bytes = std::collections::HashMap::new();
+        type_bytes.insert("String".to_string(), 100u64);
+        type_bytes.insert("Vec<u8>".to_string(), 200u64);
+        let path = std::env::temp_dir().join("memscope_bench_test_hist.svg");
+
+        plot_type_histogram_svg(&type_bytes, &path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.matches("<rect").count(), 3); // background + 2 bars
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_benchmark_run_computes_min_max_mean_median() {
+        let summary = Benchmark::run("noop", 5, || {});
+        assert_eq!(summary.iterations, 5);
+        assert!(summary.min_ns <= summary.mean_ns as u64 + 1);
+        assert!(summary.max_ns >= summary.min_ns);
+        assert!(summary.median_ns >= 0.0);
+    }
+
+    #[test]
+    fn test_benchmark_summarize_stddev_is_null_below_min_samples() {
+        let summary = Benchmark::summarize("single", vec![100], 0, 0);
+        assert_eq!(summary.stddev_ns, None);
+    }
+
+    #[test]
+    fn test_benchmark_summarize_stddev_is_some_at_min_samples() {
+        let summary = Benchmark::summarize("pair", vec![100, 200], 0, 0);
+        assert!(summary.stddev_ns.is_some());
+    }
+
+    #[test]
+    fn test_benchmark_summarize_median_even_and_odd_sample_counts() {
+        let odd = Benchmark::summarize("odd", vec![10, 20, 30], 0, 0);
+        assert_eq!(odd.median_ns, 20.0);
+        let even = Benchmark::summarize("even", vec![10, 20, 30, 40], 0, 0);
+        assert_eq!(even.median_ns, 25.0);
+    }
+
+    #[test]
+    fn test_json_reporter_emits_array_of_objects() {
+        let summary = Benchmark::summarize("bench_a", vec![10, 20], 3, 256);
+        let report = JsonReporter.report(&[summary]);
+        let parsed: serde_json::Value = serde_json::from_str(&report).unwrap();
+        assert!(parsed.is_array());
+        assert_eq!(parsed[0]["name"], "bench_a");
+    }
+
+    #[test]
+    fn test_pretty_reporter_aligns_columns_with_a_header_row() {
+        let summaries = vec![
+            Benchmark::summarize("short", vec![10], 0, 0),
+            Benchmark::summarize("a_much_longer_name", vec![10, 20], 0, 0),
+        ];
+        let report = PrettyReporter.report(&summaries);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 3); // header + 2 rows
+        assert!(lines[0].starts_with("name"));
+    }
+
+    #[test]
+    fn test_terse_reporter_emits_one_line_per_benchmark() {
+        let summaries = vec![
+            Benchmark::summarize("a", vec![10], 0, 0),
+            Benchmark::summarize("b", vec![10], 0, 0),
+        ];
+        let report = TerseReporter.report(&summaries);
+        assert_eq!(report.lines().count(), 2);
+        assert!(report.lines().next().unwrap().starts_with("a:"));
+    }
+}
@@ -4,6 +4,7 @@
 //! including CSS/JS embedding, shared resource loading, and placeholder processing.
 
 use crate::export::binary::error::BinaryExportError;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -12,16 +13,69 @@ use std::path::{Path, PathBuf};
 pub struct TemplateResourceManager {
     /// Base template directory path
     template_dir: PathBuf,
-    /// Cached CSS content
+    /// Cached CSS content, by resource file name
     css_cache: HashMap<String, String>,
-    /// Cached JS content
+    /// Cached JS content, by resource file name
     js_cache: HashMap<String, String>,
     /// SVG images cache
     svg_cache: HashMap<String, String>,
+    /// Processed resource content keyed by content digest (survives
+    /// `clear_cache()`), so an unchanged file never gets reprocessed twice
+    /// even across cache clears.
+    content_cache: HashMap<String, String>,
+    /// Most recently observed digest for each resource file name, exposed
+    /// via [`TemplateResourceManager::resource_digests`] so callers can
+    /// detect when a template's assets changed between exports.
+    resource_digests: HashMap<String, String>,
     /// Placeholder processors
     placeholder_processors: HashMap<String, Box<dyn PlaceholderProcessor>>,
 }
 
+/// SHA-256 digest of `bytes`, as a lowercase hex string.
+fn hash_bytes(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Minimal base64 encoder, used to render Subresource Integrity attributes
+/// (`integrity="sha256-<base64>"`) without taking on a dedicated dependency.
+fn base64_encode(input: &[u8]) -> String {
+    const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = String::new();
+
+    for chunk in input.chunks(3) {
+        let mut buf = [0u8; 3];
+        for (i, &b) in chunk.iter().enumerate() {
+            buf[i] = b;
+        }
+
+        let b = ((buf[0] as u32) << 16) | ((buf[1] as u32) << 8) | (buf[2] as u32);
+
+        result.push(CHARS[((b >> 18) & 63) as usize] as char);
+        result.push(CHARS[((b >> 12) & 63) as usize] as char);
+        result.push(if chunk.len() > 1 {
+            CHARS[((b >> 6) & 63) as usize] as char
+        } else {
+            '='
+        });
+        result.push(if chunk.len() > 2 {
+            CHARS[(b & 63) as usize] as char
+        } else {
+            '='
+        });
+    }
+
+    result
+}
+
+/// Render a `sha256-<base64>` Subresource Integrity value for `bytes`.
+fn sri_hash(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("sha256-{}", base64_encode(&hasher.finalize()))
+}
+
 /// Trait for processing template placeholders
 pub trait PlaceholderProcessor: Send + Sync {
     /// Process a placeholder with given data
@@ -58,6 +112,9 @@ pub struct ResourceConfig {
     pub embed_svg: bool,
     /// Whether to minify resources
     pub minify_resources: bool,
+    /// Whether to emit `integrity="sha256-..."` attributes on linked
+    /// (non-embedded) resources, for tamper-evident HTML bundles.
+    pub verify_integrity: bool,
     /// Custom resource paths
     pub custom_paths: HashMap<String, PathBuf>,
 }
@@ -69,6 +126,7 @@ impl Default for ResourceConfig {
             embed_js: true,
             embed_svg: true,
             minify_resources: false,
+            verify_integrity: false,
             custom_paths: HashMap::new(),
         }
     }
@@ -91,6 +149,8 @@ impl TemplateResourceManager {
             css_cache: HashMap::new(),
             js_cache: HashMap::new(),
             svg_cache: HashMap::new(),
+            content_cache: HashMap::new(),
+            resource_digests: HashMap::new(),
             placeholder_processors: HashMap::new(),
         };
 
@@ -112,7 +172,7 @@ impl TemplateResourceManager {
         let mut template_content =
             fs::read_to_string(&template_path).map_err(|e| BinaryExportError::Io(e))?;
 
-        // Load and embed resources
+        // Load and embed (or link) resources
         if config.embed_css {
             let css_content = if !data.css_content.is_empty() {
                 data.css_content.clone()
@@ -120,6 +180,9 @@ impl TemplateResourceManager {
                 self.load_css_resources(config)?
             };
             template_content = template_content.replace("{{CSS_CONTENT}}", &css_content);
+        } else {
+            let link_tag = self.linked_css_tag(config)?;
+            template_content = template_content.replace("{{CSS_CONTENT}}", &link_tag);
         }
 
         if config.embed_js {
@@ -129,6 +192,9 @@ impl TemplateResourceManager {
                 self.load_js_resources(config)?
             };
             template_content = template_content.replace("{{JS_CONTENT}}", &js_content);
+        } else {
+            let script_tag = self.linked_js_tag(config)?;
+            template_content = template_content.replace("{{JS_CONTENT}}", &script_tag);
         }
 
         if config.embed_svg {
@@ -156,14 +222,12 @@ impl TemplateResourceManager {
 
             let css_path = self.template_dir.join(css_file);
             if css_path.exists() {
-                let css_content =
-                    fs::read_to_string(&css_path).map_err(|e| BinaryExportError::Io(e))?;
-
-                let processed_css = if config.minify_resources {
-                    self.minify_css(&css_content)
-                } else {
-                    css_content
-                };
+                let processed_css = self.load_and_process_resource(
+                    css_file,
+                    &css_path,
+                    config.minify_resources,
+                    Self::minify_css,
+                )?;
 
                 self.css_cache
                     .insert(css_file.to_string(), processed_css.clone());
@@ -189,14 +253,12 @@ impl TemplateResourceManager {
 
             let js_path = self.template_dir.join(js_file);
             if js_path.exists() {
-                let js_content =
-                    fs::read_to_string(&js_path).map_err(|e| BinaryExportError::Io(e))?;
-
-                let processed_js = if config.minify_resources {
-                    self.minify_js(&js_content)
-                } else {
-                    js_content
-                };
+                let processed_js = self.load_and_process_resource(
+                    js_file,
+                    &js_path,
+                    config.minify_resources,
+                    Self::minify_js,
+                )?;
 
                 self.js_cache
                     .insert(js_file.to_string(), processed_js.clone());
@@ -208,6 +270,89 @@ impl TemplateResourceManager {
         Ok(combined_js)
     }
 
+    /// Read `path`, hash its raw bytes, and return the (optionally minified)
+    /// content -- reusing [`TemplateResourceManager::content_cache`] when an
+    /// identical digest has already been processed, so an unchanged file
+    /// never gets re-minified twice even across `clear_cache()` calls.
+    fn load_and_process_resource(
+        &mut self,
+        resource_name: &str,
+        path: &Path,
+        minify: bool,
+        minifier: fn(&Self, &str) -> String,
+    ) -> Result<String, BinaryExportError> {
+        let raw_bytes = fs::read(path).map_err(BinaryExportError::Io)?;
+        let digest = hash_bytes(&raw_bytes);
+        self.resource_digests
+            .insert(resource_name.to_string(), digest.clone());
+
+        // Minified and unminified output of the same bytes are cached apart.
+        let cache_key = if minify {
+            format!("{digest}:minified")
+        } else {
+            digest
+        };
+        if let Some(processed) = self.content_cache.get(&cache_key) {
+            return Ok(processed.clone());
+        }
+
+        let raw_content = String::from_utf8_lossy(&raw_bytes).into_owned();
+        let processed = if minify {
+            minifier(self, &raw_content)
+        } else {
+            raw_content
+        };
+        self.content_cache.insert(cache_key, processed.clone());
+        Ok(processed)
+    }
+
+    /// `<link>` tag for `styles.css` when CSS is linked rather than embedded.
+    fn linked_css_tag(&mut self, config: &ResourceConfig) -> Result<String, BinaryExportError> {
+        let css_path = self.template_dir.join("styles.css");
+        if !css_path.exists() {
+            return Ok(String::new());
+        }
+        let raw_bytes = fs::read(&css_path).map_err(BinaryExportError::Io)?;
+        self.resource_digests
+            .insert("styles.css".to_string(), hash_bytes(&raw_bytes));
+
+        if config.verify_integrity {
+            Ok(format!(
+                r#"<link rel="stylesheet" href="styles.css" integrity="{}" crossorigin="anonymous">"#,
+                sri_hash(&raw_bytes)
+            ))
+        } else {
+            Ok(r#"<link rel="stylesheet" href="styles.css">"#.to_string())
+        }
+    }
+
+    /// `<script>` tag for `script.js` when JS is linked rather than embedded.
+    fn linked_js_tag(&mut self, config: &ResourceConfig) -> Result<String, BinaryExportError> {
+        let js_path = self.template_dir.join("script.js");
+        if !js_path.exists() {
+            return Ok(String::new());
+        }
+        let raw_bytes = fs::read(&js_path).map_err(BinaryExportError::Io)?;
+        self.resource_digests
+            .insert("script.js".to_string(), hash_bytes(&raw_bytes));
+
+        if config.verify_integrity {
+            Ok(format!(
+                r#"<script src="script.js" integrity="{}" crossorigin="anonymous"></script>"#,
+                sri_hash(&raw_bytes)
+            ))
+        } else {
+            Ok(r#"<script src="script.js"></script>"#.to_string())
+        }
+    }
+
+    /// Most recently observed content digest per resource file name, for
+    /// callers that want to detect when a template's assets changed between
+    /// exports.
+    pub fn resource_digests(&self) -> &HashMap<String, String> {
+        &self.resource_digests
+    }
+
     /// Load SVG resources from templates directory
     fn load_svg_resources(
         &mut self,
@@ -349,7 +494,12 @@ impl TemplateResourceManager {
         self.load_js_resources(config)
     }
 
-    /// Clear resource caches
+    /// Clear resource caches.
+    ///
+    /// Only the by-name caches are cleared; `content_cache` (keyed by
+    /// content digest) is intentionally left intact, so a subsequent load of
+    /// an unchanged file is served from there instead of being re-read and
+    /// re-minified from disk.
     pub fn clear_cache(&mut self) {
         self.css_cache.clear();
         self.js_cache.clear();
@@ -591,6 +741,65 @@ mod tests {
         assert!(manager.css_cache.is_empty());
     }
 
+    #[test]
+    fn test_resource_digests_are_recorded_after_load() {
+        let temp_dir = create_test_template_dir().expect("Failed to get test value");
+        let mut manager =
+            TemplateResourceManager::new(temp_dir.path()).expect("Test operation failed");
+        let config = ResourceConfig::default();
+
+        manager
+            .get_shared_css(&config)
+            .expect("Test operation failed");
+
+        let digest = manager
+            .resource_digests()
+            .get("styles.css")
+            .expect("digest should be recorded");
+        assert_eq!(digest.len(), 64); // SHA-256 hex digest
+    }
+
+    #[test]
+    fn test_unchanged_file_reuses_content_cache_across_clear_cache() {
+        let temp_dir = create_test_template_dir().expect("Failed to get test value");
+        let mut manager =
+            TemplateResourceManager::new(temp_dir.path()).expect("Test operation failed");
+        let config = ResourceConfig::default();
+
+        let css1 = manager
+            .get_shared_css(&config)
+            .expect("Test operation failed");
+        let digest1 = manager.resource_digests().get("styles.css").cloned();
+
+        // clear_cache() drops the by-name cache, but the file is unchanged,
+        // so the digest (and its processed content) should be identical.
+        manager.clear_cache();
+        let css2 = manager
+            .get_shared_css(&config)
+            .expect("Test operation failed");
+        let digest2 = manager.resource_digests().get("styles.css").cloned();
+
+        assert_eq!(css1, css2);
+        assert_eq!(digest1, digest2);
+    }
+
+    #[test]
+    fn test_linked_css_has_no_integrity_attribute_when_disabled() {
+        let temp_dir = create_test_template_dir().expect("Failed to get test value");
+        let mut manager =
+            TemplateResourceManager::new(temp_dir.path()).expect("Test operation failed");
+
+        let config = ResourceConfig {
+            embed_css: false,
+            ..ResourceConfig::default()
+        };
+        let tag = manager
+            .linked_css_tag(&config)
+            .expect("Test operation failed");
+        assert!(!tag.contains("integrity"));
+        assert!(tag.contains(r#"href="styles.css""#));
+    }
+
     #[test]
     fn test_template_data_creation() {
         let mut custom_data = HashMap::new();
@@ -1,7 +1,9 @@
 // Core export modules
 pub mod adaptive_performance;
 pub mod analysis_engine;
+pub mod backend;
 pub mod batch_processor;
+pub mod benchmark;
 pub mod binary;
 pub mod complex_type_export;
 pub mod config_optimizer;
@@ -15,11 +17,13 @@ pub mod fast_export_coordinator;
 pub mod fixed_hybrid_template;
 pub mod high_speed_buffered_writer;
 pub mod html_export;
+pub mod non_finite_json;
 pub mod optimized_json_export;
 pub mod parallel_shard_processor;
 pub mod progress_monitor;
 pub mod quality_validator;
 pub mod schema_validator;
+pub mod sink;
 pub mod streaming_json_writer;
 pub mod system_optimizer;
 pub mod visualization;
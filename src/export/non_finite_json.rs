@@ -0,0 +1,338 @@
+//! Policy-driven handling of non-finite (`NaN`/`±Infinity`) floats during
+//! streaming JSON serialization.
+//!
+//! JSON has no representation for `NaN` or `Infinity`, and `serde_json`
+//! silently rewrites every occurrence to `null` -- indistinguishable from a
+//! genuinely absent value, and liable to confuse downstream parsers that
+//! expect a number in that field. [`NonFiniteFloatPolicy`] lets callers pick
+//! how those values are rewritten instead, and [`to_string_with_policy`] /
+//! [`to_writer_with_policy`] apply it via a [`serde_json::ser::Formatter`]
+//! wrapper that intercepts `write_f32`/`write_f64` before they reach the
+//! output stream, counting how many values it rewrote along the way.
+
+use serde::Serialize;
+use serde_json::ser::{CharEscape, CompactFormatter, Formatter, PrettyFormatter, Serializer};
+use std::cell::Cell;
+use std::io;
+use std::rc::Rc;
+
+/// How to rewrite a non-finite (`NaN`, `Infinity`, `-Infinity`) float
+/// encountered while serializing a streamed value.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum NonFiniteFloatPolicy {
+    /// Rewrite the value as JSON `null` -- `serde_json`'s own default
+    /// behavior, made explicit and countable here.
+    Null,
+    /// Rewrite the value as a fixed sentinel number (e.g. `0.0` or `-1.0`),
+    /// so downstream numeric parsers that reject `null` in a numeric field
+    /// still succeed.
+    Sentinel(f64),
+    /// Drop the field containing the non-finite value entirely.
+    ///
+    /// A [`Formatter`] only ever sees the value itself -- by the time
+    /// `write_f64` runs, the preceding key has already been written to the
+    /// output stream, so true field omission would require buffering and
+    /// rewriting the whole enclosing object. That's out of proportion for
+    /// this pass, so `SkipField` currently degrades to the same behavior as
+    /// [`NonFiniteFloatPolicy::Null`]; it's kept as its own variant so
+    /// buffering support can be added later without another config change.
+    SkipField,
+}
+
+impl Default for NonFiniteFloatPolicy {
+    fn default() -> Self {
+        NonFiniteFloatPolicy::Null
+    }
+}
+
+/// A [`Formatter`] that wraps an inner formatter (compact or pretty),
+/// forwarding everything unchanged except `write_f32`/`write_f64`, where it
+/// rewrites non-finite values per [`NonFiniteFloatPolicy`] and tallies how
+/// many it rewrote.
+struct NonFiniteFormatter<F> {
+    inner: F,
+    policy: NonFiniteFloatPolicy,
+    rewritten: Rc<Cell<usize>>,
+}
+
+impl<F> NonFiniteFormatter<F> {
+    fn new(inner: F, policy: NonFiniteFloatPolicy, rewritten: Rc<Cell<usize>>) -> Self {
+        Self {
+            inner,
+            policy,
+            rewritten,
+        }
+    }
+
+    fn record_rewrite(&self) {
+        self.rewritten.set(self.rewritten.get() + 1);
+    }
+}
+
+impl<F: Formatter> Formatter for NonFiniteFormatter<F> {
+    fn write_null<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.write_null(writer)
+    }
+
+    fn write_bool<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: bool) -> io::Result<()> {
+        self.inner.write_bool(writer, value)
+    }
+
+    fn write_i8<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: i8) -> io::Result<()> {
+        self.inner.write_i8(writer, value)
+    }
+
+    fn write_i16<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: i16) -> io::Result<()> {
+        self.inner.write_i16(writer, value)
+    }
+
+    fn write_i32<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: i32) -> io::Result<()> {
+        self.inner.write_i32(writer, value)
+    }
+
+    fn write_i64<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: i64) -> io::Result<()> {
+        self.inner.write_i64(writer, value)
+    }
+
+    fn write_i128<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: i128) -> io::Result<()> {
+        self.inner.write_i128(writer, value)
+    }
+
+    fn write_u8<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: u8) -> io::Result<()> {
+        self.inner.write_u8(writer, value)
+    }
+
+    fn write_u16<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: u16) -> io::Result<()> {
+        self.inner.write_u16(writer, value)
+    }
+
+    fn write_u32<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: u32) -> io::Result<()> {
+        self.inner.write_u32(writer, value)
+    }
+
+    fn write_u64<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: u64) -> io::Result<()> {
+        self.inner.write_u64(writer, value)
+    }
+
+    fn write_u128<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: u128) -> io::Result<()> {
+        self.inner.write_u128(writer, value)
+    }
+
+    fn write_f32<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f32) -> io::Result<()> {
+        if value.is_finite() {
+            return self.inner.write_f32(writer, value);
+        }
+        self.record_rewrite();
+        match self.policy {
+            NonFiniteFloatPolicy::Null | NonFiniteFloatPolicy::SkipField => {
+                self.inner.write_null(writer)
+            }
+            NonFiniteFloatPolicy::Sentinel(sentinel) => self.inner.write_f64(writer, sentinel),
+        }
+    }
+
+    fn write_f64<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        if value.is_finite() {
+            return self.inner.write_f64(writer, value);
+        }
+        self.record_rewrite();
+        match self.policy {
+            NonFiniteFloatPolicy::Null | NonFiniteFloatPolicy::SkipField => {
+                self.inner.write_null(writer)
+            }
+            NonFiniteFloatPolicy::Sentinel(sentinel) => self.inner.write_f64(writer, sentinel),
+        }
+    }
+
+    fn write_number_str<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        value: &str,
+    ) -> io::Result<()> {
+        self.inner.write_number_str(writer, value)
+    }
+
+    fn begin_string<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_string(writer)
+    }
+
+    fn end_string<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_string(writer)
+    }
+
+    fn write_string_fragment<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        fragment: &str,
+    ) -> io::Result<()> {
+        self.inner.write_string_fragment(writer, fragment)
+    }
+
+    fn write_char_escape<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        char_escape: CharEscape,
+    ) -> io::Result<()> {
+        self.inner.write_char_escape(writer, char_escape)
+    }
+
+    fn write_byte_array<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        value: &[u8],
+    ) -> io::Result<()> {
+        self.inner.write_byte_array(writer, value)
+    }
+
+    fn begin_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_array(writer)
+    }
+
+    fn end_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_array(writer)
+    }
+
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.inner.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_object(writer)
+    }
+
+    fn end_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_object(writer)
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.inner.begin_object_key(writer, first)
+    }
+
+    fn end_object_key<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_object_key(writer)
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_object_value(writer)
+    }
+
+    fn end_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_object_value(writer)
+    }
+
+    fn write_raw_fragment<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        fragment: &str,
+    ) -> io::Result<()> {
+        self.inner.write_raw_fragment(writer, fragment)
+    }
+}
+
+/// Serialize `value` to a `String`, rewriting non-finite floats per
+/// `policy`. Returns the rendered JSON alongside how many floats were
+/// rewritten.
+pub fn to_string_with_policy<T: ?Sized + Serialize>(
+    value: &T,
+    pretty: bool,
+    policy: NonFiniteFloatPolicy,
+) -> serde_json::Result<(String, usize)> {
+    let mut buf = Vec::new();
+    let rewritten = to_writer_with_policy(&mut buf, value, pretty, policy)?;
+    // The formatter only ever writes valid UTF-8 JSON tokens.
+    let json = String::from_utf8(buf).expect("serde_json formatter output is valid UTF-8");
+    Ok((json, rewritten))
+}
+
+/// Serialize `value` into `writer`, rewriting non-finite floats per
+/// `policy`. Returns how many floats were rewritten.
+pub fn to_writer_with_policy<W: io::Write, T: ?Sized + Serialize>(
+    writer: W,
+    value: &T,
+    pretty: bool,
+    policy: NonFiniteFloatPolicy,
+) -> serde_json::Result<usize> {
+    let rewritten = Rc::new(Cell::new(0usize));
+    if pretty {
+        let formatter =
+            NonFiniteFormatter::new(PrettyFormatter::new(), policy, Rc::clone(&rewritten));
+        let mut serializer = Serializer::with_formatter(writer, formatter);
+        value.serialize(&mut serializer)?;
+    } else {
+        let formatter = NonFiniteFormatter::new(CompactFormatter, policy, Rc::clone(&rewritten));
+        let mut serializer = Serializer::with_formatter(writer, formatter);
+        value.serialize(&mut serializer)?;
+    }
+    Ok(rewritten.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Sample {
+        ok: f64,
+        broken: f64,
+    }
+
+    #[test]
+    fn test_finite_values_pass_through_unchanged() {
+        let sample = Sample {
+            ok: 1.5,
+            broken: 2.5,
+        };
+        let (json, rewritten) =
+            to_string_with_policy(&sample, false, NonFiniteFloatPolicy::Null).unwrap();
+        assert_eq!(json, r#"{"ok":1.5,"broken":2.5}"#);
+        assert_eq!(rewritten, 0);
+    }
+
+    #[test]
+    fn test_null_policy_rewrites_nan_and_counts_it() {
+        let sample = Sample {
+            ok: 1.0,
+            broken: f64::NAN,
+        };
+        let (json, rewritten) =
+            to_string_with_policy(&sample, false, NonFiniteFloatPolicy::Null).unwrap();
+        assert_eq!(json, r#"{"ok":1.0,"broken":null}"#);
+        assert_eq!(rewritten, 1);
+    }
+
+    #[test]
+    fn test_sentinel_policy_rewrites_infinity_to_configured_value() {
+        let sample = Sample {
+            ok: 1.0,
+            broken: f64::INFINITY,
+        };
+        let (json, rewritten) =
+            to_string_with_policy(&sample, false, NonFiniteFloatPolicy::Sentinel(-1.0)).unwrap();
+        assert_eq!(json, r#"{"ok":1.0,"broken":-1.0}"#);
+        assert_eq!(rewritten, 1);
+    }
+
+    #[test]
+    fn test_skip_field_policy_currently_degrades_to_null() {
+        let sample = Sample {
+            ok: 1.0,
+            broken: f64::NEG_INFINITY,
+        };
+        let (json, rewritten) =
+            to_string_with_policy(&sample, false, NonFiniteFloatPolicy::SkipField).unwrap();
+        assert_eq!(json, r#"{"ok":1.0,"broken":null}"#);
+        assert_eq!(rewritten, 1);
+    }
+}
@@ -0,0 +1,166 @@
+//! Export sink abstraction.
+//!
+//! Every `generate_*` export method and `write_json_optimized` used to hardcode
+//! `File::create` plus `std::fs::create_dir_all`. [`ExportSink`] replaces that with
+//! a single `create_writer` call keyed by a relative file name, so an export run
+//! can be redirected anywhere that can produce a `Write` -- the local filesystem
+//! via [`LocalFsSink`], an in-memory buffer for tests, or object storage via the
+//! optional [`ObjectStorageSink`] -- without changing the writers themselves.
+
+use crate::core::types::{TrackingError, TrackingResult};
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Destination for a named export output file (e.g. `memory_analysis.json`).
+pub trait ExportSink: Send + Sync {
+    /// Open (or create) a writer for `relative_name` within this sink.
+    fn create_writer(&self, relative_name: &str) -> TrackingResult<Box<dyn Write + Send>>;
+
+    /// Called once after every file in an export run has been written. The
+    /// default is a no-op; sinks that buffer output (e.g. object storage) use
+    /// this to flush everything in one batch.
+    fn finalize(&self) -> TrackingResult<()> {
+        Ok(())
+    }
+}
+
+/// Default sink: writes each named file directly under `base_dir` on the local
+/// filesystem, creating the directory on first use.
+pub struct LocalFsSink {
+    base_dir: PathBuf,
+}
+
+impl LocalFsSink {
+    /// Create a sink rooted at `base_dir`. The directory is created lazily on
+    /// the first call to `create_writer`.
+    pub fn new(base_dir: PathBuf) -> Self {
+        Self { base_dir }
+    }
+}
+
+impl ExportSink for LocalFsSink {
+    fn create_writer(&self, relative_name: &str) -> TrackingResult<Box<dyn Write + Send>> {
+        if !self.base_dir.exists() {
+            std::fs::create_dir_all(&self.base_dir).map_err(|e| {
+                TrackingError::IoError(format!(
+                    "Failed to create export directory {}: {e}",
+                    self.base_dir.display()
+                ))
+            })?;
+        }
+        let file = File::create(self.base_dir.join(relative_name))?;
+        Ok(Box::new(file))
+    }
+}
+
+/// Uploads a finished object to storage under some key. Implemented by callers
+/// against whatever client (AWS SDK, GCS, ...) their application already
+/// depends on, so this crate doesn't need to pull in an object-storage SDK
+/// itself to support the `object-storage-sink` feature.
+#[cfg(feature = "object-storage-sink")]
+pub trait ObjectUploader: Send + Sync {
+    /// Upload `bytes` under `key`.
+    fn put_object(&self, key: &str, bytes: &[u8]) -> TrackingResult<()>;
+}
+
+/// Sink that buffers each logical file in memory and uploads it under
+/// `key_prefix` via an [`ObjectUploader`] when [`ExportSink::finalize`] runs.
+/// This avoids a local scratch directory entirely: CI and production
+/// monitoring can push `memory_analysis.json`, `lifetime.json`, etc. straight
+/// to a bucket.
+#[cfg(feature = "object-storage-sink")]
+pub struct ObjectStorageSink<U: ObjectUploader> {
+    uploader: U,
+    key_prefix: String,
+    buffers: std::sync::Arc<std::sync::Mutex<Vec<(String, Vec<u8>)>>>,
+}
+
+#[cfg(feature = "object-storage-sink")]
+impl<U: ObjectUploader> ObjectStorageSink<U> {
+    /// Create a sink that uploads every finalized file under `key_prefix`.
+    pub fn new(uploader: U, key_prefix: impl Into<String>) -> Self {
+        Self {
+            uploader,
+            key_prefix: key_prefix.into(),
+            buffers: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+        }
+    }
+}
+
+#[cfg(feature = "object-storage-sink")]
+impl<U: ObjectUploader> ExportSink for ObjectStorageSink<U> {
+    fn create_writer(&self, relative_name: &str) -> TrackingResult<Box<dyn Write + Send>> {
+        Ok(Box::new(BufferedWriter {
+            relative_name: relative_name.to_string(),
+            buffer: Vec::new(),
+            sink_buffers: self.buffers.clone(),
+        }))
+    }
+
+    fn finalize(&self) -> TrackingResult<()> {
+        let buffers = self.buffers.lock().map_err(|_| {
+            TrackingError::InternalError("object storage sink buffer lock poisoned".to_string())
+        })?;
+        for (relative_name, bytes) in buffers.iter() {
+            let key = format!("{}/{relative_name}", self.key_prefix.trim_end_matches('/'));
+            self.uploader.put_object(&key, bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single file's in-progress buffer, flushed into its sink's buffer list on drop.
+#[cfg(feature = "object-storage-sink")]
+struct BufferedWriter {
+    relative_name: String,
+    buffer: Vec<u8>,
+    sink_buffers: std::sync::Arc<std::sync::Mutex<Vec<(String, Vec<u8>)>>>,
+}
+
+#[cfg(feature = "object-storage-sink")]
+impl Write for BufferedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "object-storage-sink")]
+impl Drop for BufferedWriter {
+    fn drop(&mut self) {
+        if let Ok(mut buffers) = self.sink_buffers.lock() {
+            buffers.push((self.relative_name.clone(), std::mem::take(&mut self.buffer)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_local_fs_sink_creates_directory_and_writes_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let sink = LocalFsSink::new(temp_dir.path().join("nested"));
+
+        let mut writer = sink.create_writer("memory_analysis.json").unwrap();
+        writer.write_all(b"{}").unwrap();
+        drop(writer);
+
+        let contents =
+            std::fs::read_to_string(temp_dir.path().join("nested/memory_analysis.json")).unwrap();
+        assert_eq!(contents, "{}");
+    }
+
+    #[test]
+    fn test_local_fs_sink_finalize_is_a_noop() {
+        let temp_dir = TempDir::new().unwrap();
+        let sink = LocalFsSink::new(temp_dir.path().to_path_buf());
+        assert!(sink.finalize().is_ok());
+    }
+}
@@ -7,9 +7,453 @@ use crate::core::types::{TrackingError, TrackingResult};
 use crate::export::batch_processor::{
     BatchProcessingMetrics, ProcessedBoundaryData, ProcessedFFIData, ProcessedUnsafeData,
 };
+use crate::export::non_finite_json::{self, NonFiniteFloatPolicy};
+use memmap2::MmapMut;
 use serde::{Deserialize, Serialize};
-use std::io::{BufWriter, Write};
-use std::time::Instant;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Wraps the inner writer `W` to count bytes actually emitted to it, i.e.
+/// the compressed byte count when compression is enabled. Sits beneath the
+/// `BufWriter`/encoder stack so every byte that leaves the process is
+/// counted exactly once.
+struct CountingWriter<W: Write> {
+    inner: W,
+    bytes_written: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.bytes_written += n as u64;
+        Ok(n)
+    }
+
+    /// Forwarded explicitly (rather than relying on the default trait
+    /// method, which only ever writes the first non-empty slice) so a real
+    /// vectored write on `inner` -- when the underlying `W` is something
+    /// like a `File` or socket that batches slices into one syscall -- is
+    /// still counted correctly here.
+    fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+        let n = self.inner.write_vectored(bufs)?;
+        self.bytes_written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A growable memory-mapped backing file that the array-chunk serializer
+/// writes directly into, for [`StreamingWriterConfig::mmap_backend`]. There's
+/// no intermediate `BufWriter` here -- `write_all` copies straight into the
+/// mapped region -- so `finish()` only has to truncate off the unused tail
+/// rather than flush anything.
+struct MappedExportBuffer {
+    file: std::fs::File,
+    mmap: MmapMut,
+    /// Size of `mmap`, i.e. the current backing-file length.
+    mapped_len: u64,
+    /// How far into `mmap` has actually been written; everything past this
+    /// is truncated away in `finish()`.
+    write_offset: u64,
+    /// How much to grow the mapping by (re-mapping the file at the new size)
+    /// when `write_all` runs past `mapped_len`.
+    grow_extent: u64,
+}
+
+impl MappedExportBuffer {
+    /// Pre-size `path` to `initial_size` bytes and map it in its entirety.
+    fn create(path: &std::path::Path, initial_size: u64, grow_extent: u64) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        let mapped_len = initial_size.max(1);
+        file.set_len(mapped_len)?;
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        Ok(Self {
+            file,
+            mmap,
+            mapped_len,
+            write_offset: 0,
+            grow_extent: grow_extent.max(1),
+        })
+    }
+
+    /// Grow the backing file (by at least `needed` bytes beyond
+    /// `write_offset`, rounded up to a whole number of `grow_extent` chunks)
+    /// and remap it, since a `MmapMut` can't simply be extended in place.
+    fn ensure_capacity(&mut self, needed: u64) -> std::io::Result<()> {
+        let required = self.write_offset + needed;
+        if required <= self.mapped_len {
+            return Ok(());
+        }
+        let growth = required - self.mapped_len;
+        let extents = growth.div_ceil(self.grow_extent);
+        let new_len = self.mapped_len + extents * self.grow_extent;
+
+        // Flush and drop the old mapping before resizing the file out from
+        // under it, then remap at the new length.
+        self.mmap.flush()?;
+        self.file.set_len(new_len)?;
+        self.mmap = unsafe { MmapMut::map_mut(&self.file)? };
+        self.mapped_len = new_len;
+        Ok(())
+    }
+
+    /// The current size of the backing file/mapping -- the high-water mark
+    /// surfaced as `StreamingStats::peak_memory_usage`.
+    fn mapped_len(&self) -> u64 {
+        self.mapped_len
+    }
+
+    /// Unmap and truncate the backing file down to the bytes actually
+    /// written, returning the final byte count.
+    fn finish(self) -> std::io::Result<u64> {
+        let written = self.write_offset;
+        self.mmap.flush()?;
+        drop(self.mmap);
+        self.file.set_len(written)?;
+        Ok(written)
+    }
+}
+
+impl Write for MappedExportBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.ensure_capacity(buf.len() as u64)?;
+        let start = self.write_offset as usize;
+        let end = start + buf.len();
+        self.mmap[start..end].copy_from_slice(buf);
+        self.write_offset = end as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.mmap.flush()
+    }
+}
+
+/// The canonical top-level sections a checkpointed export tracks, in the
+/// order they're normally emitted. [`StreamingJsonWriter::resume_from`] walks
+/// these in order to find the first one that isn't yet complete.
+const CHECKPOINT_SECTIONS: [&str; 6] = [
+    "unsafe_ffi_header",
+    "unsafe_allocations",
+    "ffi_allocations",
+    "boundary_events",
+    "safety_violations",
+    "processing_metrics",
+];
+
+/// One completed top-level section, as recorded in the checkpoint sidecar
+/// file written by [`CheckpointWriter`] and consumed by
+/// [`StreamingJsonWriter::resume_from`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CheckpointEntry {
+    section: String,
+    offset: u64,
+    length: u64,
+    hash: String,
+}
+
+/// Sidecar index written alongside a checkpointed export (see
+/// [`StreamingWriterConfig::checkpoint_path`]): one JSON line per completed
+/// top-level section, each recording enough (`offset`, `length`, `hash`) to
+/// validate it byte-for-byte on a later `resume_from` without re-deriving
+/// it. Synced to disk whenever the writer it's attached to is flushed.
+struct CheckpointWriter {
+    file: std::fs::File,
+    entries: Vec<CheckpointEntry>,
+}
+
+impl CheckpointWriter {
+    /// Start a fresh checkpoint file at `path`, truncating any existing one.
+    fn create(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+        Ok(Self {
+            file,
+            entries: Vec::new(),
+        })
+    }
+
+    /// Reopen a checkpoint file for appending, seeded with the entries
+    /// `resume_from` already validated -- the file is rewritten down to
+    /// just those, discarding any trailing entries for sections that are
+    /// about to be re-driven.
+    fn reopen(path: &Path, valid_entries: Vec<CheckpointEntry>) -> std::io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+        for entry in &valid_entries {
+            let line = serde_json::to_string(entry).expect("CheckpointEntry always serializes");
+            writeln!(file, "{line}")?;
+        }
+        file.sync_all()?;
+        Ok(Self {
+            file,
+            entries: valid_entries,
+        })
+    }
+
+    /// Append `entry` to the sidecar file and remember it in memory so
+    /// `finalize` can fold the whole set into a trailing offset table.
+    fn record(&mut self, entry: CheckpointEntry) -> std::io::Result<()> {
+        let line = serde_json::to_string(&entry).expect("CheckpointEntry always serializes");
+        writeln!(self.file, "{line}")?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    fn sync(&self) -> std::io::Result<()> {
+        self.file.sync_all()
+    }
+}
+
+/// The buffered output path, with or without streaming zstd compression.
+/// `write_raw` writes go through this incrementally -- the full document is
+/// never buffered in memory for compression.
+enum StreamingSink<W: Write> {
+    Plain(BufWriter<CountingWriter<W>>),
+    ZstdCompressed(Box<zstd::stream::write::Encoder<'static, BufWriter<CountingWriter<W>>>>),
+    GzipCompressed(Box<flate2::write::GzEncoder<BufWriter<CountingWriter<W>>>>),
+    /// Writes go straight into a memory-mapped backing file instead of
+    /// through `W` at all; see [`StreamingWriterConfig::mmap_backend`].
+    Mmap(MappedExportBuffer),
+}
+
+impl<W: Write> Write for StreamingSink<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            StreamingSink::Plain(w) => w.write(buf),
+            StreamingSink::ZstdCompressed(w) => w.write(buf),
+            StreamingSink::GzipCompressed(w) => w.write(buf),
+            StreamingSink::Mmap(w) => w.write(buf),
+        }
+    }
+
+    /// Flushing a compressed sink emits a sync-flush: all data written so
+    /// far becomes decodable, but the frame stays open so more data can
+    /// still be appended. This is distinct from `finish()`, which closes the
+    /// frame and must only happen once, in `finalize`. The mmap sink has no
+    /// intermediate buffer, so this is just an `msync`.
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            StreamingSink::Plain(w) => w.flush(),
+            StreamingSink::ZstdCompressed(w) => w.flush(),
+            StreamingSink::GzipCompressed(w) => w.flush(),
+            StreamingSink::Mmap(w) => w.flush(),
+        }
+    }
+}
+
+impl<W: Write> StreamingSink<W> {
+    /// Close the sink, returning the compressed-byte count emitted to the
+    /// underlying writer. For either compressed variant, this ends the
+    /// frame -- must only be called once, from `finalize`. For the mmap
+    /// variant, this unmaps and truncates the backing file to its actual
+    /// written length.
+    fn finish(self) -> std::io::Result<u64> {
+        let buffered = match self {
+            StreamingSink::Mmap(mapped) => return mapped.finish(),
+            StreamingSink::Plain(w) => w,
+            StreamingSink::ZstdCompressed(encoder) => encoder.finish()?,
+            StreamingSink::GzipCompressed(encoder) => encoder.finish()?,
+        };
+        let counting = buffered.into_inner().map_err(|e| e.into_error())?;
+        Ok(counting.bytes_written)
+    }
+}
+
+/// Work sent to the background I/O thread when `non_blocking` is enabled.
+enum WriterCommand {
+    /// An already-rendered chunk of bytes to write to the sink.
+    Write(Vec<u8>),
+    /// Flush the sink and report back once done, so `flush()` stays a
+    /// meaningful synchronization point even though writes are async.
+    Flush(crossbeam_channel::Sender<std::io::Result<()>>),
+    /// Close the sink, report the compressed byte count, and end the
+    /// thread. Must only be sent once.
+    Shutdown(crossbeam_channel::Sender<std::io::Result<u64>>),
+}
+
+/// Owns the background I/O thread used when `StreamingWriterConfig::non_blocking`
+/// is set. `write_raw` hands owned byte buffers to `command_tx` instead of
+/// writing to the sink directly, so JSON serialization never blocks on
+/// disk/socket latency; the background thread drains the channel and performs
+/// the actual writes against the real [`StreamingSink`].
+struct BackgroundWriter {
+    command_tx: crossbeam_channel::Sender<WriterCommand>,
+    handle: Option<std::thread::JoinHandle<()>>,
+    /// Buffers handed to `command_tx` that the background thread hasn't
+    /// drained yet, surfaced via `StreamingStats::queued_buffers`.
+    queued: Arc<AtomicUsize>,
+    /// First I/O error observed on the background thread, if any. Checked on
+    /// every subsequent `write_*` call and at `finalize`, per the "propagate
+    /// on the next call" requirement -- the producer thread never blocks
+    /// waiting for it.
+    error: Arc<Mutex<Option<String>>>,
+    /// How long a `send`/`send_timeout` may block before giving up and
+    /// surfacing `TrackingError::IoError` instead of hanging forever.
+    send_deadline: Duration,
+}
+
+impl BackgroundWriter {
+    /// Spawn the background thread, moving `sink` onto it permanently. The
+    /// channel is bounded by `capacity` messages, providing the back-pressure
+    /// that keeps the producer from racing arbitrarily far ahead of disk/socket
+    /// I/O.
+    fn spawn<W: Write + Send + 'static>(
+        sink: StreamingSink<W>,
+        capacity: usize,
+        send_deadline: Duration,
+    ) -> Self {
+        let (command_tx, command_rx) = crossbeam_channel::bounded::<WriterCommand>(capacity);
+        let queued = Arc::new(AtomicUsize::new(0));
+        let error = Arc::new(Mutex::new(None));
+        let queued_thread = Arc::clone(&queued);
+        let error_thread = Arc::clone(&error);
+
+        let handle = std::thread::spawn(move || {
+            let mut sink = sink;
+            for command in command_rx.iter() {
+                match command {
+                    WriterCommand::Write(bytes) => {
+                        queued_thread.fetch_sub(1, Ordering::SeqCst);
+                        if let Err(e) = sink.write_all(&bytes) {
+                            *error_thread.lock().unwrap() = Some(e.to_string());
+                        }
+                    }
+                    WriterCommand::Flush(reply) => {
+                        let result = sink.flush();
+                        if let Err(e) = &result {
+                            *error_thread.lock().unwrap() = Some(e.to_string());
+                        }
+                        let _ = reply.send(result);
+                    }
+                    WriterCommand::Shutdown(reply) => {
+                        let result = sink.finish();
+                        if let Err(e) = &result {
+                            *error_thread.lock().unwrap() = Some(e.to_string());
+                        }
+                        let _ = reply.send(result);
+                        return;
+                    }
+                }
+            }
+        });
+
+        Self {
+            command_tx,
+            handle: Some(handle),
+            queued,
+            error,
+            send_deadline,
+        }
+    }
+
+    /// Hand a rendered buffer to the background thread, blocking only until
+    /// the bounded channel has room (back-pressure) or `send_deadline`
+    /// elapses, whichever comes first.
+    fn write(&self, bytes: Vec<u8>) -> TrackingResult<()> {
+        self.check_error()?;
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        match self
+            .command_tx
+            .send_timeout(WriterCommand::Write(bytes), self.send_deadline)
+        {
+            Ok(()) => Ok(()),
+            Err(_) => {
+                self.queued.fetch_sub(1, Ordering::SeqCst);
+                Err(TrackingError::IoError(format!(
+                    "background writer did not accept a buffer within {:?} (channel full or thread stuck)",
+                    self.send_deadline
+                )))
+            }
+        }
+    }
+
+    /// Drain the queue and flush the sink, blocking until the background
+    /// thread confirms.
+    fn flush(&self) -> TrackingResult<()> {
+        self.check_error()?;
+        let (reply_tx, reply_rx) = crossbeam_channel::bounded(1);
+        self.command_tx
+            .send_timeout(WriterCommand::Flush(reply_tx), self.send_deadline)
+            .map_err(|_| {
+                TrackingError::IoError(format!(
+                    "background writer did not accept a flush request within {:?}",
+                    self.send_deadline
+                ))
+            })?;
+        match reply_rx.recv_timeout(self.send_deadline) {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(TrackingError::IoError(e.to_string())),
+            Err(_) => Err(TrackingError::IoError(
+                "background writer did not confirm flush in time".to_string(),
+            )),
+        }
+    }
+
+    /// Close the sink and join the background thread, returning the
+    /// compressed byte count. Must only be called once.
+    fn shutdown(mut self) -> TrackingResult<u64> {
+        self.check_error()?;
+        let (reply_tx, reply_rx) = crossbeam_channel::bounded(1);
+        self.command_tx
+            .send_timeout(WriterCommand::Shutdown(reply_tx), self.send_deadline)
+            .map_err(|_| {
+                TrackingError::IoError(format!(
+                    "background writer did not accept shutdown within {:?}",
+                    self.send_deadline
+                ))
+            })?;
+        let result = match reply_rx.recv_timeout(self.send_deadline) {
+            Ok(Ok(bytes)) => Ok(bytes),
+            Ok(Err(e)) => Err(TrackingError::IoError(e.to_string())),
+            Err(_) => Err(TrackingError::IoError(
+                "background writer did not confirm shutdown in time".to_string(),
+            )),
+        };
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        result
+    }
+
+    /// Number of buffers handed over but not yet written by the background
+    /// thread.
+    fn queued_buffers(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    fn check_error(&self) -> TrackingResult<()> {
+        if let Some(message) = self.error.lock().unwrap().clone() {
+            return Err(TrackingError::IoError(message));
+        }
+        Ok(())
+    }
+}
+
+/// Where `write_raw` sends rendered bytes: straight to the sink, or to a
+/// background I/O thread when `StreamingWriterConfig::non_blocking` is set.
+enum WriteChannel<W: Write> {
+    Direct(StreamingSink<W>),
+    Background(BackgroundWriter),
+}
 
 /// Configuration for streaming JSON writer
 #[derive(Debug, Clone)]
@@ -20,14 +464,79 @@ pub struct StreamingWriterConfig {
     pub enable_compression: bool,
     /// Compression level (1-9, default: 6)
     pub compression_level: u32,
+    /// Which streaming compressor to wrap the sink in when
+    /// `enable_compression` is set (default: [`CompressionFormat::Zstd`])
+    pub compression_format: CompressionFormat,
     /// Enable pretty printing (default: false for performance)
     pub pretty_print: bool,
     /// Maximum memory usage before flushing (default: 64MB)
     pub max_memory_before_flush: usize,
-    /// Enable non-blocking writes (default: true)
+    /// Enable non-blocking writes: a background thread owns the real sink
+    /// and `write_raw` only ever hands it owned buffers over a bounded
+    /// channel, so JSON serialization never blocks on disk/socket latency
+    /// (default: true)
     pub non_blocking: bool,
-    /// Chunk size for streaming large arrays (default: 1000)
+    /// Chunk size for streaming large arrays (default: 1000). Used as the
+    /// flush-boundary item count until the adaptive heuristic below has
+    /// enough records to estimate an average size, and as its fallback.
     pub array_chunk_size: usize,
+    /// When `non_blocking` is set, how long a buffer send may block on the
+    /// bounded channel before giving up and returning `TrackingError::IoError`
+    /// rather than hanging forever on a stuck background thread (default: 30s)
+    pub background_write_deadline: Duration,
+    /// Byte-size threshold for the adaptive array-chunking heuristic: once
+    /// the serialized size accumulated since the last flush boundary crosses
+    /// this, a new boundary is emitted regardless of item count (default: 64KB)
+    pub adaptive_chunk_byte_threshold: usize,
+    /// Minimum items observed since the last flush boundary before the
+    /// adaptive heuristic trusts its average-bytes-per-record estimate;
+    /// below this, chunking flushes on the fixed `array_chunk_size` item
+    /// count instead (default: 1500)
+    pub adaptive_chunk_min_check_count: usize,
+    /// How to rewrite non-finite (`NaN`/`Infinity`) floats encountered while
+    /// serializing streamed values, since JSON has no representation for
+    /// them (default: [`NonFiniteFloatPolicy::Null`])
+    pub non_finite_policy: NonFiniteFloatPolicy,
+    /// Whether to emit one monolithic JSON object or a stream of
+    /// self-describing frames a consumer can act on as they arrive
+    /// (default: [`OutputFraming::SingleObject`])
+    pub output_framing: OutputFraming,
+    /// Serialized array items at or above this size bypass the internal
+    /// buffer and go straight to the sink through a `write_vectored` call
+    /// alongside their separator, avoiding a second copy for large records.
+    /// Items smaller than this use the ordinary buffered path, since the
+    /// extra `write_vectored` call isn't worth it for a handful of bytes
+    /// (default: 8KB). Only takes effect in direct (non-`non_blocking`),
+    /// uncompressed mode -- compression reframes every byte regardless, and
+    /// the background channel already copies into an owned buffer to cross
+    /// threads, so there's no second copy to avoid in either case.
+    pub vectored_write_threshold: usize,
+    /// When set, bypass the buffered `W` sink entirely and write through a
+    /// memory-mapped backing file instead -- see
+    /// [`StreamingWriterConfigBuilder::mmap_backend`]. The `writer: W` passed
+    /// to [`StreamingJsonWriter::with_config`] is ignored in this mode.
+    /// (default: `None`)
+    pub mmap_backend: Option<MmapBackendConfig>,
+    /// When set, maintain a checkpoint sidecar file at this path recording
+    /// each top-level section's byte offset, length, and content hash as it
+    /// completes -- see [`StreamingJsonWriter::resume_from`]. (default:
+    /// `None`)
+    pub checkpoint_path: Option<PathBuf>,
+}
+
+/// Configures the memory-mapped output backend enabled by
+/// [`StreamingWriterConfig::mmap_backend`]. See
+/// [`StreamingWriterConfigBuilder::mmap_backend`].
+#[derive(Debug, Clone)]
+pub struct MmapBackendConfig {
+    /// Path to the backing file. Created (or truncated, if it already
+    /// exists) and pre-sized to `initial_size` bytes.
+    pub path: PathBuf,
+    /// Initial backing-file size in bytes, mapped up front.
+    pub initial_size: u64,
+    /// How far past the current mapping a write has to reach before the
+    /// backing file is grown and remapped, in bytes (default: 64MB).
+    pub grow_extent: u64,
 }
 
 impl Default for StreamingWriterConfig {
@@ -36,14 +545,72 @@ impl Default for StreamingWriterConfig {
             buffer_size: 256 * 1024, // 256KB
             enable_compression: false,
             compression_level: 6,
+            compression_format: CompressionFormat::Zstd,
             pretty_print: false,
             max_memory_before_flush: 64 * 1024 * 1024, // 64MB
             non_blocking: true,
             array_chunk_size: 1000,
+            background_write_deadline: Duration::from_secs(30),
+            adaptive_chunk_byte_threshold: 64 * 1024, // 64KB
+            adaptive_chunk_min_check_count: 1500,
+            non_finite_policy: NonFiniteFloatPolicy::Null,
+            output_framing: OutputFraming::SingleObject,
+            vectored_write_threshold: 8 * 1024, // 8KB
+            mmap_backend: None,
+            checkpoint_path: None,
         }
     }
 }
 
+/// Which streaming compressor wraps the sink when
+/// `StreamingWriterConfig::enable_compression` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompressionFormat {
+    /// Zstandard, via `zstd::stream::write::Encoder` (default -- generally
+    /// the better ratio/speed tradeoff of the two)
+    Zstd,
+    /// Gzip, via `flate2::write::GzEncoder`, for consumers that expect the
+    /// ubiquitous `.gz` format instead
+    Gzip,
+}
+
+/// How a frame's bytes are delimited in the output stream, when
+/// [`OutputFraming::Framed`] is selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameEncoding {
+    /// One frame per line: the frame envelope followed by `\n`. Easy to
+    /// tail/pipe through line-oriented tools, at the cost of requiring the
+    /// envelope JSON itself to never contain a literal newline (it doesn't,
+    /// since frames are always written compact regardless of
+    /// `pretty_print`).
+    Ndjson,
+    /// Each frame is prefixed with its byte length as an unsigned LEB128
+    /// varint, so a reader never has to scan for a delimiter -- it reads the
+    /// varint, then reads exactly that many bytes.
+    LengthPrefixed,
+}
+
+/// Whether [`StreamingJsonWriter`] emits one monolithic JSON object (the
+/// historical behavior) or a sequence of independent, self-describing
+/// frames that a consumer can decode and act on as each one lands -- useful
+/// for piping an export over a socket or tailing it live, since a reader
+/// doesn't have to wait for the whole document to arrive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutputFraming {
+    /// Write a single JSON object, exactly as before.
+    SingleObject,
+    /// Write each logical section (metadata, each analysis's summary and
+    /// array chunks, processing metrics, the final integrity trailer) as its
+    /// own frame, encoded as specified.
+    Framed(FrameEncoding),
+}
+
+impl Default for OutputFraming {
+    fn default() -> Self {
+        OutputFraming::SingleObject
+    }
+}
+
 /// Metadata for JSON export
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportMetadata {
@@ -57,10 +624,147 @@ pub struct ExportMetadata {
     pub optimization_level: String,
     /// Processing mode (sequential/parallel/streaming)
     pub processing_mode: String,
-    /// Data integrity hash
+    /// Identifier recorded when the header is written, before the body
+    /// exists to hash -- not a content hash. The authoritative content
+    /// digest is the `"data_integrity"` trailer [`StreamingJsonWriter::finalize`]
+    /// appends, surfaced on [`StreamingStats::content_hash`] and checkable
+    /// with [`verify_streaming_export_integrity`].
     pub data_integrity_hash: String,
     /// Export configuration used
     pub export_config: ExportConfig,
+    /// Host hardware profile, present only when captured via
+    /// [`ExportMetadata::with_system_profile`]. `#[serde(default)]` so
+    /// documents written before this field existed still deserialize.
+    #[serde(default)]
+    pub system_profile: Option<SystemProfile>,
+}
+
+/// A one-time snapshot of the host machine an export ran on, so throughput
+/// numbers recorded in `BatchProcessingMetrics` / `StreamingStats` can be
+/// normalized against the hardware that produced them -- the same
+/// bytes/sec means something very different on a laptop than on a 64-core
+/// server. `compute_score_ops_per_sec` and `memory_bandwidth_bytes_per_sec`
+/// come from short fixed-iteration micro-benchmarks run at measurement
+/// time, not OS-reported specs, since clock speed alone doesn't capture
+/// actual achievable throughput.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemProfile {
+    /// Logical CPU count (`num_cpus::get()`)
+    pub logical_cores: usize,
+    /// Physical CPU core count (`num_cpus::get_physical()`)
+    pub physical_cores: usize,
+    /// Total installed RAM, in bytes (best-effort; `0` where unavailable)
+    pub total_memory_bytes: u64,
+    /// RAM free for new allocations at measurement time, in bytes
+    /// (best-effort; `0` where unavailable)
+    pub available_memory_bytes: u64,
+    /// CPU model string, e.g. `/proc/cpuinfo`'s `model name` field
+    /// (best-effort -- `"unknown"` where the platform doesn't expose one)
+    pub cpu_model: String,
+    /// Synthetic compute throughput, in operations/sec, from a short
+    /// fixed-iteration float micro-benchmark
+    pub compute_score_ops_per_sec: f64,
+    /// Synthetic memory-bandwidth throughput, in bytes/sec, from a timed
+    /// memcpy-over-buffer micro-benchmark
+    pub memory_bandwidth_bytes_per_sec: f64,
+}
+
+impl SystemProfile {
+    /// Measure a fresh profile: CPU topology and RAM from the OS, plus
+    /// compute/bandwidth scores from micro-benchmarks run right now. Takes
+    /// low-single-digit milliseconds -- cheap enough to run per export, but
+    /// a caller producing many exports back-to-back on the same machine can
+    /// measure once and pass the same value into
+    /// [`ExportMetadata::with_system_profile_value`] to skip re-running it.
+    pub fn measure() -> Self {
+        let (total_memory_bytes, available_memory_bytes) = read_host_memory();
+        Self {
+            logical_cores: num_cpus::get(),
+            physical_cores: num_cpus::get_physical(),
+            total_memory_bytes,
+            available_memory_bytes,
+            cpu_model: read_cpu_model(),
+            compute_score_ops_per_sec: measure_compute_score(),
+            memory_bandwidth_bytes_per_sec: measure_memory_bandwidth_score(),
+        }
+    }
+}
+
+/// Best-effort total/available RAM, in bytes, from `/proc/meminfo` on
+/// Linux. Falls back to `(0, 0)` elsewhere or if the file can't be parsed,
+/// matching the rest of this crate's platform-resource probing.
+fn read_host_memory() -> (u64, u64) {
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(meminfo) = std::fs::read_to_string("/proc/meminfo") {
+            let mut total_kb = None;
+            let mut available_kb = None;
+            for line in meminfo.lines() {
+                if let Some(rest) = line.strip_prefix("MemTotal:") {
+                    total_kb = rest.split_whitespace().next().and_then(|s| s.parse::<u64>().ok());
+                } else if let Some(rest) = line.strip_prefix("MemAvailable:") {
+                    available_kb =
+                        rest.split_whitespace().next().and_then(|s| s.parse::<u64>().ok());
+                }
+            }
+            return (
+                total_kb.unwrap_or(0) * 1024,
+                available_kb.unwrap_or(0) * 1024,
+            );
+        }
+    }
+    (0, 0)
+}
+
+/// Best-effort CPU model string from `/proc/cpuinfo` on Linux, `"unknown"`
+/// elsewhere or if it can't be read.
+fn read_cpu_model() -> String {
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(cpuinfo) = std::fs::read_to_string("/proc/cpuinfo") {
+            if let Some(model) = cpuinfo.lines().find_map(|line| {
+                line.strip_prefix("model name")
+                    .and_then(|rest| rest.trim_start().strip_prefix(':'))
+                    .map(|name| name.trim().to_string())
+            }) {
+                return model;
+            }
+        }
+    }
+    "unknown".to_string()
+}
+
+/// Fixed-iteration float micro-benchmark: enough work to take low-single-
+/// digit milliseconds (so `Instant`'s resolution doesn't dominate the
+/// measurement) without making every export noticeably slower.
+fn measure_compute_score() -> f64 {
+    const ITERATIONS: u64 = 20_000_000;
+    let start = Instant::now();
+    let mut acc = 1.0f64;
+    for i in 1..=ITERATIONS {
+        acc = (acc + i as f64).sqrt();
+    }
+    // `acc` feeds the reported score, which keeps the loop from being
+    // optimized away as dead code.
+    let elapsed_secs = start.elapsed().as_secs_f64().max(f64::MIN_POSITIVE);
+    (ITERATIONS as f64 / elapsed_secs) + (acc - acc)
+}
+
+/// Timed memcpy-over-buffer micro-benchmark, reported as bytes copied per
+/// second.
+fn measure_memory_bandwidth_score() -> f64 {
+    const BUFFER_SIZE: usize = 4 * 1024 * 1024; // 4MB
+    const REPEATS: usize = 32;
+    let src = vec![0xABu8; BUFFER_SIZE];
+    let mut dst = vec![0u8; BUFFER_SIZE];
+    let start = Instant::now();
+    for _ in 0..REPEATS {
+        dst.copy_from_slice(&src);
+    }
+    let elapsed_secs = start.elapsed().as_secs_f64().max(f64::MIN_POSITIVE);
+    // `dst` feeds the reported score, which keeps the copy loop from being
+    // optimized away as dead code.
+    (BUFFER_SIZE * REPEATS) as f64 / elapsed_secs + (dst[0] as f64 - dst[0] as f64)
 }
 
 /// Export configuration information
@@ -95,12 +799,22 @@ pub struct StreamingStats {
     pub chunks_written: u32,
     /// Compression ratio (if compression enabled)
     pub compression_ratio: Option<f64>,
+    /// Buffers handed to the background writer but not yet drained. Always
+    /// 0 when `non_blocking` is disabled.
+    pub queued_buffers: usize,
+    /// Non-finite (`NaN`/`Infinity`) float values rewritten per
+    /// `config.non_finite_policy` while serializing streamed values.
+    pub non_finite_values_rewritten: usize,
+    /// Hex-encoded BLAKE3 digest over every body byte written, recorded by
+    /// [`StreamingJsonWriter::finalize`]. Empty until then.
+    pub content_hash: String,
 }
 
 /// Streaming JSON writer with buffering support
 pub struct StreamingJsonWriter<W: Write> {
-    /// Inner buffered writer
-    writer: BufWriter<W>,
+    /// Where rendered bytes go: directly to the sink, or to a background
+    /// I/O thread. `None` only after `finalize` has closed it.
+    channel: Option<WriteChannel<W>>,
     /// Configuration
     config: StreamingWriterConfig,
     /// Statistics
@@ -111,20 +825,97 @@ pub struct StreamingJsonWriter<W: Write> {
     current_memory_usage: usize,
     /// Whether the writer has been finalized
     finalized: bool,
+    /// Running BLAKE3 digest over every body byte handed to `write_raw`, so
+    /// `finalize` can record a real content hash instead of a timestamp.
+    content_hasher: blake3::Hasher,
+    /// Sidecar checkpoint index, present when `config.checkpoint_path` is
+    /// set. See [`Self::resume_from`].
+    checkpoint: Option<CheckpointWriter>,
+    /// Live only while a checkpointed top-level section is being written;
+    /// finalized into that section's `CheckpointEntry::hash` when it
+    /// completes.
+    section_hasher: Option<blake3::Hasher>,
 }
 
-impl<W: Write> StreamingJsonWriter<W> {
+impl<W: Write + Send + 'static> StreamingJsonWriter<W> {
     /// Create a new streaming JSON writer with default configuration
     pub fn new(writer: W) -> TrackingResult<Self> {
         Self::with_config(writer, StreamingWriterConfig::default())
     }
 
-    /// Create a new streaming JSON writer with custom configuration
+    /// Create a new streaming JSON writer with custom configuration. When
+    /// `config.non_blocking` is set, the real sink is handed off to a
+    /// background I/O thread and every write goes through a bounded channel
+    /// instead (see [`WriteChannel`]).
     pub fn with_config(writer: W, config: StreamingWriterConfig) -> TrackingResult<Self> {
+        if config.checkpoint_path.is_some() && config.enable_compression {
+            // A checkpoint only records byte offsets into the raw output
+            // stream, but compression writes one continuous frame across
+            // the whole file. Resuming from a mid-export checkpoint would
+            // truncate that frame and open a second, independent one over
+            // the same file -- neither a valid single frame nor valid
+            // concatenated frames. Reject the combination up front rather
+            // than silently producing an export that fails to decompress.
+            return Err(TrackingError::ConfigurationError(
+                "checkpoint_path is incompatible with enable_compression: a checkpoint resume \
+                 would truncate and restart the compression frame mid-stream, corrupting the \
+                 output"
+                    .to_string(),
+            ));
+        }
+
         let start_time = Instant::now();
 
-        // Create buffered writer
-        let buffered_writer = BufWriter::with_capacity(config.buffer_size, writer);
+        let sink = if let Some(mmap_config) = &config.mmap_backend {
+            // The mmap backend writes straight into its own backing file, so
+            // the caller's `writer: W` has nothing to do here.
+            let mapped = MappedExportBuffer::create(
+                &mmap_config.path,
+                mmap_config.initial_size,
+                mmap_config.grow_extent,
+            )
+            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+            StreamingSink::Mmap(mapped)
+        } else {
+            // Create buffered writer over a byte counter, so compressed output
+            // size can be measured regardless of whether compression is on.
+            let counting_writer = CountingWriter {
+                inner: writer,
+                bytes_written: 0,
+            };
+            let buffered_writer = BufWriter::with_capacity(config.buffer_size, counting_writer);
+
+            if config.enable_compression {
+                match config.compression_format {
+                    CompressionFormat::Zstd => {
+                        let encoder = zstd::stream::write::Encoder::new(
+                            buffered_writer,
+                            config.compression_level as i32,
+                        )
+                        .map_err(|e| TrackingError::IoError(e.to_string()))?;
+                        StreamingSink::ZstdCompressed(Box::new(encoder))
+                    }
+                    CompressionFormat::Gzip => {
+                        let level = flate2::Compression::new(config.compression_level.clamp(0, 9));
+                        let encoder = flate2::write::GzEncoder::new(buffered_writer, level);
+                        StreamingSink::GzipCompressed(Box::new(encoder))
+                    }
+                }
+            } else {
+                StreamingSink::Plain(buffered_writer)
+            }
+        };
+
+        let channel = if config.non_blocking {
+            let capacity = (config.max_memory_before_flush / config.buffer_size.max(1)).max(1);
+            WriteChannel::Background(BackgroundWriter::spawn(
+                sink,
+                capacity,
+                config.background_write_deadline,
+            ))
+        } else {
+            WriteChannel::Direct(sink)
+        };
 
         let stats = StreamingStats {
             bytes_written: 0,
@@ -134,27 +925,46 @@ impl<W: Write> StreamingJsonWriter<W> {
             peak_memory_usage: 0,
             chunks_written: 0,
             compression_ratio: None,
+            queued_buffers: 0,
+            non_finite_values_rewritten: 0,
+            content_hash: String::new(),
         };
 
+        let checkpoint = config
+            .checkpoint_path
+            .as_deref()
+            .map(CheckpointWriter::create)
+            .transpose()
+            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+
         Ok(Self {
-            writer: buffered_writer,
+            channel: Some(channel),
             config,
             stats,
             start_time,
             current_memory_usage: 0,
             finalized: false,
+            content_hasher: blake3::Hasher::new(),
+            checkpoint,
+            section_hasher: None,
         })
     }
 
     /// Write the JSON header with metadata
     pub fn write_unsafe_ffi_header(&mut self, metadata: &ExportMetadata) -> TrackingResult<()> {
+        self.write_checkpointed_section("unsafe_ffi_header", |writer| {
+            writer.write_unsafe_ffi_header_impl(metadata)
+        })
+    }
+
+    fn write_unsafe_ffi_header_impl(&mut self, metadata: &ExportMetadata) -> TrackingResult<()> {
         self.ensure_not_finalized()?;
 
-        let header_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(metadata)?
-        } else {
-            serde_json::to_string(metadata)?
-        };
+        let header_json = self.serialize_value(metadata)?;
+
+        if matches!(self.config.output_framing, OutputFraming::Framed(_)) {
+            return self.emit_frame("metadata", &header_json);
+        }
 
         self.write_raw("{\n")?;
         self.write_raw(&format!("\"metadata\": {header_json},\n"))?;
@@ -166,9 +976,32 @@ impl<W: Write> StreamingJsonWriter<W> {
     pub fn write_unsafe_allocations_stream(
         &mut self,
         data: &ProcessedUnsafeData,
+    ) -> TrackingResult<()> {
+        self.write_checkpointed_section("unsafe_allocations", |writer| {
+            writer.write_unsafe_allocations_stream_impl(data)
+        })
+    }
+
+    fn write_unsafe_allocations_stream_impl(
+        &mut self,
+        data: &ProcessedUnsafeData,
     ) -> TrackingResult<()> {
         self.ensure_not_finalized()?;
 
+        let risk_json = self.serialize_value(&data.risk_distribution)?;
+        let blocks_json = self.serialize_value(&data.unsafe_blocks)?;
+
+        if matches!(self.config.output_framing, OutputFraming::Framed(_)) {
+            let summary = format!(
+                "{{\"total_unsafe_allocations\": {}, \"total_memory\": {}, \"risk_distribution\": {risk_json}, \"unsafe_blocks\": {blocks_json}}}",
+                data.total_allocations, data.total_memory
+            );
+            self.emit_frame("unsafe_analysis.summary", &summary)?;
+            self.write_array_chunked(&data.allocations, "unsafe_analysis.allocations")?;
+            let metrics_json = self.serialize_value(&data.performance_metrics)?;
+            return self.emit_frame("unsafe_analysis.performance_metrics", &metrics_json);
+        }
+
         self.write_raw("\"unsafe_analysis\": {\n")?;
 
         // Write summary information
@@ -177,34 +1010,16 @@ impl<W: Write> StreamingJsonWriter<W> {
             data.total_allocations
         ))?;
         self.write_raw(&format!("\"total_memory\": {},\n", data.total_memory))?;
-
-        // Write risk distribution
-        let risk_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&data.risk_distribution)?
-        } else {
-            serde_json::to_string(&data.risk_distribution)?
-        };
         self.write_raw(&format!("\"risk_distribution\": {risk_json},\n"))?;
-
-        // Write unsafe blocks
-        let blocks_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&data.unsafe_blocks)?
-        } else {
-            serde_json::to_string(&data.unsafe_blocks)?
-        };
         self.write_raw(&format!("\"unsafe_blocks\": {blocks_json},\n"))?;
 
         // Stream allocations in chunks
         self.write_raw("\"allocations\": [\n")?;
-        self.write_array_chunked(&data.allocations)?;
+        self.write_array_chunked(&data.allocations, "unsafe_analysis.allocations")?;
         self.write_raw("],\n")?;
 
         // Write performance metrics
-        let metrics_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&data.performance_metrics)?
-        } else {
-            serde_json::to_string(&data.performance_metrics)?
-        };
+        let metrics_json = self.serialize_value(&data.performance_metrics)?;
         self.write_raw(&format!("\"performance_metrics\": {metrics_json}\n"))?;
 
         self.write_raw("},\n")?;
@@ -214,8 +1029,31 @@ impl<W: Write> StreamingJsonWriter<W> {
 
     /// Write FFI allocations data in streaming fashion
     pub fn write_ffi_allocations_stream(&mut self, data: &ProcessedFFIData) -> TrackingResult<()> {
+        self.write_checkpointed_section("ffi_allocations", |writer| {
+            writer.write_ffi_allocations_stream_impl(data)
+        })
+    }
+
+    fn write_ffi_allocations_stream_impl(
+        &mut self,
+        data: &ProcessedFFIData,
+    ) -> TrackingResult<()> {
         self.ensure_not_finalized()?;
 
+        let libraries_json = self.serialize_value(&data.libraries_involved)?;
+        let hook_stats_json = self.serialize_value(&data.hook_statistics)?;
+
+        if matches!(self.config.output_framing, OutputFraming::Framed(_)) {
+            let summary = format!(
+                "{{\"total_ffi_allocations\": {}, \"total_memory\": {}, \"libraries_involved\": {libraries_json}, \"hook_statistics\": {hook_stats_json}}}",
+                data.total_allocations, data.total_memory
+            );
+            self.emit_frame("ffi_analysis.summary", &summary)?;
+            self.write_array_chunked(&data.allocations, "ffi_analysis.allocations")?;
+            let metrics_json = self.serialize_value(&data.performance_metrics)?;
+            return self.emit_frame("ffi_analysis.performance_metrics", &metrics_json);
+        }
+
         self.write_raw("\"ffi_analysis\": {\n")?;
 
         // Write summary information
@@ -224,34 +1062,16 @@ impl<W: Write> StreamingJsonWriter<W> {
             data.total_allocations
         ))?;
         self.write_raw(&format!("\"total_memory\": {},\n", data.total_memory))?;
-
-        // Write libraries involved
-        let libraries_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&data.libraries_involved)?
-        } else {
-            serde_json::to_string(&data.libraries_involved)?
-        };
         self.write_raw(&format!("\"libraries_involved\": {libraries_json},\n"))?;
-
-        // Write hook statistics
-        let hook_stats_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&data.hook_statistics)?
-        } else {
-            serde_json::to_string(&data.hook_statistics)?
-        };
         self.write_raw(&format!("\"hook_statistics\": {hook_stats_json},\n"))?;
 
         // Stream allocations in chunks
         self.write_raw("\"allocations\": [\n")?;
-        self.write_array_chunked(&data.allocations)?;
+        self.write_array_chunked(&data.allocations, "ffi_analysis.allocations")?;
         self.write_raw("],\n")?;
 
         // Write performance metrics
-        let metrics_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&data.performance_metrics)?
-        } else {
-            serde_json::to_string(&data.performance_metrics)?
-        };
+        let metrics_json = self.serialize_value(&data.performance_metrics)?;
         self.write_raw(&format!("\"performance_metrics\": {metrics_json}\n"))?;
 
         self.write_raw("},\n")?;
@@ -263,9 +1083,32 @@ impl<W: Write> StreamingJsonWriter<W> {
     pub fn write_boundary_events_stream(
         &mut self,
         data: &ProcessedBoundaryData,
+    ) -> TrackingResult<()> {
+        self.write_checkpointed_section("boundary_events", |writer| {
+            writer.write_boundary_events_stream_impl(data)
+        })
+    }
+
+    fn write_boundary_events_stream_impl(
+        &mut self,
+        data: &ProcessedBoundaryData,
     ) -> TrackingResult<()> {
         self.ensure_not_finalized()?;
 
+        let patterns_json = self.serialize_value(&data.transfer_patterns)?;
+        let risk_json = self.serialize_value(&data.risk_analysis)?;
+
+        if matches!(self.config.output_framing, OutputFraming::Framed(_)) {
+            let summary = format!(
+                "{{\"total_boundary_crossings\": {}, \"transfer_patterns\": {patterns_json}, \"risk_analysis\": {risk_json}}}",
+                data.total_crossings
+            );
+            self.emit_frame("boundary_analysis.summary", &summary)?;
+            self.write_array_chunked(&data.events, "boundary_analysis.events")?;
+            let impact_json = self.serialize_value(&data.performance_impact)?;
+            return self.emit_frame("boundary_analysis.performance_impact", &impact_json);
+        }
+
         self.write_raw("\"boundary_analysis\": {\n")?;
 
         // Write summary information
@@ -273,34 +1116,16 @@ impl<W: Write> StreamingJsonWriter<W> {
             "\"total_boundary_crossings\": {},\n",
             data.total_crossings
         ))?;
-
-        // Write transfer patterns
-        let patterns_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&data.transfer_patterns)?
-        } else {
-            serde_json::to_string(&data.transfer_patterns)?
-        };
         self.write_raw(&format!("\"transfer_patterns\": {patterns_json},\n"))?;
-
-        // Write risk analysis
-        let risk_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&data.risk_analysis)?
-        } else {
-            serde_json::to_string(&data.risk_analysis)?
-        };
         self.write_raw(&format!("\"risk_analysis\": {risk_json},\n"))?;
 
         // Stream events in chunks
         self.write_raw("\"events\": [\n")?;
-        self.write_array_chunked(&data.events)?;
+        self.write_array_chunked(&data.events, "boundary_analysis.events")?;
         self.write_raw("],\n")?;
 
         // Write performance impact
-        let impact_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&data.performance_impact)?
-        } else {
-            serde_json::to_string(&data.performance_impact)?
-        };
+        let impact_json = self.serialize_value(&data.performance_impact)?;
         self.write_raw(&format!("\"performance_impact\": {impact_json}\n"))?;
 
         self.write_raw("},\n")?;
@@ -313,23 +1138,37 @@ impl<W: Write> StreamingJsonWriter<W> {
         &mut self,
         violations: &[T],
     ) -> TrackingResult<()> {
-        self.ensure_not_finalized()?;
+        self.write_checkpointed_section("safety_violations", |writer| {
+            writer.write_safety_violations_stream_impl(violations)
+        })
+    }
 
-        self.write_raw("\"safety_violations\": {\n")?;
-        self.write_raw(&format!("\"total_violations\": {},\n", violations.len()))?;
+    fn write_safety_violations_stream_impl<T: Serialize>(
+        &mut self,
+        violations: &[T],
+    ) -> TrackingResult<()> {
+        self.ensure_not_finalized()?;
 
         // Calculate severity breakdown
         let severity_breakdown = self.calculate_severity_breakdown(violations);
-        let severity_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(&severity_breakdown)?
-        } else {
-            serde_json::to_string(&severity_breakdown)?
-        };
+        let severity_json = self.serialize_value(&severity_breakdown)?;
+
+        if matches!(self.config.output_framing, OutputFraming::Framed(_)) {
+            let summary = format!(
+                "{{\"total_violations\": {}, \"severity_breakdown\": {severity_json}}}",
+                violations.len()
+            );
+            self.emit_frame("safety_violations.summary", &summary)?;
+            return self.write_array_chunked(violations, "safety_violations.violations");
+        }
+
+        self.write_raw("\"safety_violations\": {\n")?;
+        self.write_raw(&format!("\"total_violations\": {},\n", violations.len()))?;
         self.write_raw(&format!("\"severity_breakdown\": {severity_json},\n"))?;
 
         // Stream violations in chunks
         self.write_raw("\"violations\": [\n")?;
-        self.write_array_chunked(violations)?;
+        self.write_array_chunked(violations, "safety_violations.violations")?;
         self.write_raw("]\n")?;
 
         self.write_raw("},\n")?;
@@ -341,14 +1180,23 @@ impl<W: Write> StreamingJsonWriter<W> {
     pub fn write_processing_metrics(
         &mut self,
         metrics: &BatchProcessingMetrics,
+    ) -> TrackingResult<()> {
+        self.write_checkpointed_section("processing_metrics", |writer| {
+            writer.write_processing_metrics_impl(metrics)
+        })
+    }
+
+    fn write_processing_metrics_impl(
+        &mut self,
+        metrics: &BatchProcessingMetrics,
     ) -> TrackingResult<()> {
         self.ensure_not_finalized()?;
 
-        let metrics_json = if self.config.pretty_print {
-            serde_json::to_string_pretty(metrics)?
-        } else {
-            serde_json::to_string(metrics)?
-        };
+        let metrics_json = self.serialize_value(metrics)?;
+
+        if matches!(self.config.output_framing, OutputFraming::Framed(_)) {
+            return self.emit_frame("processing_metrics", &metrics_json);
+        }
 
         self.write_raw("\"processing_metrics\": ")?;
         self.write_raw(&metrics_json)?;
@@ -356,17 +1204,79 @@ impl<W: Write> StreamingJsonWriter<W> {
         Ok(())
     }
 
-    /// Finalize the JSON document and flush all buffers
+    /// Finalize the JSON document, drain and close the background writer (if
+    /// any) exactly once, close the compression frame (if any), and flush
+    /// all buffers. Propagates any I/O error observed on the background
+    /// thread.
     pub fn finalize(&mut self) -> TrackingResult<StreamingStats> {
         if self.finalized {
             return Ok(self.stats.clone());
         }
 
-        // Close the main JSON object
-        self.write_raw("\n}\n")?;
+        // Record the digest over every body byte written so far, before the
+        // trailer itself (which isn't, and can't be, covered by its own
+        // hash) goes out. `body_bytes` lets a verifier re-slice the exact
+        // prefix that was hashed without guessing at trailing whitespace.
+        let content_hash = self.content_hasher.finalize().to_hex().to_string();
+        let body_bytes = self.stats.bytes_written;
+
+        // When checkpointing is on, fold the sidecar's per-section offsets
+        // into the trailer too, so a reader can seek straight to a section
+        // (e.g. the FFI allocations array) without parsing the rest.
+        let section_offsets_json = self.checkpoint.as_ref().map(|checkpoint| {
+            let offsets: std::collections::BTreeMap<&str, serde_json::Value> = checkpoint
+                .entries
+                .iter()
+                .map(|entry| {
+                    (
+                        entry.section.as_str(),
+                        serde_json::json!({"offset": entry.offset, "length": entry.length}),
+                    )
+                })
+                .collect();
+            serde_json::to_string(&offsets).expect("section offsets always serialize")
+        });
+        let trailer_payload = match &section_offsets_json {
+            Some(offsets_json) => format!(
+                "{{\"hash\": \"{content_hash}\", \"body_bytes\": {body_bytes}, \"section_offsets\": {offsets_json}}}"
+            ),
+            None => format!("{{\"hash\": \"{content_hash}\", \"body_bytes\": {body_bytes}}}"),
+        };
+
+        match self.config.output_framing {
+            OutputFraming::SingleObject => {
+                self.write_raw(&format!(",\n\"data_integrity\": {trailer_payload}\n"))?;
+                // Close the main JSON object
+                self.write_raw("}\n")?;
+            }
+            OutputFraming::Framed(_) => {
+                // The same literal `"hash": "..."`/`"body_bytes": N` substrings
+                // appear inside the frame's `payload` field, so
+                // `verify_streaming_export_integrity`'s marker scan finds them
+                // here too without needing to know about frames at all.
+                self.emit_frame("data_integrity", &trailer_payload)?;
+            }
+        }
+        self.stats.content_hash = content_hash;
+
+        let channel = self
+            .channel
+            .take()
+            .expect("channel is only taken here, guarded by `finalized`");
+        let compressed_bytes = match channel {
+            WriteChannel::Direct(sink) => sink
+                .finish()
+                .map_err(|e| TrackingError::IoError(e.to_string()))?,
+            WriteChannel::Background(background) => background.shutdown()?,
+        };
+        self.stats.flush_count += 1;
+        self.stats.queued_buffers = 0;
 
-        // Flush all buffers
-        self.flush()?;
+        self.stats.compression_ratio = if self.config.enable_compression {
+            Some(compressed_bytes as f64 / self.stats.bytes_written.max(1) as f64)
+        } else {
+            None
+        };
 
         // Calculate final statistics
         let total_time = self.start_time.elapsed();
@@ -377,6 +1287,12 @@ impl<W: Write> StreamingJsonWriter<W> {
             0.0
         };
 
+        if let Some(checkpoint) = &self.checkpoint {
+            checkpoint
+                .sync()
+                .map_err(|e| TrackingError::IoError(e.to_string()))?;
+        }
+
         self.finalized = true;
         Ok(self.stats.clone())
     }
@@ -386,81 +1302,641 @@ impl<W: Write> StreamingJsonWriter<W> {
         &self.stats
     }
 
-    /// Force flush the writer
+    /// Force flush the writer. In direct mode, on a compressing writer this
+    /// emits a sync-flush (whichever `CompressionFormat` is configured) so
+    /// everything written so far becomes decodable without closing the
+    /// frame. In non-blocking mode, this blocks until the background thread
+    /// has drained its queue and confirmed the flush. Also fsyncs the
+    /// checkpoint sidecar, when one is configured, so a crash right after a
+    /// flush never leaves the checkpoint believing a section completed that
+    /// the disk doesn't actually have yet.
     pub fn flush(&mut self) -> TrackingResult<()> {
-        self.writer
-            .flush()
-            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+        match self.channel_mut() {
+            WriteChannel::Direct(sink) => {
+                sink.flush()
+                    .map_err(|e| TrackingError::IoError(e.to_string()))?;
+            }
+            WriteChannel::Background(background) => {
+                background.flush()?;
+                self.stats.queued_buffers = background.queued_buffers();
+            }
+        }
+        if let Some(checkpoint) = &self.checkpoint {
+            checkpoint
+                .sync()
+                .map_err(|e| TrackingError::IoError(e.to_string()))?;
+        }
         self.stats.flush_count += 1;
         Ok(())
     }
 }
 
-// Private implementation methods
-impl<W: Write> StreamingJsonWriter<W> {
-    /// Write raw string data
-    fn write_raw(&mut self, data: &str) -> TrackingResult<()> {
-        let bytes = data.as_bytes();
-        self.writer
-            .write_all(bytes)
-            .map_err(|e| TrackingError::IoError(e.to_string()))?;
-
-        self.stats.bytes_written += bytes.len() as u64;
-        self.current_memory_usage += bytes.len();
-
-        // Update peak memory usage
-        if self.current_memory_usage > self.stats.peak_memory_usage {
-            self.stats.peak_memory_usage = self.current_memory_usage;
+const DATA_INTEGRITY_HASH_MARKER: &str = "\"data_integrity\": {\"hash\": \"";
+const DATA_INTEGRITY_BODY_BYTES_MARKER: &str = "\"body_bytes\": ";
+
+/// Re-read a document written by [`StreamingJsonWriter::finalize`] and
+/// confirm its `"data_integrity"` trailer still matches a fresh BLAKE3
+/// digest over the body bytes that preceded it. Pass the
+/// [`CompressionFormat`] the export was written with if
+/// `StreamingWriterConfig::enable_compression` was set, or `None` for an
+/// uncompressed document, matching how it was originally configured.
+///
+/// Returns `Ok(true)` if the digest matches, `Ok(false)` if it doesn't
+/// (truncation or corruption), and `Err` if the document has no
+/// `"data_integrity"` trailer to check at all.
+pub fn verify_streaming_export_integrity(
+    raw_bytes: &[u8],
+    compression: Option<CompressionFormat>,
+) -> TrackingResult<bool> {
+    let document = match compression {
+        Some(CompressionFormat::Zstd) => {
+            zstd::decode_all(raw_bytes).map_err(|e| TrackingError::IoError(e.to_string()))?
         }
-
-        // Flush if memory usage exceeds threshold
-        if self.current_memory_usage >= self.config.max_memory_before_flush {
-            self.flush()?;
-            self.current_memory_usage = 0;
+        Some(CompressionFormat::Gzip) => {
+            let mut decoder = flate2::read::GzDecoder::new(raw_bytes);
+            let mut decoded = Vec::new();
+            decoder
+                .read_to_end(&mut decoded)
+                .map_err(|e| TrackingError::IoError(e.to_string()))?;
+            decoded
         }
+        None => raw_bytes.to_vec(),
+    };
 
-        Ok(())
+    let hash_marker_pos = find_subslice(&document, DATA_INTEGRITY_HASH_MARKER.as_bytes())
+        .ok_or_else(|| {
+            TrackingError::InvalidOperation(
+                "document has no \"data_integrity\" trailer to verify".to_string(),
+            )
+        })?;
+    let hash_start = hash_marker_pos + DATA_INTEGRITY_HASH_MARKER.len();
+    let hash_end = hash_start
+        + document[hash_start..]
+            .iter()
+            .position(|&b| b == b'"')
+            .ok_or_else(|| {
+                TrackingError::InvalidOperation("malformed data_integrity hash field".to_string())
+            })?;
+    let recorded_hash = std::str::from_utf8(&document[hash_start..hash_end])
+        .map_err(|e| TrackingError::InvalidOperation(e.to_string()))?;
+
+    let body_bytes_marker_pos = find_subslice(
+        &document[hash_end..],
+        DATA_INTEGRITY_BODY_BYTES_MARKER.as_bytes(),
+    )
+    .map(|offset| hash_end + offset)
+    .ok_or_else(|| {
+        TrackingError::InvalidOperation("malformed data_integrity body_bytes field".to_string())
+    })?;
+    let body_bytes_start = body_bytes_marker_pos + DATA_INTEGRITY_BODY_BYTES_MARKER.len();
+    let body_bytes_end = body_bytes_start
+        + document[body_bytes_start..]
+            .iter()
+            .position(|&b| !b.is_ascii_digit())
+            .ok_or_else(|| {
+                TrackingError::InvalidOperation(
+                    "malformed data_integrity body_bytes field".to_string(),
+                )
+            })?;
+    let body_bytes: usize = std::str::from_utf8(&document[body_bytes_start..body_bytes_end])
+        .map_err(|e| TrackingError::InvalidOperation(e.to_string()))?
+        .parse()
+        .map_err(|e: std::num::ParseIntError| TrackingError::InvalidOperation(e.to_string()))?;
+
+    if body_bytes > document.len() {
+        return Err(TrackingError::InvalidOperation(
+            "recorded body_bytes exceeds document length -- export is truncated".to_string(),
+        ));
     }
 
-    /// Write an array in chunks to avoid memory issues
-    fn write_array_chunked<T: Serialize>(&mut self, items: &[T]) -> TrackingResult<()> {
-        let chunk_size = self.config.array_chunk_size;
-        let total_chunks = items.len().div_ceil(chunk_size);
-
-        for (chunk_idx, chunk) in items.chunks(chunk_size).enumerate() {
-            for (item_idx, item) in chunk.iter().enumerate() {
-                let item_json = if self.config.pretty_print {
-                    serde_json::to_string_pretty(item)?
-                } else {
-                    serde_json::to_string(item)?
-                };
+    let recomputed = blake3::hash(&document[..body_bytes]).to_hex().to_string();
+    Ok(recomputed == recorded_hash)
+}
 
-                self.write_raw(&item_json)?;
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
 
-                // Add comma if not the last item
-                let is_last_item_in_chunk = item_idx == chunk.len() - 1;
-                let is_last_chunk = chunk_idx == total_chunks - 1;
+/// Write every byte of `bufs`, in order, using as few `write_vectored`
+/// calls as the underlying writer allows. `write_vectored` is permitted to
+/// write fewer bytes than the sum of all slices (and the standard library's
+/// default implementation only ever writes the first non-empty one), so
+/// this loops, advancing past however many bytes actually landed each time,
+/// until nothing remains.
+fn write_vectored_exact(writer: &mut impl Write, bufs: Vec<&[u8]>) -> std::io::Result<()> {
+    let mut bufs: Vec<&[u8]> = bufs.into_iter().filter(|b| !b.is_empty()).collect();
+    while !bufs.is_empty() {
+        let io_slices: Vec<std::io::IoSlice> =
+            bufs.iter().map(|b| std::io::IoSlice::new(b)).collect();
+        let mut written = writer.write_vectored(&io_slices)?;
+        if written == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::WriteZero,
+                "write_vectored wrote 0 bytes",
+            ));
+        }
+        let mut fully_consumed = 0;
+        while fully_consumed < bufs.len() && written >= bufs[fully_consumed].len() {
+            written -= bufs[fully_consumed].len();
+            fully_consumed += 1;
+        }
+        bufs.drain(0..fully_consumed);
+        if written > 0 {
+            bufs[0] = &bufs[0][written..];
+        }
+    }
+    Ok(())
+}
 
-                if !is_last_item_in_chunk || !is_last_chunk {
-                    self.write_raw(",")?;
-                }
+/// Encode `value` as an unsigned LEB128 varint, appending it to `out`. Used
+/// to length-prefix frames under [`FrameEncoding::LengthPrefixed`] so a
+/// reader knows exactly how many bytes to read for the next frame without
+/// scanning for a delimiter.
+fn encode_varint(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            out.push(byte | 0x80);
+        } else {
+            out.push(byte);
+            break;
+        }
+    }
+}
+
+impl StreamingJsonWriter<std::fs::File> {
+    /// Resume a checkpointed export: read the checkpoint sidecar at
+    /// `config.checkpoint_path`, validate each recorded section's bytes in
+    /// `output_path` against its stored hash, and return a writer seeked
+    /// past the last valid, contiguous section along with the canonical
+    /// section names (in emission order, from [`CHECKPOINT_SECTIONS`]) that
+    /// still need to be re-driven. The caller calls only those `write_*`
+    /// methods, then `finalize`, as usual -- already-complete sections are
+    /// neither re-read nor re-written. Resuming requires a real, seekable
+    /// backing file, so this is only available for `File`-backed writers.
+    pub fn resume_from(
+        output_path: impl AsRef<Path>,
+        config: StreamingWriterConfig,
+    ) -> TrackingResult<(Self, Vec<&'static str>)> {
+        let checkpoint_path = config.checkpoint_path.clone().ok_or_else(|| {
+            TrackingError::IoError("resume_from requires config.checkpoint_path".to_string())
+        })?;
+
+        let checkpoint_contents = std::fs::read_to_string(&checkpoint_path).unwrap_or_default();
+        let mut by_section = std::collections::HashMap::new();
+        for line in checkpoint_contents.lines().filter(|l| !l.trim().is_empty()) {
+            let entry: CheckpointEntry = serde_json::from_str(line)
+                .map_err(|e| TrackingError::SerializationError(e.to_string()))?;
+            by_section.insert(entry.section.clone(), entry);
+        }
+
+        let output_bytes = std::fs::read(output_path.as_ref())
+            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+
+        let mut resume_offset: u64 = 0;
+        let mut valid_entries = Vec::new();
+        let mut remaining = Vec::new();
+        for &section in CHECKPOINT_SECTIONS.iter() {
+            if !remaining.is_empty() {
+                // Once one section is missing/invalid, every later section
+                // has to be re-driven too: its recorded offset assumed the
+                // earlier one finished cleanly, which it didn't.
+                remaining.push(section);
+                continue;
+            }
+            let Some(entry) = by_section.get(section) else {
+                remaining.push(section);
+                continue;
+            };
+            let start = entry.offset as usize;
+            let end = start + entry.length as usize;
+            let matches = entry.offset == resume_offset
+                && output_bytes
+                    .get(start..end)
+                    .map(|slice| blake3::hash(slice).to_hex().to_string())
+                    == Some(entry.hash.clone());
+            if !matches {
+                remaining.push(section);
+                continue;
+            }
+            resume_offset = end as u64;
+            valid_entries.push(entry.clone());
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(output_path.as_ref())
+            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+        file.set_len(resume_offset)
+            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+        file.seek(SeekFrom::Start(resume_offset))
+            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+
+        let mut writer = StreamingJsonWriter::with_config(file, config)?;
+        writer.stats.bytes_written = resume_offset;
+        writer.content_hasher = blake3::Hasher::new();
+        writer
+            .content_hasher
+            .update(&output_bytes[..resume_offset as usize]);
+        writer.checkpoint = Some(
+            CheckpointWriter::reopen(&checkpoint_path, valid_entries)
+                .map_err(|e| TrackingError::IoError(e.to_string()))?,
+        );
+
+        Ok((writer, remaining))
+    }
+}
+
+// Private implementation methods
+impl<W: Write + Send + 'static> StreamingJsonWriter<W> {
+    /// Serialize `value` to a JSON string honoring `config.pretty_print` and
+    /// `config.non_finite_policy`, tallying any rewritten non-finite floats
+    /// into `stats.non_finite_values_rewritten`.
+    fn serialize_value<T: Serialize>(&mut self, value: &T) -> TrackingResult<String> {
+        let (json, rewritten) = non_finite_json::to_string_with_policy(
+            value,
+            self.config.pretty_print,
+            self.config.non_finite_policy,
+        )?;
+        self.stats.non_finite_values_rewritten += rewritten;
+        Ok(json)
+    }
+
+    /// Run a top-level section's write (one of [`CHECKPOINT_SECTIONS`])
+    /// through `write`, and, when `config.checkpoint_path` is set, record
+    /// its byte range and content hash in the checkpoint sidecar once it
+    /// completes successfully. A no-op wrapper when checkpointing is off.
+    fn write_checkpointed_section(
+        &mut self,
+        section: &'static str,
+        write: impl FnOnce(&mut Self) -> TrackingResult<()>,
+    ) -> TrackingResult<()> {
+        if self.checkpoint.is_none() {
+            return write(self);
+        }
+
+        let offset = self.stats.bytes_written;
+        self.section_hasher = Some(blake3::Hasher::new());
+        let result = write(self);
+        let section_hasher = self.section_hasher.take();
+
+        if result.is_ok() {
+            if let Some(hasher) = section_hasher {
+                let entry = CheckpointEntry {
+                    section: section.to_string(),
+                    offset,
+                    length: self.stats.bytes_written - offset,
+                    hash: hasher.finalize().to_hex().to_string(),
+                };
+                self.checkpoint
+                    .as_mut()
+                    .expect("checked is_some above")
+                    .record(entry)
+                    .map_err(|e| TrackingError::IoError(e.to_string()))?;
+            }
+        }
+
+        result
+    }
 
-                if self.config.pretty_print {
-                    self.write_raw("\n")?;
+    /// The active output channel. Only `None` after `finalize` has run,
+    /// which is guarded by `ensure_not_finalized` on every public write
+    /// method.
+    fn channel_mut(&mut self) -> &mut WriteChannel<W> {
+        self.channel
+            .as_mut()
+            .expect("channel is only taken in finalize, after which no writes are possible")
+    }
+
+    /// Write raw string data. See [`Self::write_raw_bytes`].
+    fn write_raw(&mut self, data: &str) -> TrackingResult<()> {
+        self.write_raw_bytes(data.as_bytes())
+    }
+
+    /// Write raw bytes. Goes straight to the sink in direct mode, or is
+    /// handed to the background writer (subject to its bounded back-pressure
+    /// and drop-deadline) in non-blocking mode. Takes raw bytes rather than
+    /// `&str` so framed mode's varint length prefixes, which aren't valid
+    /// UTF-8 text, can go through the same path.
+    fn write_raw_bytes(&mut self, bytes: &[u8]) -> TrackingResult<()> {
+        let len = bytes.len();
+        self.content_hasher.update(bytes);
+        if let Some(section_hasher) = self.section_hasher.as_mut() {
+            section_hasher.update(bytes);
+        }
+
+        match self.channel_mut() {
+            WriteChannel::Direct(StreamingSink::Mmap(mapped)) => {
+                mapped
+                    .write_all(bytes)
+                    .map_err(|e| TrackingError::IoError(e.to_string()))?;
+                // There's no intermediate buffer in this mode, so "peak
+                // memory usage" is the mapped region's own high-water mark
+                // rather than an unflushed-bytes count.
+                let mapped_len = mapped.mapped_len();
+                self.stats.bytes_written += len as u64;
+                if mapped_len > self.stats.peak_memory_usage as u64 {
+                    self.stats.peak_memory_usage = mapped_len as usize;
                 }
+                return Ok(());
+            }
+            WriteChannel::Direct(sink) => {
+                sink.write_all(bytes)
+                    .map_err(|e| TrackingError::IoError(e.to_string()))?;
             }
+            WriteChannel::Background(background) => {
+                background.write(bytes.to_vec())?;
+            }
+        }
 
-            self.stats.chunks_written += 1;
+        self.stats.bytes_written += len as u64;
+        self.current_memory_usage += len;
+
+        // Update peak memory usage
+        if self.current_memory_usage > self.stats.peak_memory_usage {
+            self.stats.peak_memory_usage = self.current_memory_usage;
+        }
+
+        if let Some(WriteChannel::Background(background)) = &self.channel {
+            self.stats.queued_buffers = background.queued_buffers();
+        }
+
+        // Direct mode flushes eagerly past the memory threshold, mirroring
+        // the historical behavior. Background mode relies on the bounded
+        // channel's back-pressure instead of an explicit flush here.
+        if matches!(&self.channel, Some(WriteChannel::Direct(_)))
+            && self.current_memory_usage >= self.config.max_memory_before_flush
+        {
+            self.flush()?;
+            self.current_memory_usage = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Write `payload` (a serialized array item) followed by `trailing`
+    /// (its comma/newline separator). When `payload` is at or above
+    /// `config.vectored_write_threshold` and the sink is direct and
+    /// uncompressed, this flushes whatever framing is already buffered --
+    /// so it isn't reordered behind what's about to go out -- and then
+    /// writes `[payload, trailing]` to the real underlying writer through a
+    /// single `write_vectored` call, skipping the usual copy into the
+    /// `BufWriter`. Every other combination (compressed, background,
+    /// below-threshold items) falls back to the ordinary buffered path,
+    /// since there's either no real syscall to batch or no way to reach it.
+    fn write_vectored_item(&mut self, payload: &[u8], trailing: &[u8]) -> TrackingResult<()> {
+        let use_vectored = payload.len() >= self.config.vectored_write_threshold
+            && matches!(
+                &self.channel,
+                Some(WriteChannel::Direct(StreamingSink::Plain(_)))
+            );
+
+        if !use_vectored {
+            self.write_raw_bytes(payload)?;
+            if !trailing.is_empty() {
+                self.write_raw_bytes(trailing)?;
+            }
+            return Ok(());
+        }
+
+        let WriteChannel::Direct(StreamingSink::Plain(buf_writer)) = self.channel_mut() else {
+            unreachable!("matched above")
+        };
+        buf_writer
+            .flush()
+            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+        write_vectored_exact(buf_writer.get_mut(), vec![payload, trailing])
+            .map_err(|e| TrackingError::IoError(e.to_string()))?;
+
+        self.content_hasher.update(payload);
+        self.content_hasher.update(trailing);
+        let len = payload.len() + trailing.len();
+        self.stats.bytes_written += len as u64;
+        // Bypassed the buffer entirely, so there's nothing pending to count
+        // toward the eager-flush memory threshold.
+        Ok(())
+    }
+
+    /// Emit one frame of `config.output_framing == Framed(_)` output: an
+    /// envelope carrying `frame_type` as a short tag and `payload_json` as
+    /// its already-serialized body, delimited per the selected
+    /// [`FrameEncoding`]. The envelope shape (`{"frame": ..., "payload":
+    /// ...}`) is deliberately the same regardless of encoding, so a reader
+    /// can reassemble the original document by tag regardless of which
+    /// encoding produced the stream. Only meaningful when framing is
+    /// enabled; callers are expected to check that first.
+    fn emit_frame(&mut self, frame_type: &str, payload_json: &str) -> TrackingResult<()> {
+        let encoding = match self.config.output_framing {
+            OutputFraming::SingleObject => {
+                debug_assert!(
+                    false,
+                    "emit_frame called while output_framing is SingleObject"
+                );
+                return Ok(());
+            }
+            OutputFraming::Framed(encoding) => encoding,
+        };
+
+        let envelope = format!("{{\"frame\": \"{frame_type}\", \"payload\": {payload_json}}}");
+        match encoding {
+            FrameEncoding::Ndjson => {
+                self.write_raw(&envelope)?;
+                self.write_raw("\n")
+            }
+            FrameEncoding::LengthPrefixed => {
+                let mut length_prefix = Vec::new();
+                encode_varint(envelope.len() as u64, &mut length_prefix);
+                self.write_raw_bytes(&length_prefix)?;
+                self.write_raw_bytes(envelope.as_bytes())
+            }
+        }
+    }
+
+    /// Write an array in chunks to avoid memory issues
+    /// Write an array in adaptive, byte-size-aware chunks.
+    ///
+    /// Ported from Apache TsFile's chunk-writer page-sizing heuristic: a
+    /// fixed item-count boundary behaves badly once item sizes vary wildly
+    /// (tiny boundary events next to large unsafe allocations with stack
+    /// traces), so this tracks the serialized byte size accumulated since
+    /// the last flush boundary instead. Before
+    /// `adaptive_chunk_min_check_count` items have been seen there isn't
+    /// enough data to trust an average record size, so it falls back to the
+    /// historical fixed `array_chunk_size` item-count boundary. Past that,
+    /// it estimates bytes/record and predicts how many more records fit
+    /// before `adaptive_chunk_byte_threshold`, re-checking only at that
+    /// predicted item count rather than per item. Comma placement only
+    /// depends on whether an item is the last one overall, since a flush
+    /// boundary is purely an I/O checkpoint and never splits the JSON array.
+    /// `frame_tag` is only used when `config.output_framing` is
+    /// `Framed(_)`; direct callers in `SingleObject` mode may pass any
+    /// descriptive tag since it's ignored. Each item's write goes through
+    /// [`Self::write_vectored_item`], which transparently upgrades to a
+    /// vectored write for items at or above `config.vectored_write_threshold`.
+    fn write_array_chunked<T: Serialize>(
+        &mut self,
+        items: &[T],
+        frame_tag: &str,
+    ) -> TrackingResult<()> {
+        if matches!(self.config.output_framing, OutputFraming::Framed(_)) {
+            return self.write_array_chunked_framed(items, frame_tag);
+        }
+
+        let total_items = items.len();
+        let mut items_since_boundary: usize = 0;
+        let mut bytes_since_boundary: usize = 0;
+        let mut next_check_at: usize = self.config.array_chunk_size;
+
+        for (item_idx, item) in items.iter().enumerate() {
+            let item_json = self.serialize_value(item)?;
+            let is_last_item = item_idx == total_items - 1;
+
+            let mut trailing = String::new();
+            if !is_last_item {
+                trailing.push(',');
+            }
+            if self.config.pretty_print {
+                trailing.push('\n');
+            }
+            self.write_vectored_item(item_json.as_bytes(), trailing.as_bytes())?;
+            items_since_boundary += 1;
+            bytes_since_boundary += item_json.len();
+
+            if is_last_item {
+                // Always close out the final boundary, even if the adaptive
+                // threshold was never crossed.
+                self.stats.chunks_written += 1;
+                continue;
+            }
+
+            if items_since_boundary < next_check_at {
+                continue;
+            }
+
+            if items_since_boundary < self.config.adaptive_chunk_min_check_count {
+                // Not enough records yet to estimate an average size --
+                // fall back to the fixed item-count boundary.
+                self.stats.chunks_written += 1;
+                self.flush()?;
+                items_since_boundary = 0;
+                bytes_since_boundary = 0;
+                next_check_at = self.config.array_chunk_size;
+                continue;
+            }
 
-            // Flush after each chunk if non-blocking is enabled
-            if self.config.non_blocking {
+            let measured_bytes = self.estimate_chunk_bytes(bytes_since_boundary);
+            if measured_bytes >= self.config.adaptive_chunk_byte_threshold {
+                self.stats.chunks_written += 1;
                 self.flush()?;
+                items_since_boundary = 0;
+                bytes_since_boundary = 0;
+                next_check_at = self.config.array_chunk_size;
+                continue;
+            }
+
+            // Estimate bytes/record and predict how many more records fit
+            // before crossing the threshold, so the next check happens
+            // there instead of on every subsequent item.
+            let avg_bytes_per_record = measured_bytes as f64 / items_since_boundary as f64;
+            let remaining_bytes = self
+                .config
+                .adaptive_chunk_byte_threshold
+                .saturating_sub(measured_bytes) as f64;
+            let predicted_additional_records =
+                (remaining_bytes / avg_bytes_per_record).floor().max(1.0) as usize;
+            next_check_at = items_since_boundary + predicted_additional_records;
+        }
+
+        Ok(())
+    }
+
+    /// Framed counterpart of [`Self::write_array_chunked`]: the same
+    /// adaptive boundary bookkeeping, but each boundary's worth of items is
+    /// buffered and emitted as one `frame_tag`-tagged JSON-array frame
+    /// instead of written inline with manual comma placement, since a frame
+    /// must be a complete, independent value. An empty `items` still emits
+    /// one empty-array frame, so a framed consumer always sees exactly one
+    /// or more frames per section regardless of how much data it held.
+    fn write_array_chunked_framed<T: Serialize>(
+        &mut self,
+        items: &[T],
+        frame_tag: &str,
+    ) -> TrackingResult<()> {
+        let total_items = items.len();
+        let mut items_since_boundary: usize = 0;
+        let mut bytes_since_boundary: usize = 0;
+        let mut next_check_at: usize = self.config.array_chunk_size;
+        let mut pending_items: Vec<String> = Vec::new();
+
+        for (item_idx, item) in items.iter().enumerate() {
+            let item_json = self.serialize_value(item)?;
+            bytes_since_boundary += item_json.len();
+            pending_items.push(item_json);
+            items_since_boundary += 1;
+
+            let is_last_item = item_idx == total_items - 1;
+            let boundary_reached = if is_last_item {
+                true
+            } else if items_since_boundary < next_check_at {
+                false
+            } else if items_since_boundary < self.config.adaptive_chunk_min_check_count {
+                next_check_at = self.config.array_chunk_size;
+                true
+            } else {
+                let measured_bytes = self.estimate_chunk_bytes(bytes_since_boundary);
+                if measured_bytes >= self.config.adaptive_chunk_byte_threshold {
+                    next_check_at = self.config.array_chunk_size;
+                    true
+                } else {
+                    let avg_bytes_per_record = measured_bytes as f64 / items_since_boundary as f64;
+                    let remaining_bytes =
+                        self.config
+                            .adaptive_chunk_byte_threshold
+                            .saturating_sub(measured_bytes) as f64;
+                    let predicted_additional_records =
+                        (remaining_bytes / avg_bytes_per_record).floor().max(1.0) as usize;
+                    next_check_at = items_since_boundary + predicted_additional_records;
+                    false
+                }
+            };
+
+            if boundary_reached {
+                self.stats.chunks_written += 1;
+                let payload = format!("[{}]", pending_items.join(","));
+                self.emit_frame(frame_tag, &payload)?;
+                pending_items.clear();
+                items_since_boundary = 0;
+                bytes_since_boundary = 0;
             }
         }
 
+        if total_items == 0 {
+            self.stats.chunks_written += 1;
+            self.emit_frame(frame_tag, "[]")?;
+        }
+
         Ok(())
     }
 
+    /// Bytes to compare against `adaptive_chunk_byte_threshold`. There's no
+    /// cheap way to read the exact compressed byte count mid-stream without
+    /// an extra flush (which would defeat the point of batching flushes), so
+    /// with compression enabled this applies a fixed heuristic ratio to the
+    /// raw serialized size instead -- good enough to decide "is it about
+    /// time for a flush boundary", not meant to be exact.
+    fn estimate_chunk_bytes(&self, raw_bytes_since_boundary: usize) -> usize {
+        if self.config.enable_compression {
+            const ESTIMATED_COMPRESSION_RATIO: f64 = 0.5;
+            (raw_bytes_since_boundary as f64 * ESTIMATED_COMPRESSION_RATIO) as usize
+        } else {
+            raw_bytes_since_boundary
+        }
+    }
+
     /// Calculate severity breakdown for violations
     fn calculate_severity_breakdown<T: Serialize>(&self, _violations: &[T]) -> serde_json::Value {
         // Simplified implementation - in real scenario, would analyze violation types
@@ -507,6 +1983,7 @@ impl ExportMetadata {
                 pretty_print: false,
                 array_chunk_size: 1000,
             },
+            system_profile: None,
         }
     }
 
@@ -525,6 +2002,21 @@ impl ExportMetadata {
         };
         self
     }
+
+    /// Run [`SystemProfile::measure`] now and embed the result, so recorded
+    /// throughput can later be normalized against the machine that produced
+    /// it.
+    pub fn with_system_profile(self) -> Self {
+        self.with_system_profile_value(SystemProfile::measure())
+    }
+
+    /// Embed an already-measured profile instead of running the
+    /// micro-benchmarks again -- useful when exporting many documents
+    /// back-to-back on the same machine.
+    pub fn with_system_profile_value(mut self, profile: SystemProfile) -> Self {
+        self.system_profile = Some(profile);
+        self
+    }
 }
 
 /// Builder pattern for streaming writer configuration
@@ -553,6 +2045,13 @@ impl StreamingWriterConfigBuilder {
         self
     }
 
+    /// Select which streaming compressor `with_compression` wraps the sink
+    /// in (only takes effect once compression is enabled)
+    pub fn compression_format(mut self, format: CompressionFormat) -> Self {
+        self.config.compression_format = format;
+        self
+    }
+
     /// Enable pretty printing
     pub fn pretty_print(mut self) -> Self {
         self.config.pretty_print = true;
@@ -577,6 +2076,79 @@ impl StreamingWriterConfigBuilder {
         self
     }
 
+    /// Set how long a background-writer send may block before giving up
+    /// (only relevant when `non_blocking` is enabled)
+    pub fn background_write_deadline(mut self, deadline: Duration) -> Self {
+        self.config.background_write_deadline = deadline;
+        self
+    }
+
+    /// Set the byte-size threshold for the adaptive array-chunking heuristic
+    pub fn adaptive_chunk_byte_threshold(mut self, bytes: usize) -> Self {
+        self.config.adaptive_chunk_byte_threshold = bytes;
+        self
+    }
+
+    /// Set the minimum item count before the adaptive chunking heuristic
+    /// starts estimating from measured average record size
+    pub fn adaptive_chunk_min_check_count(mut self, count: usize) -> Self {
+        self.config.adaptive_chunk_min_check_count = count;
+        self
+    }
+
+    /// Set how non-finite (`NaN`/`Infinity`) floats are rewritten during
+    /// serialization
+    pub fn non_finite_policy(mut self, policy: NonFiniteFloatPolicy) -> Self {
+        self.config.non_finite_policy = policy;
+        self
+    }
+
+    /// Set whether output is one JSON object or a stream of framed sections
+    pub fn output_framing(mut self, framing: OutputFraming) -> Self {
+        self.config.output_framing = framing;
+        self
+    }
+
+    /// Set the size above which a serialized array item bypasses the
+    /// internal buffer for a vectored write
+    pub fn vectored_write_threshold(mut self, bytes: usize) -> Self {
+        self.config.vectored_write_threshold = bytes;
+        self
+    }
+
+    /// Write through a memory-mapped `path`, pre-sized to `initial_size`
+    /// bytes, instead of the buffered `W` sink -- see
+    /// [`StreamingWriterConfig::mmap_backend`]. The mapping grows in 64MB
+    /// extents by default; use [`Self::mmap_grow_extent`] to change that.
+    pub fn mmap_backend(mut self, path: impl Into<PathBuf>, initial_size: u64) -> Self {
+        self.config.mmap_backend = Some(MmapBackendConfig {
+            path: path.into(),
+            initial_size,
+            grow_extent: 64 * 1024 * 1024,
+        });
+        self
+    }
+
+    /// Override the growth extent used when the mmap backend's backing file
+    /// has to be resized (only takes effect once `mmap_backend` is set)
+    pub fn mmap_grow_extent(mut self, bytes: u64) -> Self {
+        if let Some(mmap_config) = &mut self.config.mmap_backend {
+            mmap_config.grow_extent = bytes;
+        }
+        self
+    }
+
+    /// Maintain a checkpoint sidecar file at `path`, so a crashed export can
+    /// be resumed with [`StreamingJsonWriter::resume_from`] instead of
+    /// starting over. Incompatible with [`Self::with_compression`]:
+    /// [`StreamingJsonWriter::with_config`] returns a `ConfigurationError`
+    /// if both are set on the built config, since resuming would truncate
+    /// and restart the compression frame mid-stream.
+    pub fn checkpoint_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.checkpoint_path = Some(path.into());
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> StreamingWriterConfig {
         self.config
@@ -592,14 +2164,14 @@ impl Default for StreamingWriterConfigBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Cursor;
     use crate::export::batch_processor::{
-        ProcessedUnsafeData, ProcessedFFIData, ProcessedBoundaryData,
-        BatchProcessingMetrics, UnsafeBlockInfo, RiskDistribution, ProcessedUnsafeAllocation,
-        UnsafePerformanceMetrics, LibraryInfo, HookStatistics, TransferPatterns,
-        BoundaryRiskAnalysis, ProcessedBoundaryEvent, BoundaryPerformanceImpact,
-        ProcessedFFIAllocation, FFIPerformanceMetrics
+        BatchProcessingMetrics, BoundaryPerformanceImpact, BoundaryRiskAnalysis,
+        FFIPerformanceMetrics, HookStatistics, LibraryInfo, ProcessedBoundaryData,
+        ProcessedBoundaryEvent, ProcessedFFIAllocation, ProcessedFFIData,
+        ProcessedUnsafeAllocation, ProcessedUnsafeData, RiskDistribution, TransferPatterns,
+        UnsafeBlockInfo, UnsafePerformanceMetrics,
     };
+    use std::io::Cursor;
 
     fn create_test_writer() -> StreamingJsonWriter<Cursor<Vec<u8>>> {
         let buffer = Vec::new();
@@ -607,7 +2179,9 @@ mod tests {
         StreamingJsonWriter::new(cursor).unwrap()
     }
 
-    fn create_test_writer_with_config(config: StreamingWriterConfig) -> StreamingJsonWriter<Cursor<Vec<u8>>> {
+    fn create_test_writer_with_config(
+        config: StreamingWriterConfig,
+    ) -> StreamingJsonWriter<Cursor<Vec<u8>>> {
         let buffer = Vec::new();
         let cursor = Cursor::new(buffer);
         StreamingJsonWriter::with_config(cursor, config).unwrap()
@@ -631,13 +2205,17 @@ mod tests {
             max_memory_before_flush: 32 * 1024 * 1024,
             non_blocking: false,
             array_chunk_size: 500,
+            background_write_deadline: Duration::from_secs(30),
+            adaptive_chunk_byte_threshold: 64 * 1024,
+            adaptive_chunk_min_check_count: 1500,
+            non_finite_policy: NonFiniteFloatPolicy::Null,
         };
-        
+
         let buffer = Vec::new();
         let cursor = Cursor::new(buffer);
         let writer = StreamingJsonWriter::with_config(cursor, config.clone());
         assert!(writer.is_ok());
-        
+
         let writer = writer.unwrap();
         assert_eq!(writer.config.buffer_size, config.buffer_size);
         assert_eq!(writer.config.enable_compression, config.enable_compression);
@@ -683,10 +2261,10 @@ mod tests {
     fn test_config_builder_default() {
         let builder1 = StreamingWriterConfigBuilder::new();
         let builder2 = StreamingWriterConfigBuilder::default();
-        
+
         let config1 = builder1.build();
         let config2 = builder2.build();
-        
+
         assert_eq!(config1.buffer_size, config2.buffer_size);
         assert_eq!(config1.enable_compression, config2.enable_compression);
     }
@@ -712,10 +2290,14 @@ mod tests {
             max_memory_before_flush: 64 * 1024 * 1024,
             non_blocking: true,
             array_chunk_size: 1500,
+            background_write_deadline: Duration::from_secs(30),
+            adaptive_chunk_byte_threshold: 64 * 1024,
+            adaptive_chunk_min_check_count: 1500,
+            non_finite_policy: NonFiniteFloatPolicy::Null,
         };
 
-        let metadata = ExportMetadata::for_unsafe_ffi_analysis("medium", "sequential")
-            .with_config(&config);
+        let metadata =
+            ExportMetadata::for_unsafe_ffi_analysis("medium", "sequential").with_config(&config);
 
         assert_eq!(metadata.export_config.buffer_size, 512 * 1024);
         assert!(metadata.export_config.compression_enabled);
@@ -728,10 +2310,10 @@ mod tests {
     fn test_write_header() {
         let mut writer = create_test_writer();
         let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
-        
+
         let result = writer.write_unsafe_ffi_header(&metadata);
         assert!(result.is_ok());
-        
+
         // Check that stats are updated
         let stats = writer.get_stats();
         assert!(stats.bytes_written > 0);
@@ -739,15 +2321,13 @@ mod tests {
 
     #[test]
     fn test_write_header_pretty_print() {
-        let config = StreamingWriterConfigBuilder::new()
-            .pretty_print()
-            .build();
+        let config = StreamingWriterConfigBuilder::new().pretty_print().build();
         let mut writer = create_test_writer_with_config(config);
         let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
-        
+
         let result = writer.write_unsafe_ffi_header(&metadata);
         assert!(result.is_ok());
-        
+
         let stats = writer.get_stats();
         assert!(stats.bytes_written > 0);
     }
@@ -768,38 +2348,34 @@ mod tests {
                 critical_risk: 5,
                 overall_risk_score: 6.5,
             },
-            unsafe_blocks: vec![
-                UnsafeBlockInfo {
-                    location: "test.rs:10".to_string(),
-                    allocation_count: 10,
-                    total_memory: 1024,
-                    risk_level: crate::analysis::unsafe_ffi_tracker::RiskLevel::High,
-                    functions_called: vec!["raw_pointer_deref".to_string()],
-                }
-            ],
-            allocations: vec![
-                ProcessedUnsafeAllocation {
-                    ptr: "0x1000".to_string(),
-                    size: 1024,
-                    type_name: Some("TestType".to_string()),
-                    unsafe_block_location: "test.rs:15".to_string(),
-                    call_stack: vec!["main".to_string(), "test_function".to_string()],
-                    risk_assessment: crate::analysis::unsafe_ffi_tracker::RiskAssessment {
-                        risk_level: crate::analysis::unsafe_ffi_tracker::RiskLevel::Medium,
-                        risk_factors: vec![],
-                        mitigation_suggestions: vec![],
-                        confidence_score: 0.8,
-                        assessment_timestamp: 0,
-                    },
-                    lifetime_info: crate::export::batch_processor::LifetimeInfo {
-                        allocated_at: 1000,
-                        deallocated_at: None,
-                        lifetime_ns: None,
-                        scope: "test_scope".to_string(),
-                    },
-                    memory_layout: None,
-                }
-            ],
+            unsafe_blocks: vec![UnsafeBlockInfo {
+                location: "test.rs:10".to_string(),
+                allocation_count: 10,
+                total_memory: 1024,
+                risk_level: crate::analysis::unsafe_ffi_tracker::RiskLevel::High,
+                functions_called: vec!["raw_pointer_deref".to_string()],
+            }],
+            allocations: vec![ProcessedUnsafeAllocation {
+                ptr: "0x1000".to_string(),
+                size: 1024,
+                type_name: Some("TestType".to_string()),
+                unsafe_block_location: "test.rs:15".to_string(),
+                call_stack: vec!["main".to_string(), "test_function".to_string()],
+                risk_assessment: crate::analysis::unsafe_ffi_tracker::RiskAssessment {
+                    risk_level: crate::analysis::unsafe_ffi_tracker::RiskLevel::Medium,
+                    risk_factors: vec![],
+                    mitigation_suggestions: vec![],
+                    confidence_score: 0.8,
+                    assessment_timestamp: 0,
+                },
+                lifetime_info: crate::export::batch_processor::LifetimeInfo {
+                    allocated_at: 1000,
+                    deallocated_at: None,
+                    lifetime_ns: None,
+                    scope: "test_scope".to_string(),
+                },
+                memory_layout: None,
+            }],
             performance_metrics: UnsafePerformanceMetrics {
                 processing_time_ms: 100,
                 memory_usage_bytes: 512,
@@ -810,7 +2386,7 @@ mod tests {
 
         let result = writer.write_unsafe_allocations_stream(&unsafe_data);
         assert!(result.is_ok());
-        
+
         let stats = writer.get_stats();
         assert!(stats.bytes_written > 0);
     }
@@ -824,55 +2400,51 @@ mod tests {
         let ffi_data = ProcessedFFIData {
             total_allocations: 50,
             total_memory: 512 * 1024,
-            libraries_involved: vec![
-                LibraryInfo {
-                    name: "libc".to_string(),
-                    allocation_count: 30,
-                    total_memory: 300 * 1024,
-                    functions_used: vec!["malloc".to_string(), "free".to_string()],
-                    avg_allocation_size: 10240,
-                }
-            ],
+            libraries_involved: vec![LibraryInfo {
+                name: "libc".to_string(),
+                allocation_count: 30,
+                total_memory: 300 * 1024,
+                functions_used: vec!["malloc".to_string(), "free".to_string()],
+                avg_allocation_size: 10240,
+            }],
             hook_statistics: HookStatistics {
                 total_hooks: 10,
                 success_rate: 0.9,
                 avg_overhead_ns: 1000.0,
                 methods_used: std::collections::HashMap::new(),
             },
-            allocations: vec![
-                ProcessedFFIAllocation {
-                    ptr: "0x2000".to_string(),
-                    size: 2048,
-                    library_name: "libc".to_string(),
-                    function_name: "malloc".to_string(),
-                    call_stack: vec!["main".to_string(), "ffi_function".to_string()],
-                    hook_info: crate::analysis::unsafe_ffi_tracker::LibCHookInfo {
-                        hook_method: crate::analysis::unsafe_ffi_tracker::HookMethod::DynamicLinker,
-                        original_function: "malloc".to_string(),
-                        hook_timestamp: 1000,
-                        allocation_metadata: crate::analysis::unsafe_ffi_tracker::AllocationMetadata {
-                            requested_size: 2048,
-                            actual_size: 2048,
-                            alignment: 8,
-                            allocator_info: "libc".to_string(),
-                            protection_flags: None,
-                        },
-                        hook_overhead_ns: Some(100),
+            allocations: vec![ProcessedFFIAllocation {
+                ptr: "0x2000".to_string(),
+                size: 2048,
+                library_name: "libc".to_string(),
+                function_name: "malloc".to_string(),
+                call_stack: vec!["main".to_string(), "ffi_function".to_string()],
+                hook_info: crate::analysis::unsafe_ffi_tracker::LibCHookInfo {
+                    hook_method: crate::analysis::unsafe_ffi_tracker::HookMethod::DynamicLinker,
+                    original_function: "malloc".to_string(),
+                    hook_timestamp: 1000,
+                    allocation_metadata: crate::analysis::unsafe_ffi_tracker::AllocationMetadata {
+                        requested_size: 2048,
+                        actual_size: 2048,
+                        alignment: 8,
+                        allocator_info: "libc".to_string(),
+                        protection_flags: None,
                     },
-                    ownership_info: crate::export::batch_processor::OwnershipInfo {
-                        owner_context: "FFI".to_string(),
-                        owner_function: "malloc".to_string(),
-                        transfer_timestamp: 1000,
-                        expected_lifetime: None,
-                    },
-                    interop_metadata: crate::export::batch_processor::InteropMetadata {
-                        marshalling_info: "C-compatible".to_string(),
-                        type_conversion: "Direct".to_string(),
-                        performance_impact: "Low".to_string(),
-                        safety_considerations: vec!["Manual memory management".to_string()],
-                    },
-                }
-            ],
+                    hook_overhead_ns: Some(100),
+                },
+                ownership_info: crate::export::batch_processor::OwnershipInfo {
+                    owner_context: "FFI".to_string(),
+                    owner_function: "malloc".to_string(),
+                    transfer_timestamp: 1000,
+                    expected_lifetime: None,
+                },
+                interop_metadata: crate::export::batch_processor::InteropMetadata {
+                    marshalling_info: "C-compatible".to_string(),
+                    type_conversion: "Direct".to_string(),
+                    performance_impact: "Low".to_string(),
+                    safety_considerations: vec!["Manual memory management".to_string()],
+                },
+            }],
             performance_metrics: FFIPerformanceMetrics {
                 processing_time_ms: 50,
                 memory_usage_bytes: 256,
@@ -883,7 +2455,7 @@ mod tests {
 
         let result = writer.write_ffi_allocations_stream(&ffi_data);
         assert!(result.is_ok());
-        
+
         let stats = writer.get_stats();
         assert!(stats.bytes_written > 0);
     }
@@ -913,25 +2485,23 @@ mod tests {
                 common_risk_patterns: vec!["Unvalidated pointer transfer".to_string()],
                 mitigation_recommendations: vec!["Add validation".to_string()],
             },
-            events: vec![
-                ProcessedBoundaryEvent {
-                    event_id: "boundary_1".to_string(),
-                    event_type: "safe_to_unsafe".to_string(),
-                    timestamp: 1234567890,
-                    from_context: crate::export::batch_processor::ContextInfo {
-                        name: "Rust".to_string(),
-                        function: "main".to_string(),
-                        metadata: std::collections::HashMap::new(),
-                    },
-                    to_context: crate::export::batch_processor::ContextInfo {
-                        name: "FFI".to_string(),
-                        function: "malloc".to_string(),
-                        metadata: std::collections::HashMap::new(),
-                    },
-                    memory_passport: None,
-                    risk_factors: vec!["raw_pointer".to_string()],
-                }
-            ],
+            events: vec![ProcessedBoundaryEvent {
+                event_id: "boundary_1".to_string(),
+                event_type: "safe_to_unsafe".to_string(),
+                timestamp: 1234567890,
+                from_context: crate::export::batch_processor::ContextInfo {
+                    name: "Rust".to_string(),
+                    function: "main".to_string(),
+                    metadata: std::collections::HashMap::new(),
+                },
+                to_context: crate::export::batch_processor::ContextInfo {
+                    name: "FFI".to_string(),
+                    function: "malloc".to_string(),
+                    metadata: std::collections::HashMap::new(),
+                },
+                memory_passport: None,
+                risk_factors: vec!["raw_pointer".to_string()],
+            }],
             performance_impact: BoundaryPerformanceImpact {
                 total_processing_time_ms: 100,
                 avg_crossing_time_ns: 5000.0,
@@ -942,7 +2512,7 @@ mod tests {
 
         let result = writer.write_boundary_events_stream(&boundary_data);
         assert!(result.is_ok());
-        
+
         let stats = writer.get_stats();
         assert!(stats.bytes_written > 0);
     }
@@ -975,7 +2545,7 @@ mod tests {
 
         let result = writer.write_safety_violations_stream(&violations);
         assert!(result.is_ok());
-        
+
         let stats = writer.get_stats();
         assert!(stats.bytes_written > 0);
     }
@@ -999,11 +2569,58 @@ mod tests {
 
         let result = writer.write_processing_metrics(&metrics);
         assert!(result.is_ok());
-        
+
         let stats = writer.get_stats();
         assert!(stats.bytes_written > 0);
     }
 
+    #[test]
+    fn test_non_finite_metrics_are_sanitized_and_counted() {
+        let mut writer = create_test_writer();
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+
+        // throughput_items_per_sec would be NaN if a batch processed in zero
+        // measured time produced a divide-by-zero upstream.
+        let metrics = BatchProcessingMetrics {
+            total_items: 0,
+            batch_count: 0,
+            total_processing_time_ms: 0,
+            avg_batch_time_ms: 0.0,
+            peak_memory_usage_bytes: 0,
+            parallel_processing_used: false,
+            threads_used: 1,
+            throughput_items_per_sec: f64::NAN,
+        };
+
+        writer.write_processing_metrics(&metrics).unwrap();
+        assert_eq!(writer.get_stats().non_finite_values_rewritten, 1);
+    }
+
+    #[test]
+    fn test_non_finite_sentinel_policy_is_honored_end_to_end() {
+        let config = StreamingWriterConfigBuilder::new()
+            .non_finite_policy(NonFiniteFloatPolicy::Sentinel(-1.0))
+            .build();
+        let mut writer = create_test_writer_with_config(config);
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+
+        let metrics = BatchProcessingMetrics {
+            total_items: 0,
+            batch_count: 0,
+            total_processing_time_ms: 0,
+            avg_batch_time_ms: 0.0,
+            peak_memory_usage_bytes: 0,
+            parallel_processing_used: false,
+            threads_used: 1,
+            throughput_items_per_sec: f64::INFINITY,
+        };
+
+        writer.write_processing_metrics(&metrics).unwrap();
+        assert_eq!(writer.get_stats().non_finite_values_rewritten, 1);
+    }
+
     #[test]
     fn test_finalize() {
         let mut writer = create_test_writer();
@@ -1012,7 +2629,7 @@ mod tests {
 
         let result = writer.finalize();
         assert!(result.is_ok());
-        
+
         let stats = result.unwrap();
         assert!(stats.bytes_written > 0);
         assert!(stats.flush_count > 0);
@@ -1027,16 +2644,69 @@ mod tests {
         // First finalize should succeed
         let result1 = writer.finalize();
         assert!(result1.is_ok());
-        
+
         // Second finalize should return the same stats (idempotent)
         let result2 = writer.finalize();
         assert!(result2.is_ok());
-        
+
         let stats1 = result1.unwrap();
         let stats2 = result2.unwrap();
         assert_eq!(stats1.bytes_written, stats2.bytes_written);
     }
 
+    #[test]
+    fn test_finalize_records_content_hash_and_verifies() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let mut writer = StreamingJsonWriter::new(SharedBufferWriter(buffer.clone())).unwrap();
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        let stats = writer.finalize().unwrap();
+
+        assert!(!stats.content_hash.is_empty());
+
+        let written = buffer.lock().unwrap().clone();
+        assert!(verify_streaming_export_integrity(&written, None).unwrap());
+    }
+
+    #[test]
+    fn test_verify_streaming_export_integrity_detects_corruption() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let mut writer = StreamingJsonWriter::new(SharedBufferWriter(buffer.clone())).unwrap();
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        writer.finalize().unwrap();
+
+        let mut written = buffer.lock().unwrap().clone();
+        assert!(verify_streaming_export_integrity(&written, None).unwrap());
+
+        // Flip a byte well inside the body (long before the trailer) and
+        // confirm the recomputed digest no longer matches.
+        written[10] ^= 0xFF;
+        assert!(!verify_streaming_export_integrity(&written, None).unwrap());
+    }
+
+    #[test]
+    fn test_verify_streaming_export_integrity_works_with_compression() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let config = StreamingWriterConfigBuilder::new()
+            .with_compression(3)
+            .build();
+        let mut writer =
+            StreamingJsonWriter::with_config(SharedBufferWriter(buffer.clone()), config).unwrap();
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        writer.finalize().unwrap();
+
+        let written = buffer.lock().unwrap().clone();
+        assert!(verify_streaming_export_integrity(&written, Some(CompressionFormat::Zstd)).unwrap());
+    }
+
+    #[test]
+    fn test_verify_streaming_export_integrity_errors_without_trailer() {
+        let result = verify_streaming_export_integrity(b"{\"no\": \"trailer\"}", None);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_write_after_finalize() {
         let mut writer = create_test_writer();
@@ -1047,7 +2717,7 @@ mod tests {
         // Writing after finalize should fail
         let result = writer.write_unsafe_ffi_header(&metadata);
         assert!(result.is_err());
-        
+
         if let Err(TrackingError::InvalidOperation(msg)) = result {
             assert!(msg.contains("finalized"));
         } else {
@@ -1059,10 +2729,10 @@ mod tests {
     fn test_flush() {
         let mut writer = create_test_writer();
         let initial_flush_count = writer.get_stats().flush_count;
-        
+
         let result = writer.flush();
         assert!(result.is_ok());
-        
+
         let stats = writer.get_stats();
         assert_eq!(stats.flush_count, initial_flush_count + 1);
     }
@@ -1071,7 +2741,7 @@ mod tests {
     fn test_get_stats() {
         let writer = create_test_writer();
         let stats = writer.get_stats();
-        
+
         assert_eq!(stats.bytes_written, 0);
         assert_eq!(stats.flush_count, 0);
         assert_eq!(stats.total_write_time_ms, 0);
@@ -1079,19 +2749,21 @@ mod tests {
         assert_eq!(stats.peak_memory_usage, 0);
         assert_eq!(stats.chunks_written, 0);
         assert_eq!(stats.compression_ratio, None);
+        assert_eq!(stats.queued_buffers, 0);
     }
 
     #[test]
     fn test_memory_flush_threshold() {
         let config = StreamingWriterConfigBuilder::new()
             .max_memory_before_flush(100) // Very small threshold
+            .non_blocking(false) // exercise the direct-mode eager-flush path
             .build();
         let mut writer = create_test_writer_with_config(config);
-        
+
         let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
         let result = writer.write_unsafe_ffi_header(&metadata);
         assert!(result.is_ok());
-        
+
         // Should have triggered flush due to small threshold
         let stats = writer.get_stats();
         assert!(stats.flush_count > 0);
@@ -1103,7 +2775,7 @@ mod tests {
             .array_chunk_size(2) // Small chunk size for testing
             .build();
         let mut writer = create_test_writer_with_config(config);
-        
+
         let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
         writer.write_unsafe_ffi_header(&metadata).unwrap();
 
@@ -1114,25 +2786,115 @@ mod tests {
         }
 
         let items = vec![
-            TestItem { id: 1, value: "test1".to_string() },
-            TestItem { id: 2, value: "test2".to_string() },
-            TestItem { id: 3, value: "test3".to_string() },
-            TestItem { id: 4, value: "test4".to_string() },
-            TestItem { id: 5, value: "test5".to_string() },
-        ];
-
-        let violations = items;
-        let result = writer.write_safety_violations_stream(&violations);
+            TestItem {
+                id: 1,
+                value: "test1".to_string(),
+            },
+            TestItem {
+                id: 2,
+                value: "test2".to_string(),
+            },
+            TestItem {
+                id: 3,
+                value: "test3".to_string(),
+            },
+            TestItem {
+                id: 4,
+                value: "test4".to_string(),
+            },
+            TestItem {
+                id: 5,
+                value: "test5".to_string(),
+            },
+        ];
+
+        let violations = items;
+        let result = writer.write_safety_violations_stream(&violations);
         assert!(result.is_ok());
-        
+
         let stats = writer.get_stats();
         assert!(stats.chunks_written > 1); // Should have multiple chunks
     }
 
+    /// A writer whose contents can be inspected after the fact, for tests
+    /// that need to check the bytes actually produced rather than just the
+    /// reported stats.
+    #[derive(Clone)]
+    struct SharedBufferWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBufferWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_adaptive_chunk_byte_threshold_suppresses_small_item_flushing() {
+        // Item-count chunking alone would flush every 5 items (10 chunks for
+        // 50 items). With a byte threshold far above what 50 tiny integers
+        // ever add up to, the adaptive check should find there's no need to
+        // flush at all until the final item.
+        let config = StreamingWriterConfigBuilder::new()
+            .array_chunk_size(5)
+            .adaptive_chunk_min_check_count(5)
+            .adaptive_chunk_byte_threshold(100_000)
+            .non_blocking(false)
+            .build();
+        let mut writer = create_test_writer_with_config(config);
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+
+        let violations: Vec<u32> = (0..50).collect();
+        writer.write_safety_violations_stream(&violations).unwrap();
+
+        let stats = writer.get_stats();
+        assert_eq!(
+            stats.chunks_written, 1,
+            "byte-size-aware chunking should have skipped every fixed-size-only boundary"
+        );
+    }
+
+    #[test]
+    fn test_adaptive_chunking_comma_placement_stays_correct_across_boundaries() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let config = StreamingWriterConfigBuilder::new()
+            .array_chunk_size(3)
+            .adaptive_chunk_min_check_count(3)
+            .adaptive_chunk_byte_threshold(1) // force a flush at every check point
+            .non_blocking(false)
+            .build();
+        let mut writer =
+            StreamingJsonWriter::with_config(SharedBufferWriter(buffer.clone()), config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+
+        let violations: Vec<u32> = (0..20).collect();
+        writer.write_safety_violations_stream(&violations).unwrap();
+        writer.finalize().unwrap();
+
+        let written = buffer.lock().unwrap().clone();
+        let output = String::from_utf8(written).expect("writer output is valid utf8");
+
+        let array_start = output.find("\"violations\": [\n").unwrap() + "\"violations\": [\n".len();
+        let array_end = array_start + output[array_start..].find("]\n").unwrap();
+        let array_body = &output[array_start..array_end];
+
+        let parsed: Vec<u32> = serde_json::from_str(&format!("[{array_body}]"))
+            .expect("array body must be valid JSON despite multiple adaptive flush boundaries");
+        assert_eq!(parsed, violations);
+    }
+
     #[test]
     fn test_streaming_writer_config_default() {
         let config = StreamingWriterConfig::default();
-        
+
         assert_eq!(config.buffer_size, 256 * 1024);
         assert!(!config.enable_compression);
         assert_eq!(config.compression_level, 6);
@@ -1140,6 +2902,13 @@ mod tests {
         assert_eq!(config.max_memory_before_flush, 64 * 1024 * 1024);
         assert!(config.non_blocking);
         assert_eq!(config.array_chunk_size, 1000);
+        assert_eq!(config.background_write_deadline, Duration::from_secs(30));
+        assert_eq!(config.adaptive_chunk_byte_threshold, 64 * 1024);
+        assert_eq!(config.adaptive_chunk_min_check_count, 1500);
+        assert_eq!(config.non_finite_policy, NonFiniteFloatPolicy::Null);
+        assert_eq!(config.output_framing, OutputFraming::SingleObject);
+        assert_eq!(config.vectored_write_threshold, 8 * 1024);
+        assert_eq!(config.compression_format, CompressionFormat::Zstd);
     }
 
     #[test]
@@ -1152,31 +2921,735 @@ mod tests {
             peak_memory_usage: 2048,
             chunks_written: 3,
             compression_ratio: Some(0.75),
+            queued_buffers: 0,
+            non_finite_values_rewritten: 0,
+            content_hash: "abc123".to_string(),
         };
 
         let json = serde_json::to_string(&stats);
         assert!(json.is_ok());
-        
+
         let deserialized: Result<StreamingStats, _> = serde_json::from_str(&json.unwrap());
         assert!(deserialized.is_ok());
-        
+
         let deserialized_stats = deserialized.unwrap();
         assert_eq!(deserialized_stats.bytes_written, stats.bytes_written);
-        assert_eq!(deserialized_stats.compression_ratio, stats.compression_ratio);
+        assert_eq!(
+            deserialized_stats.compression_ratio,
+            stats.compression_ratio
+        );
     }
 
     #[test]
     fn test_export_metadata_serialization() {
         let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
-        
+
         let json = serde_json::to_string(&metadata);
         assert!(json.is_ok());
-        
+
         let deserialized: Result<ExportMetadata, _> = serde_json::from_str(&json.unwrap());
         assert!(deserialized.is_ok());
-        
+
         let deserialized_metadata = deserialized.unwrap();
         assert_eq!(deserialized_metadata.analysis_type, metadata.analysis_type);
-        assert_eq!(deserialized_metadata.schema_version, metadata.schema_version);
+        assert_eq!(
+            deserialized_metadata.schema_version,
+            metadata.schema_version
+        );
+        assert!(deserialized_metadata.system_profile.is_none());
+    }
+
+    #[test]
+    fn test_export_metadata_without_system_profile_deserializes_old_documents() {
+        // A document written before `system_profile` existed has no such
+        // field at all; `#[serde(default)]` should still let it parse.
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        let mut value = serde_json::to_value(&metadata).unwrap();
+        value.as_object_mut().unwrap().remove("system_profile");
+
+        let deserialized: ExportMetadata = serde_json::from_value(value).unwrap();
+        assert!(deserialized.system_profile.is_none());
+    }
+
+    #[test]
+    fn test_with_system_profile_measures_and_round_trips() {
+        let metadata =
+            ExportMetadata::for_unsafe_ffi_analysis("high", "parallel").with_system_profile();
+        let profile = metadata.system_profile.as_ref().expect("profile recorded");
+
+        assert!(profile.logical_cores >= 1);
+        assert!(profile.physical_cores >= 1);
+        assert!(profile.compute_score_ops_per_sec > 0.0);
+        assert!(profile.memory_bandwidth_bytes_per_sec > 0.0);
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        let deserialized: ExportMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized.system_profile.unwrap().logical_cores,
+            profile.logical_cores
+        );
+    }
+
+    #[test]
+    fn test_with_system_profile_value_reuses_a_measurement_without_rerunning_it() {
+        let profile = SystemProfile::measure();
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel")
+            .with_system_profile_value(profile.clone());
+
+        assert_eq!(
+            metadata.system_profile.unwrap().compute_score_ops_per_sec,
+            profile.compute_score_ops_per_sec
+        );
+    }
+
+    #[test]
+    fn test_compressed_output_is_valid_zstd_and_round_trips() {
+        let config = StreamingWriterConfigBuilder::new()
+            .with_compression(3)
+            .build();
+        let buffer = Vec::new();
+        let cursor = Cursor::new(buffer);
+        let mut writer = StreamingJsonWriter::with_config(cursor, config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        let stats = writer.finalize().unwrap();
+
+        assert!(stats.compression_ratio.is_some());
+    }
+
+    #[test]
+    fn test_gzip_compressed_output_is_valid_gzip_and_verifies() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let config = StreamingWriterConfigBuilder::new()
+            .with_compression(6)
+            .compression_format(CompressionFormat::Gzip)
+            .non_blocking(false)
+            .build();
+        let mut writer =
+            StreamingJsonWriter::with_config(SharedBufferWriter(buffer.clone()), config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        let violations = vec!["repeated-violation-text".to_string(); 200];
+        writer.write_safety_violations_stream(&violations).unwrap();
+        let stats = writer.finalize().unwrap();
+
+        let written = buffer.lock().unwrap().clone();
+        // A real gzip stream starts with the two-byte magic number.
+        assert_eq!(&written[0..2], &[0x1f, 0x8b]);
+        assert!(stats.compression_ratio.unwrap() < 1.0);
+        assert!(verify_streaming_export_integrity(&written, Some(CompressionFormat::Gzip)).unwrap());
+    }
+
+    #[test]
+    fn test_compression_disabled_reports_no_ratio() {
+        let mut writer = create_test_writer();
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+
+        let stats = writer.finalize().unwrap();
+        assert_eq!(stats.compression_ratio, None);
+    }
+
+    #[test]
+    fn test_compressed_writer_shrinks_repetitive_content() {
+        let config = StreamingWriterConfigBuilder::new()
+            .with_compression(9)
+            .build();
+        let buffer = Vec::new();
+        let cursor = Cursor::new(buffer);
+        let mut writer = StreamingJsonWriter::with_config(cursor, config).unwrap();
+
+        // Highly repetitive payload so the compressed output is smaller than
+        // the uncompressed bytes fed in, keeping the ratio well under 1.0.
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        for _ in 0..200 {
+            writer
+                .write_processing_metrics(&BatchProcessingMetrics {
+                    total_items: 1,
+                    batch_count: 1,
+                    total_processing_time_ms: 1,
+                    avg_batch_time_ms: 1.0,
+                    peak_memory_usage_bytes: 1,
+                    parallel_processing_used: false,
+                    threads_used: 1,
+                    throughput_items_per_sec: 1.0,
+                })
+                .unwrap();
+        }
+        let stats = writer.finalize().unwrap();
+
+        let ratio = stats.compression_ratio.expect("compression enabled");
+        assert!(
+            ratio < 1.0,
+            "expected compression to shrink repetitive output, got ratio {ratio}"
+        );
+    }
+
+    #[test]
+    fn test_finalize_closes_compression_frame_exactly_once() {
+        let config = StreamingWriterConfigBuilder::new()
+            .with_compression(1)
+            .build();
+        let buffer = Vec::new();
+        let cursor = Cursor::new(buffer);
+        let mut writer = StreamingJsonWriter::with_config(cursor, config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+
+        let result1 = writer.finalize();
+        assert!(result1.is_ok());
+
+        // Second finalize must not try to close the frame again -- it
+        // should just return the already-computed stats.
+        let result2 = writer.finalize();
+        assert!(result2.is_ok());
+        assert_eq!(
+            result1.unwrap().compression_ratio,
+            result2.unwrap().compression_ratio
+        );
+    }
+
+    #[test]
+    fn test_flush_on_compressed_writer_keeps_stream_decodable() {
+        let config = StreamingWriterConfigBuilder::new()
+            .with_compression(3)
+            .build();
+        let buffer = Vec::new();
+        let cursor = Cursor::new(buffer);
+        let mut writer = StreamingJsonWriter::with_config(cursor, config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+
+        // A mid-stream flush must succeed without finishing the frame, so
+        // writing can continue afterwards.
+        assert!(writer.flush().is_ok());
+        assert!(writer
+            .write_processing_metrics(&BatchProcessingMetrics {
+                total_items: 1,
+                batch_count: 1,
+                total_processing_time_ms: 1,
+                avg_batch_time_ms: 1.0,
+                peak_memory_usage_bytes: 1,
+                parallel_processing_used: false,
+                threads_used: 1,
+                throughput_items_per_sec: 1.0,
+            })
+            .is_ok());
+
+        assert!(writer.finalize().is_ok());
+    }
+
+    #[test]
+    fn test_non_blocking_writer_delivers_all_bytes_via_background_thread() {
+        let config = StreamingWriterConfigBuilder::new()
+            .non_blocking(true)
+            .build();
+        let buffer = Vec::new();
+        let cursor = Cursor::new(buffer);
+        let mut writer = StreamingJsonWriter::with_config(cursor, config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        let stats = writer.finalize().unwrap();
+
+        assert!(stats.bytes_written > 0);
+        assert_eq!(stats.queued_buffers, 0);
+    }
+
+    #[test]
+    fn test_non_blocking_flush_drains_queue_and_reports_zero_backlog() {
+        let config = StreamingWriterConfigBuilder::new()
+            .non_blocking(true)
+            .build();
+        let buffer = Vec::new();
+        let cursor = Cursor::new(buffer);
+        let mut writer = StreamingJsonWriter::with_config(cursor, config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        writer.flush().unwrap();
+
+        // A synchronous flush() must wait for the background thread to
+        // drain, so the backlog is empty immediately after it returns.
+        assert_eq!(writer.get_stats().queued_buffers, 0);
+
+        assert!(writer.finalize().is_ok());
+    }
+
+    #[test]
+    fn test_non_blocking_writer_is_a_drop_in_for_direct_mode() {
+        // Same sequence of calls should succeed whether or not the
+        // background writer is in the loop.
+        for non_blocking in [true, false] {
+            let config = StreamingWriterConfigBuilder::new()
+                .non_blocking(non_blocking)
+                .build();
+            let buffer = Vec::new();
+            let cursor = Cursor::new(buffer);
+            let mut writer = StreamingJsonWriter::with_config(cursor, config).unwrap();
+
+            let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+            writer.write_unsafe_ffi_header(&metadata).unwrap();
+            let stats = writer.finalize().unwrap();
+            assert!(stats.bytes_written > 0);
+        }
+    }
+
+    #[test]
+    fn test_background_write_deadline_is_configurable() {
+        let config = StreamingWriterConfigBuilder::new()
+            .non_blocking(true)
+            .background_write_deadline(Duration::from_millis(500))
+            .build();
+        assert_eq!(config.background_write_deadline, Duration::from_millis(500));
+
+        let buffer = Vec::new();
+        let cursor = Cursor::new(buffer);
+        let mut writer = StreamingJsonWriter::with_config(cursor, config).unwrap();
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        assert!(writer.finalize().is_ok());
+    }
+
+    #[test]
+    fn test_ndjson_framing_emits_one_frame_per_line_and_preserves_items() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let config = StreamingWriterConfigBuilder::new()
+            .output_framing(OutputFraming::Framed(FrameEncoding::Ndjson))
+            .non_blocking(false)
+            .build();
+        let mut writer =
+            StreamingJsonWriter::with_config(SharedBufferWriter(buffer.clone()), config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        let violations: Vec<u32> = (0..5).collect();
+        writer.write_safety_violations_stream(&violations).unwrap();
+        writer.finalize().unwrap();
+
+        let written = buffer.lock().unwrap().clone();
+        let output = String::from_utf8(written).expect("NDJSON framing is valid utf8");
+
+        let frames: Vec<serde_json::Value> = output
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_str(line).expect("each NDJSON line is one JSON frame"))
+            .collect();
+
+        let tags: Vec<&str> = frames
+            .iter()
+            .map(|frame| frame["frame"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                "metadata",
+                "safety_violations.summary",
+                "safety_violations.violations",
+                "data_integrity",
+            ]
+        );
+
+        let parsed: Vec<u32> = serde_json::from_value(frames[2]["payload"].clone()).unwrap();
+        assert_eq!(parsed, violations);
+    }
+
+    #[test]
+    fn test_length_prefixed_framing_round_trips_via_varint_length() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let config = StreamingWriterConfigBuilder::new()
+            .output_framing(OutputFraming::Framed(FrameEncoding::LengthPrefixed))
+            .non_blocking(false)
+            .build();
+        let mut writer =
+            StreamingJsonWriter::with_config(SharedBufferWriter(buffer.clone()), config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        let violations: Vec<u32> = (0..5).collect();
+        writer.write_safety_violations_stream(&violations).unwrap();
+        writer.finalize().unwrap();
+
+        let written = buffer.lock().unwrap().clone();
+
+        // Walk the stream by decoding each varint length, then reading
+        // exactly that many bytes -- a reader should never need to scan for
+        // a delimiter the way NDJSON requires.
+        let mut offset = 0;
+        let mut frames = Vec::new();
+        while offset < written.len() {
+            let mut value: u64 = 0;
+            let mut shift = 0;
+            loop {
+                let byte = written[offset];
+                offset += 1;
+                value |= ((byte & 0x7F) as u64) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                shift += 7;
+            }
+            let frame_len = value as usize;
+            let frame_bytes = &written[offset..offset + frame_len];
+            offset += frame_len;
+            frames.push(serde_json::from_slice::<serde_json::Value>(frame_bytes).unwrap());
+        }
+
+        let tags: Vec<&str> = frames
+            .iter()
+            .map(|frame| frame["frame"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                "metadata",
+                "safety_violations.summary",
+                "safety_violations.violations",
+                "data_integrity",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_streaming_export_integrity_works_with_framed_output() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let config = StreamingWriterConfigBuilder::new()
+            .output_framing(OutputFraming::Framed(FrameEncoding::Ndjson))
+            .non_blocking(false)
+            .build();
+        let mut writer =
+            StreamingJsonWriter::with_config(SharedBufferWriter(buffer.clone()), config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        writer.finalize().unwrap();
+
+        let written = buffer.lock().unwrap().clone();
+        assert!(verify_streaming_export_integrity(&written, None).unwrap());
+    }
+
+    #[test]
+    fn test_large_array_items_use_vectored_write_path_and_stay_well_formed() {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let config = StreamingWriterConfigBuilder::new()
+            .vectored_write_threshold(16) // trivially small so the payload below qualifies
+            .non_blocking(false)
+            .build();
+        let mut writer =
+            StreamingJsonWriter::with_config(SharedBufferWriter(buffer.clone()), config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        let violations = vec!["a-fairly-long-violation-payload".to_string(); 3];
+        writer.write_safety_violations_stream(&violations).unwrap();
+        writer.finalize().unwrap();
+
+        let written = buffer.lock().unwrap().clone();
+        let output = String::from_utf8(written.clone()).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let parsed: Vec<String> =
+            serde_json::from_value(document["safety_violations"]["violations"].clone()).unwrap();
+        assert_eq!(parsed, violations);
+        assert!(verify_streaming_export_integrity(&written, None).unwrap());
+    }
+
+    #[test]
+    fn test_small_array_items_skip_vectored_write_path() {
+        // With the default 8KB threshold, ordinary small test items never
+        // qualify, so this should behave exactly like `test_array_chunking`.
+        let config = StreamingWriterConfigBuilder::new()
+            .array_chunk_size(2)
+            .build();
+        let mut writer = create_test_writer_with_config(config);
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+
+        let violations: Vec<u32> = (0..5).collect();
+        let result = writer.write_safety_violations_stream(&violations);
+        assert!(result.is_ok());
+        assert!(writer.finalize().is_ok());
+    }
+
+    #[test]
+    fn test_mmap_backend_writes_through_to_backing_file_and_verifies() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.json");
+        let config = StreamingWriterConfigBuilder::new()
+            .mmap_backend(path.clone(), 4096)
+            .non_blocking(false)
+            .build();
+        // The mmap backend ignores the passed-in writer entirely.
+        let mut writer =
+            StreamingJsonWriter::with_config(Cursor::new(Vec::new()), config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        let violations = vec!["a-violation".to_string(); 10];
+        writer.write_safety_violations_stream(&violations).unwrap();
+        let stats = writer.finalize().unwrap();
+
+        let written = std::fs::read(&path).unwrap();
+        assert_eq!(written.len() as u64, stats.bytes_written);
+        let document: serde_json::Value = serde_json::from_slice(&written).unwrap();
+        let parsed: Vec<String> =
+            serde_json::from_value(document["safety_violations"]["violations"].clone()).unwrap();
+        assert_eq!(parsed, violations);
+        assert!(verify_streaming_export_integrity(&written, None).unwrap());
+    }
+
+    #[test]
+    fn test_mmap_backend_grows_backing_file_past_initial_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.json");
+        let config = StreamingWriterConfigBuilder::new()
+            // Deliberately tiny, so even the metadata header forces a regrow.
+            .mmap_backend(path.clone(), 16)
+            .mmap_grow_extent(64)
+            .non_blocking(false)
+            .build();
+        let mut writer =
+            StreamingJsonWriter::with_config(Cursor::new(Vec::new()), config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        let violations = vec!["another-violation".to_string(); 50];
+        writer.write_safety_violations_stream(&violations).unwrap();
+        let stats = writer.finalize().unwrap();
+
+        assert!(stats.peak_memory_usage as u64 > 16);
+        let written = std::fs::read(&path).unwrap();
+        assert_eq!(written.len() as u64, stats.bytes_written);
+        assert!(verify_streaming_export_integrity(&written, None).unwrap());
+    }
+
+    fn sample_unsafe_data() -> ProcessedUnsafeData {
+        ProcessedUnsafeData {
+            total_allocations: 1,
+            total_memory: 1024,
+            risk_distribution: RiskDistribution {
+                low_risk: 1,
+                medium_risk: 0,
+                high_risk: 0,
+                critical_risk: 0,
+                overall_risk_score: 1.0,
+            },
+            unsafe_blocks: vec![],
+            allocations: vec![],
+            performance_metrics: UnsafePerformanceMetrics {
+                processing_time_ms: 1,
+                memory_usage_bytes: 1,
+                risk_assessments_performed: 1,
+                avg_risk_assessment_time_ns: 1.0,
+            },
+        }
+    }
+
+    fn sample_ffi_data() -> ProcessedFFIData {
+        ProcessedFFIData {
+            total_allocations: 0,
+            total_memory: 0,
+            libraries_involved: vec![],
+            hook_statistics: HookStatistics {
+                total_hooks: 0,
+                success_rate: 0.0,
+                avg_overhead_ns: 0.0,
+                methods_used: std::collections::HashMap::new(),
+            },
+            allocations: vec![],
+            performance_metrics: FFIPerformanceMetrics {
+                processing_time_ms: 0,
+                memory_usage_bytes: 0,
+                hook_operations_processed: 0,
+                avg_hook_processing_time_ns: 0.0,
+            },
+        }
+    }
+
+    fn sample_boundary_data() -> ProcessedBoundaryData {
+        ProcessedBoundaryData {
+            total_crossings: 0,
+            transfer_patterns: TransferPatterns {
+                dominant_direction: "none".to_string(),
+                frequency_by_type: std::collections::HashMap::new(),
+                avg_transfer_size: 0,
+                peak_activity_time: None,
+            },
+            risk_analysis: BoundaryRiskAnalysis {
+                overall_risk_score: 0.0,
+                high_risk_transfers: 0,
+                common_risk_patterns: vec![],
+                mitigation_recommendations: vec![],
+            },
+            events: vec![],
+            performance_impact: BoundaryPerformanceImpact {
+                total_processing_time_ms: 0,
+                avg_crossing_time_ns: 0.0,
+                overhead_percentage: 0.0,
+                optimization_opportunities: vec![],
+            },
+        }
+    }
+
+    fn sample_processing_metrics() -> BatchProcessingMetrics {
+        BatchProcessingMetrics {
+            total_items: 1,
+            batch_count: 1,
+            total_processing_time_ms: 1,
+            avg_batch_time_ms: 1.0,
+            peak_memory_usage_bytes: 1,
+            parallel_processing_used: false,
+            threads_used: 1,
+            throughput_items_per_sec: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_path_with_compression_is_rejected() {
+        // A checkpoint only records offsets into the raw output stream, but
+        // compression writes one continuous frame across the whole file:
+        // resuming would truncate and restart that frame mid-stream and
+        // corrupt the export, so the combination must be rejected up front.
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint_path = dir.path().join("export.checkpoint");
+        let config = StreamingWriterConfigBuilder::new()
+            .checkpoint_path(checkpoint_path)
+            .with_compression(3)
+            .non_blocking(false)
+            .build();
+        let output_path = dir.path().join("export.json");
+        let file = std::fs::File::create(&output_path).unwrap();
+
+        let result = StreamingJsonWriter::with_config(file, config);
+        assert!(matches!(result, Err(TrackingError::ConfigurationError(_))));
+    }
+
+    #[test]
+    fn test_checkpoint_records_section_offsets_and_finalize_folds_offset_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_path = dir.path().join("export.json");
+        let checkpoint_path = dir.path().join("export.checkpoint");
+        let config = StreamingWriterConfigBuilder::new()
+            .checkpoint_path(checkpoint_path.clone())
+            .non_blocking(false)
+            .build();
+        let file = std::fs::File::create(&output_path).unwrap();
+        let mut writer = StreamingJsonWriter::with_config(file, config).unwrap();
+
+        let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+        writer.write_unsafe_ffi_header(&metadata).unwrap();
+        writer
+            .write_safety_violations_stream(&["v".to_string()])
+            .unwrap();
+        let stats = writer.finalize().unwrap();
+
+        let checkpoint_contents = std::fs::read_to_string(&checkpoint_path).unwrap();
+        let entries: Vec<CheckpointEntry> = checkpoint_contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let sections: Vec<&str> = entries.iter().map(|e| e.section.as_str()).collect();
+        assert_eq!(sections, vec!["unsafe_ffi_header", "safety_violations"]);
+
+        let written = std::fs::read(&output_path).unwrap();
+        for entry in &entries {
+            let start = entry.offset as usize;
+            let end = start + entry.length as usize;
+            let actual_hash = blake3::hash(&written[start..end]).to_hex().to_string();
+            assert_eq!(actual_hash, entry.hash);
+        }
+
+        let document: serde_json::Value = serde_json::from_slice(&written).unwrap();
+        let offsets = &document["data_integrity"]["section_offsets"];
+        assert!(offsets["unsafe_ffi_header"]["offset"].is_u64());
+        assert!(offsets["safety_violations"]["length"].is_u64());
+        assert!(verify_streaming_export_integrity(&written, None).unwrap());
+        assert!(stats.bytes_written > 0);
+    }
+
+    #[test]
+    fn test_resume_from_skips_completed_sections_and_redrives_remaining() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_path = dir.path().join("export.json");
+        let checkpoint_path = dir.path().join("export.checkpoint");
+        let config = StreamingWriterConfigBuilder::new()
+            .checkpoint_path(checkpoint_path.clone())
+            .non_blocking(false)
+            .build();
+
+        // Simulate a crash partway through: write the first two canonical
+        // sections, flush (so the checkpoint sidecar is durable), then drop
+        // the writer without ever calling `finalize`.
+        {
+            let file = std::fs::File::create(&output_path).unwrap();
+            let mut writer = StreamingJsonWriter::with_config(file, config.clone()).unwrap();
+            let metadata = ExportMetadata::for_unsafe_ffi_analysis("high", "parallel");
+            writer.write_unsafe_ffi_header(&metadata).unwrap();
+            writer
+                .write_unsafe_allocations_stream(&sample_unsafe_data())
+                .unwrap();
+            writer.flush().unwrap();
+        }
+
+        let (mut writer, remaining) =
+            StreamingJsonWriter::resume_from(&output_path, config).unwrap();
+        assert_eq!(
+            remaining,
+            vec![
+                "ffi_allocations",
+                "boundary_events",
+                "safety_violations",
+                "processing_metrics",
+            ]
+        );
+
+        writer
+            .write_ffi_allocations_stream(&sample_ffi_data())
+            .unwrap();
+        writer
+            .write_boundary_events_stream(&sample_boundary_data())
+            .unwrap();
+        writer
+            .write_safety_violations_stream(&["v".to_string()])
+            .unwrap();
+        writer
+            .write_processing_metrics(&sample_processing_metrics())
+            .unwrap();
+        let stats = writer.finalize().unwrap();
+
+        let written = std::fs::read(&output_path).unwrap();
+        assert_eq!(written.len() as u64, stats.bytes_written);
+        assert!(verify_streaming_export_integrity(&written, None).unwrap());
+        let document: serde_json::Value = serde_json::from_slice(&written).unwrap();
+        assert!(document["unsafe_analysis"].is_object());
+        assert!(document["ffi_analysis"].is_object());
+        assert!(document["boundary_analysis"].is_object());
+        assert!(document["safety_violations"].is_object());
+        assert!(document["processing_metrics"].is_object());
+
+        let checkpoint_contents = std::fs::read_to_string(&checkpoint_path).unwrap();
+        let sections: Vec<String> = checkpoint_contents
+            .lines()
+            .map(|line| {
+                let entry: CheckpointEntry = serde_json::from_str(line).unwrap();
+                entry.section
+            })
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                "unsafe_ffi_header",
+                "unsafe_allocations",
+                "ffi_allocations",
+                "boundary_events",
+                "safety_violations",
+                "processing_metrics",
+            ]
+        );
     }
 }